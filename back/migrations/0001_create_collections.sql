@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS collections (
+  id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  slug VARCHAR(64) NOT NULL,
+  name VARCHAR(255) NOT NULL,
+  description TEXT NOT NULL,
+  chain VARCHAR(64) NULL,
+  contract_address CHAR(42) NULL,
+  base_uri VARCHAR(2048) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+    ON UPDATE CURRENT_TIMESTAMP(6),
+  UNIQUE KEY collections_slug (slug),
+  KEY collections_chain_contract (chain, contract_address)
+);
@@ -0,0 +1,48 @@
+CREATE TABLE IF NOT EXISTS indexed_contracts (
+  id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  chain VARCHAR(64) NOT NULL,
+  address CHAR(42) NOT NULL,
+  standard VARCHAR(16) NOT NULL,
+  start_block BIGINT UNSIGNED NOT NULL,
+  next_block BIGINT UNSIGNED NOT NULL,
+  -- Hash of block `next_block - 1` when it was scanned, to detect reorgs.
+  last_block_hash CHAR(66) NULL,
+  created_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+  updated_at DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6)
+    ON UPDATE CURRENT_TIMESTAMP(6),
+  UNIQUE KEY indexed_contracts_chain_address (chain, address)
+);
+
+CREATE TABLE IF NOT EXISTS token_transfers (
+  id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  contract_id BIGINT UNSIGNED NOT NULL,
+  block_number BIGINT UNSIGNED NOT NULL,
+  tx_hash CHAR(66) NOT NULL,
+  log_index INT UNSIGNED NOT NULL,
+  from_address CHAR(42) NOT NULL,
+  to_address CHAR(42) NOT NULL,
+  token_id VARCHAR(78) NULL,
+  value DECIMAL(65, 0) NULL,
+  UNIQUE KEY token_transfers_log (contract_id, block_number, log_index),
+  KEY token_transfers_from (contract_id, from_address),
+  KEY token_transfers_to (contract_id, to_address),
+  KEY token_transfers_token (contract_id, token_id),
+  CONSTRAINT token_transfers_contract FOREIGN KEY (contract_id)
+    REFERENCES indexed_contracts (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS token_approvals (
+  id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY,
+  contract_id BIGINT UNSIGNED NOT NULL,
+  block_number BIGINT UNSIGNED NOT NULL,
+  tx_hash CHAR(66) NOT NULL,
+  log_index INT UNSIGNED NOT NULL,
+  owner_address CHAR(42) NOT NULL,
+  spender_address CHAR(42) NOT NULL,
+  token_id VARCHAR(78) NULL,
+  value DECIMAL(65, 0) NULL,
+  UNIQUE KEY token_approvals_log (contract_id, block_number, log_index),
+  KEY token_approvals_owner (contract_id, owner_address),
+  CONSTRAINT token_approvals_contract FOREIGN KEY (contract_id)
+    REFERENCES indexed_contracts (id) ON DELETE CASCADE
+);
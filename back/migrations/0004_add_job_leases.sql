@@ -0,0 +1,2 @@
+ALTER TABLE jobs
+  ADD COLUMN lease_until DATETIME(6) NULL AFTER error;
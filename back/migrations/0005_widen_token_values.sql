@@ -0,0 +1,16 @@
+-- DECIMAL(65, 0) tops out below 2^256, so `type(uint256).max` approvals and
+-- large transfers failed to insert. Balances are summed in Rust instead.
+ALTER TABLE token_transfers MODIFY value VARCHAR(78) NULL;
+
+ALTER TABLE token_approvals MODIFY value VARCHAR(78) NULL;
+
+-- Hash of the last block of each stored batch, so a reorg can be walked back
+-- to the newest block that is still canonical.
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+  contract_id BIGINT UNSIGNED NOT NULL,
+  block_number BIGINT UNSIGNED NOT NULL,
+  block_hash CHAR(66) NOT NULL,
+  PRIMARY KEY (contract_id, block_number),
+  CONSTRAINT indexed_blocks_contract FOREIGN KEY (contract_id)
+    REFERENCES indexed_contracts (id) ON DELETE CASCADE
+);
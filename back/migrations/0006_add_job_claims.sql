@@ -0,0 +1,4 @@
+-- Bumped on every claim; a worker's updates must carry the value it claimed
+-- with, so one whose lease lapsed cannot overwrite the job's new run.
+ALTER TABLE jobs
+  ADD COLUMN claim INT UNSIGNED NOT NULL DEFAULT 0 AFTER lease_until;
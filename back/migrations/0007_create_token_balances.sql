@@ -0,0 +1,33 @@
+-- Running ERC-20 balance per holder, moved by every stored or rewound batch
+-- so holder pages are read in order instead of summed from all transfers.
+-- Sums can outgrow `uint256` (and every SQL number type), so they are kept as
+-- decimal text: `balance` zero-padded to 155 digits (2^512) so it sorts, and
+-- NULL unless positive; `deficit` is what the holder sent beyond what they
+-- received since `start_block`, NULL unless positive.
+CREATE TABLE IF NOT EXISTS token_balances (
+  contract_id BIGINT UNSIGNED NOT NULL,
+  address CHAR(42) NOT NULL,
+  balance CHAR(155) NULL,
+  deficit VARCHAR(155) NULL,
+  PRIMARY KEY (contract_id, address),
+  KEY token_balances_balance (contract_id, balance),
+  CONSTRAINT token_balances_contract FOREIGN KEY (contract_id)
+    REFERENCES indexed_contracts (id) ON DELETE CASCADE
+);
+
+-- ERC-20 contracts indexed before the table existed are scanned again from
+-- `start_block` to fill it in.
+DELETE FROM token_transfers WHERE contract_id IN (
+  SELECT id FROM indexed_contracts WHERE standard = 'erc20'
+);
+
+DELETE FROM token_approvals WHERE contract_id IN (
+  SELECT id FROM indexed_contracts WHERE standard = 'erc20'
+);
+
+DELETE FROM indexed_blocks WHERE contract_id IN (
+  SELECT id FROM indexed_contracts WHERE standard = 'erc20'
+);
+
+UPDATE indexed_contracts SET next_block = start_block, last_block_hash = NULL
+  WHERE standard = 'erc20';
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use ethers::{
+  providers::{Http, Provider},
+  types::{Address, U256},
+};
+use shared::contract_abis::ierc721::IERC721;
+
+use crate::errors::AppError;
+
+/// RPC providers for the chains configured through `RPC_URLS`.
+#[derive(Clone, Default)]
+pub struct Chains {
+  providers: Arc<HashMap<String, Arc<Provider<Http>>>>,
+}
+
+impl Chains {
+  /// Parses `name=url` pairs separated by commas,
+  /// e.g. `sepolia=https://rpc.sepolia.org,mainnet=https://eth.llamarpc.com`.
+  pub fn parse(spec: &str) -> Result<Self, anyhow::Error> {
+    let mut providers = HashMap::new();
+
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+      let (name, url) = entry
+        .split_once('=')
+        .with_context(|| format!("Invalid RPC_URLS entry: {}", entry))?;
+      let provider = Provider::<Http>::try_from(url.trim())
+        .with_context(|| format!("Invalid RPC URL for {}", name))?;
+      providers.insert(name.trim().to_string(), Arc::new(provider));
+    }
+
+    Ok(Self {
+      providers: Arc::new(providers),
+    })
+  }
+
+  pub fn names(&self) -> Vec<String> {
+    let mut names = self.providers.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    names
+  }
+
+  pub fn provider(&self, chain: &str) -> Option<Arc<Provider<Http>>> {
+    self.providers.get(chain).cloned()
+  }
+
+  /// A token counts as minted when `ownerOf` returns a non-zero owner.
+  /// Reverts (the usual answer for unminted ids) are reported as `false`;
+  /// any other RPC error is returned so an outage never hides metadata.
+  pub async fn is_minted(
+    &self,
+    chain: &str,
+    contract_address: Address,
+    token_id: U256,
+  ) -> Result<bool, AppError> {
+    let provider = self
+      .provider(chain)
+      .ok_or_else(|| AppError::NotFound(format!("chain {}", chain)))?;
+    let contract = IERC721::new(contract_address, provider);
+
+    match contract.owner_of(token_id).call().await {
+      Ok(owner) => Ok(!owner.is_zero()),
+      Err(e) if e.is_revert() => Ok(false),
+      Err(e) => Err(AppError::Rpc(e.to_string())),
+    }
+  }
+}
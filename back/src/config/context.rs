@@ -1,17 +1,177 @@
-use super::{AppState, MovieState};
-use axum::Error;
+use super::{AppState, Config, MovieState};
+use crate::chain::Chains;
+use crate::jobs::Jobs;
+use crate::middleware::metrics::Metrics;
+use crate::middleware::rate_limit::RateLimit;
+use crate::shutdown::Background;
+use crate::storage::Storage;
+use anyhow::Context;
 use dotenv;
+use sqlx::mysql::{MySqlConnectOptions, MySqlPool, MySqlPoolOptions};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing_subscriber::EnvFilter;
 
-pub async fn load() -> Result<AppState, Error> {
+pub async fn load() -> Result<AppState, anyhow::Error> {
   dotenv::dotenv().ok();
-  tracing_subscriber::fmt::init();
+  init_tracing();
 
-  // let db_name = std::env::var("DB_NAME").unwrap();
-  // let db_url = std::env::var("DB_URL").unwrap();
+  let storage_root =
+    std::env::var("STORAGE_ROOT").unwrap_or_else(|_| "./storage".to_string());
+  let rpc_urls = std::env::var("RPC_URLS").unwrap_or_default();
+
+  let config = load_config()?;
+  let db = connect_db(&config).await?;
 
   let app_state = AppState {
     movie_state: MovieState {},
+    rate_limiter: config.rate_limiter(),
+    config: Arc::new(config),
+    storage: Storage::new(storage_root),
+    chains: Chains::parse(&rpc_urls)?,
+    db,
+    metrics: Metrics::new(),
+    background: Background::default(),
+    jobs: Jobs::default(),
   };
 
   Ok(app_state)
 }
+
+fn load_config() -> Result<Config, anyhow::Error> {
+  let default = Config::default();
+
+  Ok(Config {
+    ready_check_rpc: env_or("READY_CHECK_RPC", default.ready_check_rpc)?,
+    ready_check_timeout: Duration::from_millis(env_or(
+      "READY_CHECK_TIMEOUT_MS",
+      default.ready_check_timeout.as_millis() as u64,
+    )?),
+    drain_timeout: Duration::from_secs(env_or(
+      "SHUTDOWN_DRAIN_TIMEOUT_SECS",
+      default.drain_timeout.as_secs(),
+    )?),
+    run_migrations: env_or("RUN_MIGRATIONS", default.run_migrations)?,
+    job_workers: env_or("JOB_WORKERS", default.job_workers)?,
+    job_poll_interval: Duration::from_millis(env_or(
+      "JOB_POLL_INTERVAL_MS",
+      default.job_poll_interval.as_millis() as u64,
+    )?),
+    job_lease: Duration::from_secs(env_or(
+      "JOB_LEASE_SECS",
+      default.job_lease.as_secs(),
+    )?),
+    indexer_enabled: env_or("INDEXER_ENABLED", default.indexer_enabled)?,
+    indexer_poll_interval: Duration::from_secs(env_or(
+      "INDEXER_POLL_INTERVAL_SECS",
+      default.indexer_poll_interval.as_secs(),
+    )?),
+    indexer_confirmations: env_or(
+      "INDEXER_CONFIRMATIONS",
+      default.indexer_confirmations,
+    )?,
+    indexer_batch_blocks: env_or(
+      "INDEXER_BATCH_BLOCKS",
+      default.indexer_batch_blocks,
+    )?,
+
+    body_limit: env_or("BODY_LIMIT_BYTES", default.body_limit)?,
+    upload_body_limit: env_or(
+      "UPLOAD_BODY_LIMIT_BYTES",
+      default.upload_body_limit,
+    )?,
+
+    rate_limit_ip: RateLimit {
+      burst: env_or("RATE_LIMIT_IP_BURST", default.rate_limit_ip.burst)?,
+      per_second: env_or(
+        "RATE_LIMIT_IP_PER_SEC",
+        default.rate_limit_ip.per_second,
+      )?,
+    },
+    rate_limit_api_key: RateLimit {
+      burst: env_or(
+        "RATE_LIMIT_API_KEY_BURST",
+        default.rate_limit_api_key.burst,
+      )?,
+      per_second: env_or(
+        "RATE_LIMIT_API_KEY_PER_SEC",
+        default.rate_limit_api_key.per_second,
+      )?,
+    },
+    api_keys: std::env::var("API_KEYS")
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|key| !key.is_empty())
+      .map(str::to_string)
+      .collect(),
+    trust_forwarded_for: env_or(
+      "TRUST_FORWARDED_FOR",
+      default.trust_forwarded_for,
+    )?,
+  })
+}
+
+fn env_or<T>(key: &str, default: T) -> Result<T, anyhow::Error>
+where
+  T: std::str::FromStr,
+  T::Err: std::error::Error + Send + Sync + 'static,
+{
+  match std::env::var(key) {
+    Ok(value) => value.parse::<T>().context(key.to_string()),
+    Err(_) => Ok(default),
+  }
+}
+
+/// The pool connects lazily, so without `RUN_MIGRATIONS` the server comes up
+/// even while MySQL is unreachable; queries fail until it is back.
+async fn connect_db(config: &Config) -> Result<MySqlPool, anyhow::Error> {
+  let host = std::env::var("DB_HOST").context("DB_HOST")?;
+  let user = std::env::var("DB_USER").context("DB_USER")?;
+  let pass = std::env::var("DB_PASS").unwrap_or_default();
+  let port = env_or::<u16>("DB_PORT", 3306)?;
+  let name = std::env::var("DB_NAME").unwrap_or_else(|_| "toys".to_string());
+  let max_connections = env_or::<u32>("DB_MAX_CONNECTIONS", 10)?;
+
+  let options = MySqlConnectOptions::new()
+    .host(&host)
+    .port(port)
+    .username(&user)
+    .password(&pass)
+    .database(&name);
+
+  let db = MySqlPoolOptions::new()
+    .max_connections(max_connections)
+    .connect_lazy_with(options);
+
+  if config.run_migrations {
+    sqlx::migrate!("./migrations")
+      .run(&db)
+      .await
+      .context("Database migration failed")?;
+  }
+
+  Ok(db)
+}
+
+/// `LOG_FORMAT=json` switches to one JSON object per line with the current
+/// span's fields (request id, method, uri) attached; anything else keeps the
+/// human readable format. Filtering follows `RUST_LOG` (default `info`).
+fn init_tracing() {
+  let filter = EnvFilter::try_from_default_env()
+    .unwrap_or_else(|_| EnvFilter::new("info"));
+  let json = std::env::var("LOG_FORMAT")
+    .map(|format| format.eq_ignore_ascii_case("json"))
+    .unwrap_or(false);
+
+  if json {
+    tracing_subscriber::fmt()
+      .json()
+      .with_env_filter(filter)
+      .with_current_span(true)
+      .with_span_list(false)
+      .init();
+  } else {
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+  }
+}
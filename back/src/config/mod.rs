@@ -1,10 +1,120 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use axum::extract::FromRef;
+use sqlx::MySqlPool;
+
+use crate::chain::Chains;
+use crate::jobs::Jobs;
+use crate::middleware::metrics::Metrics;
+use crate::middleware::rate_limit::{RateLimit, RateLimiter};
+use crate::shutdown::Background;
+use crate::storage::Storage;
 
 pub mod context;
 
 #[derive(Clone)]
 pub struct AppState {
   pub movie_state: MovieState,
+  pub config: Arc<Config>,
+  pub storage: Storage,
+  pub chains: Chains,
+  pub db: MySqlPool,
+  pub metrics: Metrics,
+  pub background: Background,
+  pub jobs: Jobs,
+  pub rate_limiter: RateLimiter,
+}
+
+/// Tunables read from the environment by [`context::load`].
+#[derive(Clone, Debug)]
+pub struct Config {
+  /// Include `eth_blockNumber` on every configured chain in `/health/ready`.
+  pub ready_check_rpc: bool,
+  pub ready_check_timeout: Duration,
+  /// How long in-flight requests, then background workers, then the
+  /// database pool each get to finish after SIGTERM/SIGINT before they are
+  /// dropped.
+  pub drain_timeout: Duration,
+  /// Apply `back/migrations` at startup. Off by default since it needs
+  /// MySQL up before the server will start.
+  pub run_migrations: bool,
+
+  /// Workers processing `POST /jobs`; 0 leaves jobs queued.
+  pub job_workers: usize,
+  /// How often idle workers check the table for jobs queued elsewhere.
+  pub job_poll_interval: Duration,
+  /// How long a claimed job stays reserved without a heartbeat. Workers
+  /// renew it every third of this; once it lapses any instance may take the
+  /// job over.
+  pub job_lease: Duration,
+
+  /// Scan `POST /contracts` registrations for `Transfer`/`Approval` logs.
+  pub indexer_enabled: bool,
+  pub indexer_poll_interval: Duration,
+  /// Blocks behind head before logs are indexed.
+  pub indexer_confirmations: u64,
+  /// Largest `eth_getLogs` block range per request.
+  pub indexer_batch_blocks: u64,
+
+  /// Body limit for every route without a more specific one.
+  pub body_limit: usize,
+  /// Body limit for metadata uploads (`PUT /collections/{slug}/tokens/..`,
+  /// `PUT /collections/{slug}/placeholder`).
+  pub upload_body_limit: usize,
+
+  pub rate_limit_ip: RateLimit,
+  pub rate_limit_api_key: RateLimit,
+  /// Keys accepted in `X-Api-Key`; each gets its own bucket. Metadata and
+  /// reveal writes are refused without one.
+  pub api_keys: Vec<String>,
+  /// Take the client IP from `X-Forwarded-For` (only behind a proxy).
+  pub trust_forwarded_for: bool,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      ready_check_rpc: false,
+      ready_check_timeout: Duration::from_secs(2),
+      drain_timeout: Duration::from_secs(30),
+      run_migrations: false,
+
+      job_workers: 2,
+      job_poll_interval: Duration::from_secs(5),
+      job_lease: Duration::from_secs(60),
+
+      indexer_enabled: true,
+      indexer_poll_interval: Duration::from_secs(15),
+      indexer_confirmations: 12,
+      indexer_batch_blocks: 2000,
+
+      body_limit: 64 * 1024,
+      upload_body_limit: 1024 * 1024,
+
+      rate_limit_ip: RateLimit {
+        burst: 120,
+        per_second: 20.0,
+      },
+      rate_limit_api_key: RateLimit {
+        burst: 600,
+        per_second: 100.0,
+      },
+      api_keys: vec![],
+      trust_forwarded_for: false,
+    }
+  }
+}
+
+impl Config {
+  pub fn rate_limiter(&self) -> RateLimiter {
+    RateLimiter::new(
+      self.rate_limit_ip,
+      self.rate_limit_api_key,
+      self.api_keys.clone(),
+      self.trust_forwarded_for,
+    )
+  }
 }
 
 #[derive(Clone)]
@@ -15,3 +125,9 @@ impl FromRef<AppState> for MovieState {
     app_state.movie_state.clone()
   }
 }
+
+impl FromRef<AppState> for Metrics {
+  fn from_ref(app_state: &AppState) -> Metrics {
+    app_state.metrics.clone()
+  }
+}
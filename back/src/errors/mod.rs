@@ -1,56 +1,124 @@
 use axum::{
-  http::StatusCode,
+  http::{header::RETRY_AFTER, StatusCode},
   response::{IntoResponse, Response},
   Json,
 };
 use serde_json::json;
 
-// pub enum AppError {
-//   Auth(),
-//   Api(String),
-// }
-
-// impl<'a> From<movie::MovieApiError> for AppError {
-//   fn from(inner: movie::MovieApiError) -> Self {
-//     AppError::MovieApi(inner)
-//   }
-// }
-
-// impl<'a> IntoResponse for AppError {
-//   fn into_response(self) -> Response {
-//     let (status, error_message) = match self {
-//       AppError::MovieApi(movie::MovieApiError::API(e)) => {
-//         tracing::error!("Call movie API failed: {}", e);
-//         (
-//           StatusCode::EXPECTATION_FAILED,
-//           json!({"msg": "movie api error"}),
-//         )
-//       }
-//       AppError::MovieApi(movie::MovieApiError::Input(input, value)) => (
-//         StatusCode::BAD_REQUEST,
-//         json!({"msg": "Invalid input", "input": input, "value": value}),
-//       ),
-//       AppError::UserApi(user::UserApiError::JWT(e)) => {
-//         tracing::error!("Call user API failed: {}", e);
-//         (StatusCode::EXPECTATION_FAILED, json!({"msg": "jwt error"}))
-//       }
-//       AppError::Api(e) => {
-//         tracing::error!("Call API failed: {}", e);
-//         (
-//           StatusCode::EXPECTATION_FAILED,
-//           json!({"msg": "api call error"}),
-//         )
-//       }
-//       AppError::Auth() => {
-//         tracing::error!("Auth failed");
-//         (StatusCode::UNAUTHORIZED, json!({"msg": "auth failed"}))
-//       }
-//     };
-
-//     let body = Json(json!({
-//         "error": error_message,
-//     }));
-
-//     (status, body).into_response()
-//   }
-// }
+/// Body of every error response, e.g.
+/// `{"error": {"msg": "Invalid input", "input": "slug", "value": "a/b"}}`.
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug)]
+pub struct ErrorResponse {
+  #[schema(value_type = Object)]
+  pub error: serde_json::Value,
+}
+
+/// Handler errors. `into_response` logs each one; it runs inside the
+/// request span, so the log line carries that request's `X-Request-Id`.
+#[derive(Debug)]
+pub enum AppError {
+  NotFound(String),
+  Input(String, String),
+  /// Write route called without a configured `X-Api-Key`.
+  Unauthorized,
+  Conflict(String),
+  Storage(std::io::Error),
+  Db(sqlx::Error),
+  Rpc(String),
+  /// Token bucket empty; the value is how long until one token refills.
+  RateLimited(std::time::Duration),
+  /// Request body over the route's limit, in bytes.
+  PayloadTooLarge(usize),
+}
+
+impl From<std::io::Error> for AppError {
+  fn from(inner: std::io::Error) -> Self {
+    AppError::Storage(inner)
+  }
+}
+
+impl From<sqlx::Error> for AppError {
+  fn from(inner: sqlx::Error) -> Self {
+    AppError::Db(inner)
+  }
+}
+
+impl IntoResponse for AppError {
+  fn into_response(self) -> Response {
+    let retry_after = match &self {
+      AppError::RateLimited(wait) => Some(wait.as_secs_f64().ceil() as u64),
+      _ => None,
+    };
+
+    let (status, error_message) = match self {
+      AppError::NotFound(what) => {
+        tracing::warn!("Not found: {}", what);
+        (
+          StatusCode::NOT_FOUND,
+          json!({"msg": "not found", "what": what}),
+        )
+      }
+      AppError::Input(input, value) => {
+        tracing::warn!("Invalid input {}: {}", input, value);
+        (
+          StatusCode::BAD_REQUEST,
+          json!({"msg": "Invalid input", "input": input, "value": value}),
+        )
+      }
+      AppError::Unauthorized => {
+        tracing::warn!("Missing or unknown API key");
+        (StatusCode::UNAUTHORIZED, json!({"msg": "api key required"}))
+      }
+      AppError::Conflict(what) => {
+        tracing::warn!("Conflict: {}", what);
+        (
+          StatusCode::CONFLICT,
+          json!({"msg": "already exists", "what": what}),
+        )
+      }
+      AppError::Db(e) => {
+        tracing::error!("Database query failed: {}", e);
+        (
+          StatusCode::INTERNAL_SERVER_ERROR,
+          json!({"msg": "database error"}),
+        )
+      }
+      AppError::Storage(e) => {
+        tracing::error!("Storage failed: {}", e);
+        (
+          StatusCode::INTERNAL_SERVER_ERROR,
+          json!({"msg": "storage error"}),
+        )
+      }
+      AppError::Rpc(e) => {
+        tracing::error!("Call RPC failed: {}", e);
+        (StatusCode::BAD_GATEWAY, json!({"msg": "rpc error"}))
+      }
+      AppError::RateLimited(wait) => {
+        tracing::warn!("Rate limited for {:?}", wait);
+        (
+          StatusCode::TOO_MANY_REQUESTS,
+          json!({"msg": "too many requests"}),
+        )
+      }
+      AppError::PayloadTooLarge(limit) => {
+        tracing::warn!("Request body over {} bytes", limit);
+        (
+          StatusCode::PAYLOAD_TOO_LARGE,
+          json!({"msg": "payload too large", "limit": limit}),
+        )
+      }
+    };
+
+    let body = Json(json!({
+        "error": error_message,
+    }));
+
+    match retry_after {
+      Some(secs) => {
+        (status, [(RETRY_AFTER, secs.max(1).to_string())], body).into_response()
+      }
+      None => (status, body).into_response(),
+    }
+  }
+}
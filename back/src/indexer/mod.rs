@@ -0,0 +1,344 @@
+use ethers::{
+  contract::EthLogDecode,
+  providers::{Http, Middleware, Provider},
+  types::{Address, Filter, Log, H256},
+};
+use shared::contract_abis::{ierc20, ierc721};
+use shared::indexer::{IndexedContract, TokenStandard, TokenTransfer};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::AppState;
+use crate::errors::AppError;
+use crate::model::indexer;
+
+/// Scan position of one contract.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cursor {
+  pub start_block: u64,
+  /// First block not yet scanned.
+  pub next_block: u64,
+  /// Hash of `next_block - 1` as seen when it was scanned.
+  pub last_block_hash: Option<H256>,
+  /// `(block, hash)` of the last block of earlier batches, newest first, to
+  /// find where a reorg forked off.
+  pub checkpoints: Vec<(u64, H256)>,
+}
+
+/// One `Approval` log; only stored, not served.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenApproval {
+  pub block_number: u64,
+  pub tx_hash: String,
+  pub log_index: u32,
+  pub owner_address: String,
+  pub spender_address: String,
+  pub token_id: Option<String>,
+  pub value: Option<String>,
+}
+
+/// Confirmed logs of `from..=to`, to be stored before moving the cursor to
+/// `to + 1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+  pub from: u64,
+  pub to: u64,
+  pub block_hash: H256,
+  pub transfers: Vec<TokenTransfer>,
+  pub approvals: Vec<TokenApproval>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+  /// No confirmed block past the cursor yet.
+  Idle,
+  Batch(Batch),
+  /// The block before the cursor is no longer canonical. Everything from
+  /// `cursor.next_block` on must be dropped and rescanned.
+  Reorg(Cursor),
+}
+
+/// Looks at the chain once and says what to do next.
+///
+/// Only blocks at least `confirmations` deep are scanned. A reorg deeper than
+/// that is caught by re-checking the hash of the last scanned block; the
+/// cursor then walks back through `cursor.checkpoints` to the newest one that
+/// is still canonical, or to `start_block` when none is.
+pub async fn step(
+  provider: &Provider<Http>,
+  address: Address,
+  standard: TokenStandard,
+  cursor: &Cursor,
+  confirmations: u64,
+  batch_blocks: u64,
+) -> Result<Step, AppError> {
+  if let (Some(expected), Some(previous)) =
+    (cursor.last_block_hash, cursor.next_block.checked_sub(1))
+  {
+    if block_hash(provider, previous).await? != Some(expected) {
+      return Ok(Step::Reorg(fork_point(provider, cursor).await?));
+    }
+  }
+
+  let head = provider.get_block_number().await.map_err(rpc)?.as_u64();
+  let Some(safe) = head.checked_sub(confirmations) else {
+    return Ok(Step::Idle);
+  };
+  if cursor.next_block > safe {
+    return Ok(Step::Idle);
+  }
+
+  let from = cursor.next_block;
+  let to = safe.min(from + batch_blocks.max(1) - 1);
+
+  let filter = Filter::new()
+    .address(address)
+    .from_block(from)
+    .to_block(to)
+    .topic0(vec![transfer_topic(), approval_topic()]);
+  let logs = provider.get_logs(&filter).await.map_err(rpc)?;
+
+  let block_hash = block_hash(provider, to)
+    .await?
+    .ok_or_else(|| AppError::Rpc(format!("block {} not found", to)))?;
+
+  let mut batch = Batch {
+    from,
+    to,
+    block_hash,
+    transfers: Vec::new(),
+    approvals: Vec::new(),
+  };
+  for log in logs {
+    decode(&mut batch, standard, log);
+  }
+
+  Ok(Step::Batch(batch))
+}
+
+/// Cursor just past the newest checkpoint below the cursor whose hash the
+/// chain still agrees with.
+async fn fork_point(
+  provider: &Provider<Http>,
+  cursor: &Cursor,
+) -> Result<Cursor, AppError> {
+  let candidates = cursor.checkpoints.iter().filter(|(number, _)| {
+    *number >= cursor.start_block && number + 1 < cursor.next_block
+  });
+  for &(number, hash) in candidates {
+    if block_hash(provider, number).await? == Some(hash) {
+      return Ok(Cursor {
+        start_block: cursor.start_block,
+        next_block: number + 1,
+        last_block_hash: Some(hash),
+        checkpoints: cursor
+          .checkpoints
+          .iter()
+          .filter(|(older, _)| *older <= number)
+          .copied()
+          .collect(),
+      });
+    }
+  }
+
+  Ok(Cursor {
+    start_block: cursor.start_block,
+    next_block: cursor.start_block,
+    last_block_hash: None,
+    checkpoints: Vec::new(),
+  })
+}
+
+/// Runs [`step`] for every registered contract each
+/// `Config::indexer_poll_interval` until shutdown.
+pub fn spawn(state: &AppState) {
+  if !state.config.indexer_enabled {
+    return;
+  }
+
+  let state = state.clone();
+  state
+    .background
+    .clone()
+    .spawn("indexer", |token| worker(state, token));
+}
+
+async fn worker(state: AppState, token: CancellationToken) {
+  while !token.is_cancelled() {
+    match indexer::list(&state.db).await {
+      Ok(contracts) => {
+        for contract in contracts {
+          if token.is_cancelled() {
+            return;
+          }
+          if let Err(e) = catch_up(&state, &token, &contract).await {
+            tracing::warn!(
+              contract = contract.id,
+              chain = contract.chain,
+              "Indexing failed: {:?}",
+              e
+            );
+          }
+        }
+      }
+      Err(e) => tracing::warn!("Listing indexed contracts failed: {:?}", e),
+    }
+
+    tokio::select! {
+      _ = token.cancelled() => {}
+      _ = tokio::time::sleep(state.config.indexer_poll_interval) => {}
+    }
+  }
+}
+
+/// Steps one contract until it is idle or shutdown begins.
+async fn catch_up(
+  state: &AppState,
+  token: &CancellationToken,
+  contract: &IndexedContract,
+) -> Result<(), AppError> {
+  let Some(provider) = state.chains.provider(&contract.chain) else {
+    return Ok(());
+  };
+  let address = contract.address.parse::<Address>().map_err(|_| {
+    AppError::Input("address".to_string(), contract.address.clone())
+  })?;
+
+  while !token.is_cancelled() {
+    let cursor = indexer::cursor(&state.db, contract.id).await?;
+    let step = step(
+      &provider,
+      address,
+      contract.standard,
+      &cursor,
+      state.config.indexer_confirmations,
+      state.config.indexer_batch_blocks,
+    )
+    .await?;
+
+    match step {
+      Step::Idle => return Ok(()),
+      Step::Batch(batch) => {
+        tracing::debug!(
+          contract = contract.id,
+          from = batch.from,
+          to = batch.to,
+          transfers = batch.transfers.len(),
+          "Indexed blocks"
+        );
+        indexer::store_batch(&state.db, contract.id, &batch).await?;
+      }
+      Step::Reorg(rewound) => {
+        tracing::warn!(
+          contract = contract.id,
+          from = cursor.next_block,
+          to = rewound.next_block,
+          "Reorg detected, rewinding"
+        );
+        indexer::rewind(&state.db, contract.id, &rewound).await?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn decode(batch: &mut Batch, standard: TokenStandard, log: Log) {
+  let (Some(block_number), Some(tx_hash), Some(log_index)) =
+    (log.block_number, log.transaction_hash, log.log_index)
+  else {
+    return;
+  };
+  let block_number = block_number.as_u64();
+  let tx_hash = format!("{:#x}", tx_hash);
+  let log_index = log_index.as_u32();
+  let raw = log.into();
+
+  match standard {
+    TokenStandard::Erc721 => match ierc721::IERC721Events::decode_log(&raw) {
+      Ok(ierc721::IERC721Events::TransferFilter(e)) => {
+        batch.transfers.push(TokenTransfer {
+          block_number,
+          tx_hash,
+          log_index,
+          from_address: hex_address(e.from),
+          to_address: hex_address(e.to),
+          token_id: Some(e.token_id.to_string()),
+          value: None,
+        })
+      }
+      Ok(ierc721::IERC721Events::ApprovalFilter(e)) => {
+        batch.approvals.push(TokenApproval {
+          block_number,
+          tx_hash,
+          log_index,
+          owner_address: hex_address(e.owner),
+          spender_address: hex_address(e.approved),
+          token_id: Some(e.token_id.to_string()),
+          value: None,
+        })
+      }
+      _ => skipped(block_number, log_index),
+    },
+    TokenStandard::Erc20 => match ierc20::IERC20Events::decode_log(&raw) {
+      Ok(ierc20::IERC20Events::TransferFilter(e)) => {
+        batch.transfers.push(TokenTransfer {
+          block_number,
+          tx_hash,
+          log_index,
+          from_address: hex_address(e.from),
+          to_address: hex_address(e.to),
+          token_id: None,
+          value: Some(e.value.to_string()),
+        })
+      }
+      Ok(ierc20::IERC20Events::ApprovalFilter(e)) => {
+        batch.approvals.push(TokenApproval {
+          block_number,
+          tx_hash,
+          log_index,
+          owner_address: hex_address(e.owner),
+          spender_address: hex_address(e.spender),
+          token_id: None,
+          value: Some(e.value.to_string()),
+        })
+      }
+      Err(_) => skipped(block_number, log_index),
+    },
+  }
+}
+
+/// Logs with the right topic but the wrong layout, e.g. an ERC-20 contract
+/// registered as ERC-721.
+fn skipped(block_number: u64, log_index: u32) {
+  tracing::warn!(block_number, log_index, "Skipping undecodable log");
+}
+
+async fn block_hash(
+  provider: &Provider<Http>,
+  number: u64,
+) -> Result<Option<H256>, AppError> {
+  Ok(
+    provider
+      .get_block(number)
+      .await
+      .map_err(rpc)?
+      .and_then(|block| block.hash),
+  )
+}
+
+fn transfer_topic() -> H256 {
+  ethers::utils::keccak256("Transfer(address,address,uint256)").into()
+}
+
+fn approval_topic() -> H256 {
+  ethers::utils::keccak256("Approval(address,address,uint256)").into()
+}
+
+/// Lowercase `0x`-prefixed, the form addresses are stored and queried in.
+pub fn hex_address(address: Address) -> String {
+  format!("{:#x}", address)
+}
+
+fn rpc(e: impl std::fmt::Display) -> AppError {
+  AppError::Rpc(e.to_string())
+}
@@ -0,0 +1,172 @@
+use std::io::{Cursor, Write};
+
+use ethers::types::U256;
+use ethers::utils::{hex, keccak256};
+use shared::metadata::{numbered, Metadata};
+use tokio_util::sync::CancellationToken;
+use zip::{write::FileOptions, ZipWriter};
+
+use super::JobError;
+use crate::config::AppState;
+use crate::model::job;
+use crate::storage::validate_slug;
+
+/// Tokens between progress writes and cancellation checks.
+const CHUNK: u32 = 100;
+
+/// Zip of generated tokens:
+///
+/// ```text
+/// {token_id}.json
+/// {project_name}.csv
+/// manifest.json    keccak256 of each token file plus their provenance hash
+/// ```
+pub struct Archive {
+  project_name: String,
+  zip: ZipWriter<Cursor<Vec<u8>>>,
+  csv: String,
+  hashes: Vec<[u8; 32]>,
+}
+
+impl Archive {
+  pub fn new(project_name: &str, template: &Metadata) -> Self {
+    let mut csv = "token_id,name,description,image".to_string();
+    for i in 0..template.attributes.len() {
+      csv.push_str(&format!(
+        ",attributes.trait_type_{},attributes.value_{}",
+        i, i
+      ));
+    }
+    csv.push('\n');
+
+    Self {
+      project_name: project_name.to_string(),
+      zip: ZipWriter::new(Cursor::new(Vec::new())),
+      csv,
+      hashes: Vec::new(),
+    }
+  }
+
+  /// Adds the next token, numbered from 0, and returns its JSON.
+  pub fn add(&mut self, metadata: &Metadata) -> Result<Vec<u8>, JobError> {
+    let token_id = self.hashes.len();
+    let bytes = serde_json::to_vec(metadata).map_err(failed)?;
+
+    self
+      .zip
+      .start_file(format!("{}.json", token_id), FileOptions::default())
+      .map_err(failed)?;
+    self.zip.write_all(&bytes).map_err(failed)?;
+
+    self.csv.push_str(&format!(
+      "{},{},{},{}",
+      token_id,
+      csv_field(&metadata.name),
+      csv_field(&metadata.description),
+      csv_field(&metadata.image)
+    ));
+    for attribute in metadata.attributes.iter() {
+      self.csv.push_str(&format!(
+        ",{},{}",
+        csv_field(&attribute.trait_type),
+        csv_field(&attribute.value)
+      ));
+    }
+    self.csv.push('\n');
+
+    self.hashes.push(keccak256(&bytes));
+
+    Ok(bytes)
+  }
+
+  /// Provenance hash: keccak256 over every token hash in token order.
+  pub fn provenance(&self) -> [u8; 32] {
+    keccak256(self.hashes.concat())
+  }
+
+  pub fn finish(mut self) -> Result<Vec<u8>, JobError> {
+    let manifest = serde_json::json!({
+      "count": self.hashes.len(),
+      "provenance": hex::encode_prefixed(self.provenance()),
+      "tokens": self
+        .hashes
+        .iter()
+        .enumerate()
+        .map(|(token_id, hash)| serde_json::json!({
+          "token_id": token_id,
+          "keccak256": hex::encode_prefixed(hash),
+        }))
+        .collect::<Vec<_>>(),
+    });
+
+    self
+      .zip
+      .start_file(format!("{}.csv", self.project_name), FileOptions::default())
+      .map_err(failed)?;
+    self.zip.write_all(self.csv.as_bytes()).map_err(failed)?;
+
+    self
+      .zip
+      .start_file("manifest.json", FileOptions::default())
+      .map_err(failed)?;
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(failed)?;
+    self.zip.write_all(&manifest).map_err(failed)?;
+
+    Ok(self.zip.finish().map_err(failed)?.into_inner())
+  }
+}
+
+pub(super) async fn run(
+  state: &AppState,
+  token: &CancellationToken,
+  claim: job::Claim,
+  project_name: &str,
+  template: &Metadata,
+  count: u32,
+  slug: Option<&str>,
+) -> Result<(), JobError> {
+  if let Some(slug) = slug {
+    validate_slug(slug)?;
+  }
+
+  let mut archive = Archive::new(project_name, template);
+
+  for token_id in 0..count {
+    if token_id % CHUNK == 0 && token_id > 0 {
+      if token.is_cancelled() {
+        return Err(JobError::Cancelled);
+      }
+      job::set_progress(&state.db, claim, token_id).await?;
+      tokio::task::yield_now().await;
+    }
+
+    let metadata = numbered(template, token_id);
+    archive.add(&metadata)?;
+
+    if let Some(slug) = slug {
+      let value = serde_json::to_value(&metadata).map_err(failed)?;
+      state
+        .storage
+        .put_token_metadata(slug, U256::from(token_id), &value)
+        .await?;
+    }
+  }
+
+  let bytes = archive.finish()?;
+  state.storage.put_job_result(claim.id, &bytes).await?;
+
+  Ok(())
+}
+
+fn failed(e: impl std::fmt::Display) -> JobError {
+  JobError::Failed(e.to_string())
+}
+
+/// Quotes a CSV field when it contains a separator, quote or newline.
+fn csv_field(value: &str) -> String {
+  if value.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", value.replace('"', "\"\""))
+  } else {
+    value.to_string()
+  }
+}
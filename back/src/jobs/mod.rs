@@ -0,0 +1,155 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use shared::job::JobRequest;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::AppState;
+use crate::errors::AppError;
+use crate::model::job;
+
+pub mod generate;
+
+/// Wakes idle workers when a job is queued. Workers also poll the table every
+/// `Config::job_poll_interval`, so jobs queued by another instance, or left
+/// behind by one that died (see `Config::job_lease`), are picked up too.
+#[derive(Clone, Default)]
+pub struct Jobs {
+  queued: Arc<Notify>,
+}
+
+impl Jobs {
+  pub fn wake(&self) {
+    self.queued.notify_one();
+  }
+}
+
+/// Why a job stopped before finishing.
+#[derive(Debug)]
+pub enum JobError {
+  /// Shutdown began; the job goes back in the queue.
+  Cancelled,
+  /// Another worker claimed the job after our lease lapsed; it is theirs to
+  /// record now.
+  LeaseLost(String),
+  Failed(String),
+}
+
+impl From<AppError> for JobError {
+  fn from(e: AppError) -> Self {
+    match e {
+      // The only conflict a running job meets is `job::held` refusing a write.
+      AppError::Conflict(e) => JobError::LeaseLost(e),
+      e => JobError::Failed(format!("{:?}", e)),
+    }
+  }
+}
+
+/// Starts `Config::job_workers` workers on the background tracker.
+pub fn spawn_workers(state: &AppState) {
+  for _ in 0..state.config.job_workers {
+    let state = state.clone();
+    state
+      .background
+      .clone()
+      .spawn("job-worker", |token| worker(state, token));
+  }
+}
+
+async fn worker(state: AppState, token: CancellationToken) {
+  while !token.is_cancelled() {
+    let claimed = match job::claim_next(&state.db, state.config.job_lease).await
+    {
+      Ok(claimed) => claimed,
+      Err(e) => {
+        tracing::warn!("Claiming a job failed: {:?}", e);
+        None
+      }
+    };
+
+    match claimed {
+      Some((claim, request)) => run(&state, &token, claim, request).await,
+      None => {
+        tokio::select! {
+          _ = token.cancelled() => {}
+          _ = state.jobs.queued.notified() => {}
+          _ = tokio::time::sleep(state.config.job_poll_interval) => {}
+        }
+      }
+    }
+  }
+}
+
+async fn run(
+  state: &AppState,
+  token: &CancellationToken,
+  claim: job::Claim,
+  request: JobRequest,
+) {
+  let id = claim.id;
+  tracing::info!(job = id, kind = job::kind(&request), "Job started");
+
+  let work = async {
+    match request {
+      JobRequest::Generate {
+        project_name,
+        template,
+        count,
+        slug,
+      } => {
+        generate::run(
+          state,
+          token,
+          claim,
+          &project_name,
+          &template,
+          count,
+          slug.as_deref(),
+        )
+        .await
+      }
+    }
+  };
+  tokio::pin!(work);
+
+  // Keeps the lease alive so other instances leave the job alone. Without it
+  // another worker may claim the job, so the work stops rather than racing it.
+  let lease = state.config.job_lease;
+  let mut heartbeat =
+    tokio::time::interval((lease / 3).max(Duration::from_secs(1)));
+  heartbeat.tick().await;
+  let outcome = loop {
+    tokio::select! {
+      outcome = &mut work => break outcome,
+      _ = heartbeat.tick() => {
+        if let Err(e) = job::renew_lease(&state.db, claim, lease).await {
+          break Err(JobError::LeaseLost(format!("{:?}", e)));
+        }
+      }
+    }
+  };
+
+  let recorded = match outcome {
+    Ok(()) => {
+      tracing::info!(job = id, "Job done");
+      job::finish(&state.db, claim).await
+    }
+    Err(JobError::Cancelled) => {
+      tracing::info!(job = id, "Job interrupted by shutdown, requeued");
+      job::requeue(&state.db, claim).await
+    }
+    Err(JobError::LeaseLost(error)) => {
+      tracing::warn!(job = id, "Job stopped, lease lost: {}", error);
+      Ok(())
+    }
+    Err(JobError::Failed(error)) => {
+      tracing::warn!(job = id, "Job failed: {}", error);
+      job::fail(&state.db, claim, &error).await
+    }
+  };
+
+  if let Err(e) = recorded {
+    tracing::error!(job = id, "Recording job outcome failed: {:?}", e);
+  }
+}
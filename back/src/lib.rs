@@ -1,66 +1,161 @@
-use std::net::SocketAddr;
-
 use anyhow::Context;
 use axum::{
-  http::header::{AUTHORIZATION, CONTENT_TYPE},
-  http::{HeaderValue, StatusCode},
+  http::header::{AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER},
+  http::{HeaderName, StatusCode},
   routing::{get, post},
   Json, Router,
 };
+use shared::metadata::Metadata;
+use std::future::IntoFuture;
+use std::net::SocketAddr;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 
+use crate::config::AppState;
+
+pub mod chain;
 pub mod config;
 pub mod errors;
+pub mod indexer;
+pub mod jobs;
+pub mod middleware;
 pub mod model;
 pub mod service;
+pub mod shutdown;
+pub mod storage;
 
 pub async fn start_server() -> Result<(), anyhow::Error> {
-  let app_state = config::context::load().await.unwrap();
+  let app_state = config::context::load().await?;
+  let drain_timeout = app_state.config.drain_timeout;
+  let background = app_state.background.clone();
+  let db = app_state.db.clone();
+  jobs::spawn_workers(&app_state);
+  indexer::spawn(&app_state);
+  let cancel_requests = CancellationToken::new();
+  let app =
+    shutdown::cancel_requests_on(app(app_state), cancel_requests.clone());
 
-  let app = Router::new()
-    .route("/health", get("OK"))
-    .route("/json", post(get_json))
-    .layer(
-      CorsLayer::new()
-        .allow_origin(Any) // TODO 배포 시 변경
-        .allow_methods(Any)
-        .allow_headers(vec![CONTENT_TYPE, AUTHORIZATION]),
+  let listener = tokio::net::TcpListener::bind("0.0.0.0:3001").await.unwrap();
+  tracing::info!("Listening on {}", listener.local_addr()?);
+
+  let stop_accepting = CancellationToken::new();
+  let mut server = tokio::spawn(
+    axum::serve(
+      listener,
+      app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .with_state(app_state);
+    .with_graceful_shutdown(stop_accepting.clone().cancelled_owned())
+    .into_future(),
+  );
 
-  let listener = tokio::net::TcpListener::bind("0.0.0.0:3001").await.unwrap();
+  tokio::select! {
+    result = &mut server => {
+      return result?.context("HTTP server error");
+    }
+    signal = shutdown::signal() => {
+      tracing::info!(signal, "Shutdown signal received, draining requests");
+    }
+  }
+
+  stop_accepting.cancel();
+  match tokio::time::timeout(drain_timeout, &mut server).await {
+    Ok(result) => {
+      result?.context("HTTP server error")?;
+      tracing::info!("In-flight requests drained");
+    }
+    Err(_) => {
+      tracing::warn!(
+        "Requests still running after {:?}, cancelling them",
+        drain_timeout
+      );
+      cancel_requests.cancel();
+      server.abort();
+    }
+  }
 
-  axum::serve(listener, app.into_make_service())
-    .with_graceful_shutdown(shutdown_signal())
+  background.shutdown(drain_timeout).await;
+
+  tracing::info!("Closing database pool");
+  if tokio::time::timeout(drain_timeout, db.close())
     .await
-    .context("HTTP server error")?;
+    .is_err()
+  {
+    tracing::warn!(
+      "Database connections still checked out after {:?}, exiting anyway",
+      drain_timeout
+    );
+  }
 
+  tracing::info!("Server down");
   Ok(())
 }
 
-async fn shutdown_signal() {
-  tokio::signal::ctrl_c()
-    .await
-    .expect("Expect shutdown signal handler");
-  println!("Server Down")
-}
+pub fn app(app_state: AppState) -> Router {
+  let x_request_id = HeaderName::from_static("x-request-id");
+  let config = app_state.config.clone();
 
-async fn get_json(Json(payload): Json<A>) -> (StatusCode, &'static str) {
-  tracing::info!("asc");
-  tracing::info!("payload: {:?}", payload);
-  (StatusCode::CREATED, "1231231")
-}
+  let uploads = middleware::limit::max_body(
+    service::metadata::upload_routes(),
+    config.upload_body_limit,
+  );
+  let writes = uploads
+    .merge(middleware::limit::max_body(
+      service::metadata::settings_routes()
+        .merge(service::collection::collection_write_routes())
+        .merge(service::indexer::indexer_write_routes())
+        .merge(service::job::job_write_routes()),
+      config.body_limit,
+    ))
+    .route_layer(axum::middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::auth::require_api_key,
+    ));
+  let api = Router::new()
+    .route("/json", post(get_json))
+    .merge(service::collection::collection_routes())
+    .merge(service::indexer::indexer_routes())
+    .merge(service::job::job_routes())
+    .merge(service::metadata::metadata_routes());
+  let api = middleware::limit::max_body(api, config.body_limit)
+    .merge(writes)
+    .route_layer(axum::middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::rate_limit::limit,
+    ));
 
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
-struct A {
-  name: String,
-  description: String,
-  image: String,
-  attributes: Vec<Attribute>,
+  let app = Router::new()
+    .route("/metrics", get(middleware::metrics::handler))
+    .merge(service::docs::docs_routes())
+    .merge(service::health::health_routes())
+    .merge(api)
+    .route_layer(axum::middleware::from_fn_with_state(
+      app_state.clone(),
+      middleware::metrics::track,
+    ))
+    .layer(
+      CorsLayer::new()
+        .allow_origin(Any) // TODO 배포 시 변경
+        .allow_methods(Any)
+        .allow_headers(vec![
+          CONTENT_TYPE,
+          AUTHORIZATION,
+          x_request_id.clone(),
+          HeaderName::from_static(middleware::rate_limit::API_KEY_HEADER),
+        ])
+        .expose_headers(vec![x_request_id, RETRY_AFTER]),
+    )
+    .with_state(app_state);
+
+  middleware::trace::apply(app)
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
-struct Attribute {
-  trait_type: String,
-  value: String,
+#[utoipa::path(
+  post,
+  path = "/json",
+  request_body = Metadata,
+  responses((status = 201, description = "Logged", body = String))
+)]
+async fn get_json(Json(payload): Json<Metadata>) -> (StatusCode, &'static str) {
+  tracing::info!(?payload, "json received");
+  (StatusCode::CREATED, "1231231")
 }
@@ -0,0 +1,29 @@
+use axum::{
+  extract::{Request, State},
+  middleware::Next,
+  response::{IntoResponse, Response},
+};
+
+use crate::config::AppState;
+use crate::errors::AppError;
+use crate::middleware::rate_limit::API_KEY_HEADER;
+
+/// Lets a request through only when `X-Api-Key` holds one of `API_KEYS`.
+/// With no keys configured every request is refused.
+pub async fn require_api_key(
+  State(state): State<AppState>,
+  request: Request,
+  next: Next,
+) -> Response {
+  let authorized = request
+    .headers()
+    .get(API_KEY_HEADER)
+    .and_then(|value| value.to_str().ok())
+    .is_some_and(|key| state.config.api_keys.iter().any(|k| k == key));
+
+  if authorized {
+    next.run(request).await
+  } else {
+    AppError::Unauthorized.into_response()
+  }
+}
@@ -0,0 +1,49 @@
+use axum::{
+  body::Body,
+  extract::{DefaultBodyLimit, Request},
+  http::header::CONTENT_LENGTH,
+  middleware::Next,
+  response::IntoResponse,
+  Router,
+};
+
+use crate::config::AppState;
+use crate::errors::AppError;
+
+/// Caps request bodies on every route currently in `router` at `limit` bytes.
+///
+/// A declared `Content-Length` over the limit is answered with a `413` from
+/// [`AppError`] before the handler runs. Bodies sent without one (chunked)
+/// are read up to the limit first so they get the same JSON `413`; the
+/// extractors' own limit is raised or lowered to match as a backstop.
+pub fn max_body(router: Router<AppState>, limit: usize) -> Router<AppState> {
+  router
+    .route_layer(axum::middleware::from_fn(
+      move |request: Request, next: Next| async move {
+        let declared = request
+          .headers()
+          .get(CONTENT_LENGTH)
+          .and_then(|value| value.to_str().ok())
+          .and_then(|value| value.parse::<usize>().ok());
+
+        match declared {
+          Some(length) if length > limit => {
+            AppError::PayloadTooLarge(limit).into_response()
+          }
+          Some(_) => next.run(request).await,
+          None => {
+            let (parts, body) = request.into_parts();
+            match axum::body::to_bytes(body, limit).await {
+              Ok(bytes) => {
+                next
+                  .run(Request::from_parts(parts, Body::from(bytes)))
+                  .await
+              }
+              Err(_) => AppError::PayloadTooLarge(limit).into_response(),
+            }
+          }
+        }
+      },
+    ))
+    .route_layer(DefaultBodyLimit::max(limit))
+}
@@ -0,0 +1,151 @@
+use std::time::Instant;
+
+use axum::{
+  extract::{MatchedPath, Request, State},
+  http::header::CONTENT_TYPE,
+  middleware::Next,
+  response::{IntoResponse, Response},
+};
+use prometheus::{
+  Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts,
+  Registry, TextEncoder,
+};
+use sqlx::MySqlPool;
+
+use crate::config::AppState;
+
+/// Prometheus collectors for one server instance. Each `Metrics` owns its
+/// registry, so routers built in tests don't share counters.
+#[derive(Clone)]
+pub struct Metrics {
+  registry: Registry,
+  http_requests: IntCounterVec,
+  http_request_duration: HistogramVec,
+  metadata_reads: IntCounterVec,
+  db_pool_connections: IntGaugeVec,
+}
+
+impl Default for Metrics {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Metrics {
+  pub fn new() -> Self {
+    let registry = Registry::new();
+
+    let http_requests = IntCounterVec::new(
+      Opts::new("http_requests_total", "HTTP requests handled"),
+      &["method", "route", "status"],
+    )
+    .unwrap();
+    let http_request_duration = HistogramVec::new(
+      HistogramOpts::new(
+        "http_request_duration_seconds",
+        "HTTP request latency in seconds",
+      ),
+      &["method", "route", "status"],
+    )
+    .unwrap();
+    let metadata_reads = IntCounterVec::new(
+      Opts::new("metadata_reads_total", "Hosted token metadata reads"),
+      &["collection"],
+    )
+    .unwrap();
+    let db_pool_connections = IntGaugeVec::new(
+      Opts::new("db_pool_connections", "MySQL pool connections by state"),
+      &["state"],
+    )
+    .unwrap();
+
+    registry.register(Box::new(http_requests.clone())).unwrap();
+    registry
+      .register(Box::new(http_request_duration.clone()))
+      .unwrap();
+    registry.register(Box::new(metadata_reads.clone())).unwrap();
+    registry
+      .register(Box::new(db_pool_connections.clone()))
+      .unwrap();
+
+    Self {
+      registry,
+      http_requests,
+      http_request_duration,
+      metadata_reads,
+      db_pool_connections,
+    }
+  }
+
+  pub fn metadata_read(&self, collection: &str) {
+    self.metadata_reads.with_label_values(&[collection]).inc();
+  }
+
+  /// Text exposition format, with the pool gauges sampled at call time.
+  pub fn render(&self, db: &MySqlPool) -> String {
+    let size = db.size() as i64;
+    let idle = db.num_idle() as i64;
+    self
+      .db_pool_connections
+      .with_label_values(&["idle"])
+      .set(idle);
+    self
+      .db_pool_connections
+      .with_label_values(&["in_use"])
+      .set(size - idle);
+    self
+      .db_pool_connections
+      .with_label_values(&["max"])
+      .set(db.options().get_max_connections() as i64);
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+      .encode(&self.registry.gather(), &mut buffer)
+      .unwrap();
+
+    String::from_utf8(buffer).unwrap()
+  }
+}
+
+/// Counts and times each request by its route template (`/metadata/:slug/..`)
+/// rather than the raw path, to keep label cardinality bounded.
+pub async fn track(
+  State(metrics): State<Metrics>,
+  request: Request,
+  next: Next,
+) -> Response {
+  let start = Instant::now();
+  let method = request.method().to_string();
+  let route = request
+    .extensions()
+    .get::<MatchedPath>()
+    .map(|path| path.as_str().to_string())
+    .unwrap_or_else(|| "unmatched".to_string());
+
+  let response = next.run(request).await;
+
+  let status = response.status().as_u16().to_string();
+  let labels = [method.as_str(), route.as_str(), status.as_str()];
+  metrics.http_requests.with_label_values(&labels).inc();
+  metrics
+    .http_request_duration
+    .with_label_values(&labels)
+    .observe(start.elapsed().as_secs_f64());
+
+  response
+}
+
+#[utoipa::path(
+  get,
+  path = "/metrics",
+  tag = "metrics",
+  responses(
+    (status = 200, description = "Prometheus text exposition format", body = String, content_type = "text/plain")
+  )
+)]
+pub async fn handler(State(state): State<AppState>) -> impl IntoResponse {
+  (
+    [(CONTENT_TYPE, TextEncoder::new().format_type().to_string())],
+    state.metrics.render(&state.db),
+  )
+}
@@ -0,0 +1,5 @@
+pub mod auth;
+pub mod limit;
+pub mod metrics;
+pub mod rate_limit;
+pub mod trace;
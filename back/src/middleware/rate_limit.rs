@@ -0,0 +1,145 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{
+  extract::{ConnectInfo, Request, State},
+  http::HeaderMap,
+  middleware::Next,
+  response::{IntoResponse, Response},
+};
+use tokio::time::Instant;
+
+use crate::config::AppState;
+use crate::errors::AppError;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Buckets untouched for this long are full again and can be dropped.
+const IDLE_BUCKET_TTL: Duration = Duration::from_secs(600);
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Bucket size and refill rate for one class of client.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+  pub burst: u32,
+  pub per_second: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Client {
+  Ip(IpAddr),
+  ApiKey(String),
+}
+
+struct Bucket {
+  tokens: f64,
+  updated: Instant,
+}
+
+/// Token buckets keyed by client IP, or by API key when the request carries
+/// one of the configured keys in `X-Api-Key`.
+#[derive(Clone)]
+pub struct RateLimiter {
+  ip: RateLimit,
+  api_key: RateLimit,
+  api_keys: Arc<HashSet<String>>,
+  trust_forwarded_for: bool,
+  buckets: Arc<Mutex<HashMap<Client, Bucket>>>,
+}
+
+impl RateLimiter {
+  pub fn new(
+    ip: RateLimit,
+    api_key: RateLimit,
+    api_keys: impl IntoIterator<Item = String>,
+    trust_forwarded_for: bool,
+  ) -> Self {
+    Self {
+      ip,
+      api_key,
+      api_keys: Arc::new(api_keys.into_iter().collect()),
+      trust_forwarded_for,
+      buckets: Arc::new(Mutex::new(HashMap::new())),
+    }
+  }
+
+  /// Takes one token for `client`, or returns how long until one is free.
+  fn acquire(&self, client: Client) -> Result<(), Duration> {
+    let limit = match client {
+      Client::Ip(_) => self.ip,
+      Client::ApiKey(_) => self.api_key,
+    };
+    let now = Instant::now();
+    let mut buckets = self.buckets.lock().unwrap();
+
+    if buckets.len() > PRUNE_THRESHOLD {
+      buckets.retain(|_, bucket| now - bucket.updated < IDLE_BUCKET_TTL);
+    }
+
+    let bucket = buckets.entry(client).or_insert(Bucket {
+      tokens: limit.burst as f64,
+      updated: now,
+    });
+
+    let elapsed = (now - bucket.updated).as_secs_f64();
+    bucket.tokens =
+      (bucket.tokens + elapsed * limit.per_second).min(limit.burst as f64);
+    bucket.updated = now;
+
+    if bucket.tokens >= 1.0 {
+      bucket.tokens -= 1.0;
+      Ok(())
+    } else if limit.per_second > 0.0 {
+      Err(Duration::from_secs_f64(
+        (1.0 - bucket.tokens) / limit.per_second,
+      ))
+    } else {
+      Err(IDLE_BUCKET_TTL)
+    }
+  }
+
+  fn client(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> Client {
+    let api_key = headers
+      .get(API_KEY_HEADER)
+      .and_then(|value| value.to_str().ok())
+      .filter(|key| self.api_keys.contains(*key));
+    if let Some(key) = api_key {
+      return Client::ApiKey(key.to_string());
+    }
+
+    let forwarded = if self.trust_forwarded_for {
+      headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+    } else {
+      None
+    };
+
+    let ip = forwarded
+      .or(peer.map(|addr| addr.ip()))
+      .unwrap_or(IpAddr::from([0, 0, 0, 0]));
+
+    Client::Ip(ip)
+  }
+}
+
+pub async fn limit(
+  State(state): State<AppState>,
+  request: Request,
+  next: Next,
+) -> Response {
+  let peer = request
+    .extensions()
+    .get::<ConnectInfo<SocketAddr>>()
+    .map(|ConnectInfo(addr)| *addr);
+  let client = state.rate_limiter.client(request.headers(), peer);
+
+  match state.rate_limiter.acquire(client) {
+    Ok(()) => next.run(request).await,
+    Err(wait) => AppError::RateLimited(wait).into_response(),
+  }
+}
@@ -0,0 +1,45 @@
+use axum::{body::Body, http::Request, Router};
+use tower::ServiceBuilder;
+use tower_http::{
+  request_id::{
+    MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer,
+  },
+  trace::{DefaultOnResponse, TraceLayer},
+  LatencyUnit,
+};
+use tracing::Level;
+
+/// Wraps the router so every request gets an `X-Request-Id` (kept from the
+/// client if present), runs inside a span carrying that id, and logs its
+/// status and latency on completion. The id is echoed back in the response.
+pub fn apply(router: Router) -> Router {
+  router.layer(
+    ServiceBuilder::new()
+      .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+      .layer(
+        TraceLayer::new_for_http()
+          .make_span_with(make_span)
+          .on_response(
+            DefaultOnResponse::new()
+              .level(Level::INFO)
+              .latency_unit(LatencyUnit::Millis),
+          ),
+      )
+      .layer(PropagateRequestIdLayer::x_request_id()),
+  )
+}
+
+fn make_span(request: &Request<Body>) -> tracing::Span {
+  let request_id = request
+    .extensions()
+    .get::<RequestId>()
+    .and_then(|id| id.header_value().to_str().ok())
+    .unwrap_or_default();
+
+  tracing::info_span!(
+    "request",
+    request_id,
+    method = %request.method(),
+    uri = %request.uri(),
+  )
+}
@@ -0,0 +1,198 @@
+use ethers::types::Address;
+use shared::collection::{
+  Collection, CollectionFields, CollectionQuery, NewCollection, Page,
+};
+use sqlx::{MySql, MySqlPool, QueryBuilder};
+
+use crate::errors::AppError;
+use crate::storage::validate_slug;
+
+const SELECT_COLUMNS: &str = "SELECT id, slug, name, description, chain, \
+  contract_address, base_uri, created_at, updated_at FROM collections";
+
+pub fn validate(fields: &CollectionFields) -> Result<(), AppError> {
+  if fields.name.trim().is_empty() || fields.name.len() > 255 {
+    return Err(AppError::Input("name".to_string(), fields.name.clone()));
+  }
+  if let Some(chain) = &fields.chain {
+    if chain.is_empty() || chain.len() > 64 {
+      return Err(AppError::Input("chain".to_string(), chain.clone()));
+    }
+  }
+  if let Some(contract_address) = &fields.contract_address {
+    if contract_address.len() != 42
+      || contract_address.parse::<Address>().is_err()
+    {
+      return Err(AppError::Input(
+        "contract_address".to_string(),
+        contract_address.clone(),
+      ));
+    }
+  }
+  if let Some(base_uri) = &fields.base_uri {
+    if base_uri.len() > 2048 {
+      return Err(AppError::Input("base_uri".to_string(), base_uri.clone()));
+    }
+  }
+
+  Ok(())
+}
+
+pub async fn list(
+  db: &MySqlPool,
+  query: &CollectionQuery,
+) -> Result<Page<Collection>, AppError> {
+  let page = query.page.unwrap_or(1).max(1);
+  let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+
+  let mut count =
+    QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM collections");
+  push_filters(&mut count, query);
+  let total: i64 = count.build_query_scalar().fetch_one(db).await?;
+
+  let mut select = QueryBuilder::<MySql>::new(SELECT_COLUMNS);
+  push_filters(&mut select, query);
+  select
+    .push(" ORDER BY id DESC LIMIT ")
+    .push_bind(per_page)
+    .push(" OFFSET ")
+    .push_bind((page as u64 - 1) * per_page as u64);
+  let items = select.build_query_as::<Collection>().fetch_all(db).await?;
+
+  Ok(Page {
+    items,
+    page,
+    per_page,
+    total: total as u64,
+  })
+}
+
+pub async fn find(db: &MySqlPool, slug: &str) -> Result<Collection, AppError> {
+  sqlx::query_as::<_, Collection>(&format!("{} WHERE slug = ?", SELECT_COLUMNS))
+    .bind(slug)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("collection {}", slug)))
+}
+
+pub async fn create(
+  db: &MySqlPool,
+  new: &NewCollection,
+) -> Result<Collection, AppError> {
+  validate_slug(&new.slug)?;
+  validate(&new.fields)?;
+
+  sqlx::query(
+    "INSERT INTO collections \
+      (slug, name, description, chain, contract_address, base_uri) \
+      VALUES (?, ?, ?, ?, ?, ?)",
+  )
+  .bind(&new.slug)
+  .bind(&new.fields.name)
+  .bind(&new.fields.description)
+  .bind(&new.fields.chain)
+  .bind(&new.fields.contract_address)
+  .bind(&new.fields.base_uri)
+  .execute(db)
+  .await
+  .map_err(|e| conflict_on_duplicate(e, &new.slug))?;
+
+  find(db, &new.slug).await
+}
+
+pub async fn update(
+  db: &MySqlPool,
+  slug: &str,
+  fields: &CollectionFields,
+) -> Result<Collection, AppError> {
+  validate(fields)?;
+
+  let result = sqlx::query(
+    "UPDATE collections SET name = ?, description = ?, chain = ?, \
+      contract_address = ?, base_uri = ? WHERE slug = ?",
+  )
+  .bind(&fields.name)
+  .bind(&fields.description)
+  .bind(&fields.chain)
+  .bind(&fields.contract_address)
+  .bind(&fields.base_uri)
+  .bind(slug)
+  .execute(db)
+  .await?;
+
+  // sqlx connects with CLIENT_FOUND_ROWS, so this counts matched rows
+  // even when nothing changed.
+  if result.rows_affected() == 0 {
+    return Err(AppError::NotFound(format!("collection {}", slug)));
+  }
+
+  find(db, slug).await
+}
+
+pub async fn delete(db: &MySqlPool, slug: &str) -> Result<(), AppError> {
+  let result = sqlx::query("DELETE FROM collections WHERE slug = ?")
+    .bind(slug)
+    .execute(db)
+    .await?;
+
+  if result.rows_affected() == 0 {
+    return Err(AppError::NotFound(format!("collection {}", slug)));
+  }
+
+  Ok(())
+}
+fn push_filters(
+  builder: &mut QueryBuilder<'_, MySql>,
+  query: &CollectionQuery,
+) {
+  let mut separator = " WHERE ";
+
+  if let Some(chain) = &query.chain {
+    builder
+      .push(separator)
+      .push("chain = ")
+      .push_bind(chain.clone());
+    separator = " AND ";
+  }
+  if let Some(contract_address) = &query.contract_address {
+    builder
+      .push(separator)
+      .push("LOWER(contract_address) = ")
+      .push_bind(contract_address.to_lowercase());
+    separator = " AND ";
+  }
+  if let Some(q) = query.q.as_deref().filter(|q| !q.is_empty()) {
+    let pattern = contains_pattern(&q.to_lowercase());
+    builder
+      .push(separator)
+      .push("(LOWER(name) LIKE ")
+      .push_bind(pattern.clone())
+      .push(" OR slug LIKE ")
+      .push_bind(pattern)
+      .push(")");
+  }
+}
+
+/// `LIKE` pattern matching `q` anywhere, with `%`, `_` and the escape
+/// character itself taken literally.
+pub fn contains_pattern(q: &str) -> String {
+  let mut pattern = String::with_capacity(q.len() + 2);
+  pattern.push('%');
+  for c in q.chars() {
+    if matches!(c, '\\' | '%' | '_') {
+      pattern.push('\\');
+    }
+    pattern.push(c);
+  }
+  pattern.push('%');
+  pattern
+}
+
+fn conflict_on_duplicate(e: sqlx::Error, slug: &str) -> AppError {
+  match &e {
+    sqlx::Error::Database(db) if db.is_unique_violation() => {
+      AppError::Conflict(format!("collection {}", slug))
+    }
+    _ => e.into(),
+  }
+}
@@ -0,0 +1,556 @@
+use std::collections::HashMap;
+
+use ethers::types::{Address, H256, U256, U512};
+use shared::collection::Page;
+use shared::indexer::{
+  EventQuery, Holder, IndexedContract, NewIndexedContract, TokenStandard,
+  TokenTransfer,
+};
+use sqlx::{MySql, MySqlConnection, MySqlPool, QueryBuilder};
+
+use crate::errors::AppError;
+use crate::indexer::{hex_address, Batch, Cursor};
+
+const SELECT_COLUMNS: &str = "SELECT id, chain, address, standard, \
+  start_block, next_block, created_at, updated_at FROM indexed_contracts";
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Checkpoints kept per contract. A reorg older than all of them rescans from
+/// `start_block`.
+const CHECKPOINTS: u32 = 128;
+
+#[derive(sqlx::FromRow)]
+struct ContractRow {
+  id: u64,
+  chain: String,
+  address: String,
+  standard: String,
+  start_block: u64,
+  next_block: u64,
+  created_at: chrono::DateTime<chrono::Utc>,
+  updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ContractRow {
+  fn into_contract(self) -> Result<IndexedContract, AppError> {
+    let standard = self
+      .standard
+      .parse::<TokenStandard>()
+      .map_err(|e| AppError::Db(sqlx::Error::Decode(e.into())))?;
+
+    Ok(IndexedContract {
+      id: self.id,
+      chain: self.chain,
+      address: self.address,
+      standard,
+      start_block: self.start_block,
+      next_block: self.next_block,
+      created_at: self.created_at,
+      updated_at: self.updated_at,
+    })
+  }
+}
+
+pub async fn list(db: &MySqlPool) -> Result<Vec<IndexedContract>, AppError> {
+  sqlx::query_as::<_, ContractRow>(&format!("{} ORDER BY id", SELECT_COLUMNS))
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(ContractRow::into_contract)
+    .collect()
+}
+
+pub async fn find(
+  db: &MySqlPool,
+  id: u64,
+) -> Result<IndexedContract, AppError> {
+  sqlx::query_as::<_, ContractRow>(&format!("{} WHERE id = ?", SELECT_COLUMNS))
+    .bind(id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("contract {}", id)))?
+    .into_contract()
+}
+
+pub async fn create(
+  db: &MySqlPool,
+  new: &NewIndexedContract,
+) -> Result<IndexedContract, AppError> {
+  let address = new
+    .address
+    .parse::<Address>()
+    .map_err(|_| AppError::Input("address".to_string(), new.address.clone()))?;
+
+  let result = sqlx::query(
+    "INSERT INTO indexed_contracts \
+      (chain, address, standard, start_block, next_block) \
+      VALUES (?, ?, ?, ?, ?)",
+  )
+  .bind(&new.chain)
+  .bind(hex_address(address))
+  .bind(new.standard.as_str())
+  .bind(new.start_block)
+  .bind(new.start_block)
+  .execute(db)
+  .await
+  .map_err(|e| match &e {
+    sqlx::Error::Database(db) if db.is_unique_violation() => {
+      AppError::Conflict(format!("contract {} on {}", new.address, new.chain))
+    }
+    _ => e.into(),
+  })?;
+
+  find(db, result.last_insert_id()).await
+}
+
+pub async fn cursor(db: &MySqlPool, id: u64) -> Result<Cursor, AppError> {
+  let (start_block, next_block, last_block_hash): (u64, u64, Option<String>) =
+    sqlx::query_as(
+      "SELECT start_block, next_block, last_block_hash \
+        FROM indexed_contracts WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("contract {}", id)))?;
+
+  let checkpoints: Vec<(u64, String)> = sqlx::query_as(
+    "SELECT block_number, block_hash FROM indexed_blocks \
+      WHERE contract_id = ? ORDER BY block_number DESC LIMIT ?",
+  )
+  .bind(id)
+  .bind(CHECKPOINTS)
+  .fetch_all(db)
+  .await?;
+
+  Ok(Cursor {
+    start_block,
+    next_block,
+    last_block_hash: last_block_hash.and_then(|hash| hash.parse::<H256>().ok()),
+    checkpoints: checkpoints
+      .into_iter()
+      .filter_map(|(number, hash)| Some((number, hash.parse::<H256>().ok()?)))
+      .collect(),
+  })
+}
+
+/// Stores a batch and moves the cursor past it in one transaction. Rows
+/// already in the range (from an interrupted run) are replaced, and the hash
+/// of `batch.to` is kept as a checkpoint.
+pub async fn store_batch(
+  db: &MySqlPool,
+  id: u64,
+  batch: &Batch,
+) -> Result<(), AppError> {
+  let mut tx = db.begin().await?;
+
+  let replaced = transfers_from(&mut tx, id, batch.from).await?;
+  update_balances(&mut tx, id, &batch.transfers, &replaced).await?;
+
+  for table in ["token_transfers", "token_approvals", "indexed_blocks"] {
+    sqlx::query(&format!(
+      "DELETE FROM {} WHERE contract_id = ? AND block_number >= ?",
+      table
+    ))
+    .bind(id)
+    .bind(batch.from)
+    .execute(&mut *tx)
+    .await?;
+  }
+
+  for transfer in batch.transfers.iter() {
+    sqlx::query(
+      "INSERT INTO token_transfers (contract_id, block_number, tx_hash, \
+        log_index, from_address, to_address, token_id, value) \
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(transfer.block_number)
+    .bind(&transfer.tx_hash)
+    .bind(transfer.log_index)
+    .bind(&transfer.from_address)
+    .bind(&transfer.to_address)
+    .bind(&transfer.token_id)
+    .bind(&transfer.value)
+    .execute(&mut *tx)
+    .await?;
+  }
+
+  for approval in batch.approvals.iter() {
+    sqlx::query(
+      "INSERT INTO token_approvals (contract_id, block_number, tx_hash, \
+        log_index, owner_address, spender_address, token_id, value) \
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(approval.block_number)
+    .bind(&approval.tx_hash)
+    .bind(approval.log_index)
+    .bind(&approval.owner_address)
+    .bind(&approval.spender_address)
+    .bind(&approval.token_id)
+    .bind(&approval.value)
+    .execute(&mut *tx)
+    .await?;
+  }
+
+  sqlx::query(
+    "INSERT INTO indexed_blocks (contract_id, block_number, block_hash) \
+      VALUES (?, ?, ?)",
+  )
+  .bind(id)
+  .bind(batch.to)
+  .bind(format!("{:#x}", batch.block_hash))
+  .execute(&mut *tx)
+  .await?;
+
+  // The derived table lets MySQL read the table it deletes from.
+  sqlx::query(
+    "DELETE FROM indexed_blocks WHERE contract_id = ? AND block_number < ( \
+      SELECT block_number FROM ( \
+        SELECT block_number FROM indexed_blocks WHERE contract_id = ? \
+          ORDER BY block_number DESC LIMIT 1 OFFSET ? \
+      ) oldest)",
+  )
+  .bind(id)
+  .bind(id)
+  .bind(CHECKPOINTS - 1)
+  .execute(&mut *tx)
+  .await?;
+
+  sqlx::query(
+    "UPDATE indexed_contracts SET next_block = ?, last_block_hash = ? \
+      WHERE id = ?",
+  )
+  .bind(batch.to + 1)
+  .bind(format!("{:#x}", batch.block_hash))
+  .bind(id)
+  .execute(&mut *tx)
+  .await?;
+
+  tx.commit().await?;
+
+  Ok(())
+}
+
+/// Drops everything from `cursor.next_block` on and moves the cursor back.
+pub async fn rewind(
+  db: &MySqlPool,
+  id: u64,
+  cursor: &Cursor,
+) -> Result<(), AppError> {
+  let mut tx = db.begin().await?;
+
+  let dropped = transfers_from(&mut tx, id, cursor.next_block).await?;
+  update_balances(&mut tx, id, &[], &dropped).await?;
+
+  for table in ["token_transfers", "token_approvals", "indexed_blocks"] {
+    sqlx::query(&format!(
+      "DELETE FROM {} WHERE contract_id = ? AND block_number >= ?",
+      table
+    ))
+    .bind(id)
+    .bind(cursor.next_block)
+    .execute(&mut *tx)
+    .await?;
+  }
+
+  sqlx::query(
+    "UPDATE indexed_contracts SET next_block = ?, last_block_hash = ? \
+      WHERE id = ?",
+  )
+  .bind(cursor.next_block)
+  .bind(cursor.last_block_hash.map(|hash| format!("{:#x}", hash)))
+  .bind(id)
+  .execute(&mut *tx)
+  .await?;
+
+  tx.commit().await?;
+
+  Ok(())
+}
+
+/// Stored transfers from block `from` on, about to be deleted.
+async fn transfers_from(
+  conn: &mut MySqlConnection,
+  id: u64,
+  from: u64,
+) -> Result<Vec<TokenTransfer>, AppError> {
+  Ok(
+    sqlx::query_as::<_, TokenTransfer>(
+      "SELECT block_number, tx_hash, log_index, from_address, to_address, \
+        token_id, value FROM token_transfers \
+        WHERE contract_id = ? AND block_number >= ?",
+    )
+    .bind(id)
+    .bind(from)
+    .fetch_all(conn)
+    .await?,
+  )
+}
+
+/// Moves `token_balances` by the ERC-20 transfers being stored (`added`) and
+/// deleted (`removed`). ERC-721 transfers carry no value and change nothing.
+async fn update_balances(
+  conn: &mut MySqlConnection,
+  id: u64,
+  added: &[TokenTransfer],
+  removed: &[TokenTransfer],
+) -> Result<(), AppError> {
+  let mut changes = erc20_changes(added)?;
+  for (address, (credit, debit)) in erc20_changes(removed)? {
+    let change = changes.entry(address).or_default();
+    *change = settle(change.0 + debit, change.1 + credit);
+  }
+
+  for (address, (credit, debit)) in changes {
+    if credit.is_zero() && debit.is_zero() {
+      continue;
+    }
+
+    let stored: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+      "SELECT balance, deficit FROM token_balances \
+        WHERE contract_id = ? AND address = ? FOR UPDATE",
+    )
+    .bind(id)
+    .bind(&address)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let (balance, deficit) = stored.unwrap_or_default();
+    let (balance, deficit) =
+      settle(credit + decimal(balance)?, debit + decimal(deficit)?);
+
+    if balance.is_zero() && deficit.is_zero() {
+      sqlx::query(
+        "DELETE FROM token_balances WHERE contract_id = ? AND address = ?",
+      )
+      .bind(id)
+      .bind(&address)
+      .execute(&mut *conn)
+      .await?;
+      continue;
+    }
+
+    let balance = (!balance.is_zero()).then(|| format!("{:0>155}", balance));
+    let deficit = (!deficit.is_zero()).then(|| deficit.to_string());
+    sqlx::query(
+      "INSERT INTO token_balances (contract_id, address, balance, deficit) \
+        VALUES (?, ?, ?, ?) ON DUPLICATE KEY UPDATE balance = ?, deficit = ?",
+    )
+    .bind(id)
+    .bind(&address)
+    .bind(&balance)
+    .bind(&deficit)
+    .bind(&balance)
+    .bind(&deficit)
+    .execute(&mut *conn)
+    .await?;
+  }
+
+  Ok(())
+}
+
+fn decimal(value: Option<String>) -> Result<U512, AppError> {
+  value.map_or(Ok(U512::zero()), |value| {
+    U512::from_dec_str(&value)
+      .map_err(|e| AppError::Db(sqlx::Error::Decode(e.into())))
+  })
+}
+
+/// Newest first, optionally only those from or to `query.address`.
+pub async fn transfers(
+  db: &MySqlPool,
+  id: u64,
+  query: &EventQuery,
+) -> Result<Page<TokenTransfer>, AppError> {
+  let (page, per_page) = paging(query);
+  let address = match &query.address {
+    Some(address) => {
+      Some(hex_address(address.parse::<Address>().map_err(|_| {
+        AppError::Input("address".to_string(), address.clone())
+      })?))
+    }
+    None => None,
+  };
+
+  let push_filters = |builder: &mut QueryBuilder<'_, MySql>| {
+    builder.push(" WHERE contract_id = ").push_bind(id);
+    if let Some(address) = &address {
+      builder
+        .push(" AND (from_address = ")
+        .push_bind(address.clone())
+        .push(" OR to_address = ")
+        .push_bind(address.clone())
+        .push(")");
+    }
+  };
+
+  let mut count =
+    QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM token_transfers");
+  push_filters(&mut count);
+  let total: i64 = count.build_query_scalar().fetch_one(db).await?;
+
+  let mut select = QueryBuilder::<MySql>::new(
+    "SELECT block_number, tx_hash, log_index, from_address, to_address, \
+      token_id, value FROM token_transfers",
+  );
+  push_filters(&mut select);
+  select
+    .push(" ORDER BY block_number DESC, log_index DESC LIMIT ")
+    .push_bind(per_page)
+    .push(" OFFSET ")
+    .push_bind((page as u64 - 1) * per_page as u64);
+  let items = select
+    .build_query_as::<TokenTransfer>()
+    .fetch_all(db)
+    .await?;
+
+  Ok(Page {
+    items,
+    page,
+    per_page,
+    total: total as u64,
+  })
+}
+
+/// Current holders by balance, largest first. ERC-721 ownership is the
+/// receiver of each token's latest transfer; ERC-20 balances are read from
+/// `token_balances`, kept up to date as batches are stored.
+pub async fn holders(
+  db: &MySqlPool,
+  contract: &IndexedContract,
+  query: &EventQuery,
+) -> Result<Page<Holder>, AppError> {
+  match contract.standard {
+    TokenStandard::Erc721 => erc721_holders(db, contract.id, query).await,
+    TokenStandard::Erc20 => erc20_holders(db, contract.id, query).await,
+  }
+}
+
+async fn erc20_holders(
+  db: &MySqlPool,
+  id: u64,
+  query: &EventQuery,
+) -> Result<Page<Holder>, AppError> {
+  let (page, per_page) = paging(query);
+
+  let total: i64 = sqlx::query_scalar(
+    "SELECT COUNT(*) FROM token_balances \
+      WHERE contract_id = ? AND balance IS NOT NULL",
+  )
+  .bind(id)
+  .fetch_one(db)
+  .await?;
+
+  let items = sqlx::query_as::<_, Holder>(
+    "SELECT address, TRIM(LEADING '0' FROM balance) AS balance \
+      FROM token_balances WHERE contract_id = ? AND balance IS NOT NULL \
+      ORDER BY balance DESC, address LIMIT ? OFFSET ?",
+  )
+  .bind(id)
+  .bind(per_page)
+  .bind((page as u64 - 1) * per_page as u64)
+  .fetch_all(db)
+  .await?;
+
+  Ok(Page {
+    items,
+    page,
+    per_page,
+    total: total as u64,
+  })
+}
+
+async fn erc721_holders(
+  db: &MySqlPool,
+  id: u64,
+  query: &EventQuery,
+) -> Result<Page<Holder>, AppError> {
+  let (page, per_page) = paging(query);
+
+  let mut count = QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM (");
+  push_erc721_balances(&mut count, id);
+  count.push(") holders");
+  let total: i64 = count.build_query_scalar().fetch_one(db).await?;
+
+  let mut select = QueryBuilder::<MySql>::new(
+    "SELECT address, CAST(balance AS CHAR) AS balance FROM (",
+  );
+  push_erc721_balances(&mut select, id);
+  select
+    .push(") holders ORDER BY holders.balance DESC, address LIMIT ")
+    .push_bind(per_page)
+    .push(" OFFSET ")
+    .push_bind((page as u64 - 1) * per_page as u64);
+  let items = select.build_query_as::<Holder>().fetch_all(db).await?;
+
+  Ok(Page {
+    items,
+    page,
+    per_page,
+    total: total as u64,
+  })
+}
+
+/// `(address, balance)` rows for every ERC-721 holder.
+fn push_erc721_balances(builder: &mut QueryBuilder<'_, MySql>, id: u64) {
+  builder
+    .push(
+      "SELECT owner AS address, COUNT(*) AS balance FROM ( \
+        SELECT to_address AS owner, ROW_NUMBER() OVER ( \
+          PARTITION BY token_id ORDER BY block_number DESC, log_index DESC \
+        ) AS latest FROM token_transfers WHERE contract_id = ",
+    )
+    .push_bind(id)
+    .push(") owners WHERE latest = 1 AND owner <> ")
+    .push_bind(ZERO_ADDRESS)
+    .push(" GROUP BY owner");
+}
+
+/// Net ERC-20 change per holder from `transfers` as `(credit, debit)`, at
+/// most one side non-zero. Sums are kept in 512 bits so no run of `uint256`
+/// amounts can overflow them; the mint and burn address is left out, as are
+/// holders the transfers leave where they were.
+pub fn erc20_changes(
+  transfers: &[TokenTransfer],
+) -> Result<HashMap<String, (U512, U512)>, AppError> {
+  let mut totals = HashMap::<&str, (U512, U512)>::new();
+  for transfer in transfers {
+    let Some(value) = &transfer.value else {
+      continue;
+    };
+    let value = U256::from_dec_str(value)
+      .map_err(|e| AppError::Db(sqlx::Error::Decode(e.into())))?;
+    totals.entry(&transfer.to_address).or_default().0 += U512::from(value);
+    totals.entry(&transfer.from_address).or_default().1 += U512::from(value);
+  }
+
+  Ok(
+    totals
+      .into_iter()
+      .filter(|(address, (credit, debit))| {
+        *address != ZERO_ADDRESS && credit != debit
+      })
+      .map(|(address, (credit, debit))| {
+        (address.to_string(), settle(credit, debit))
+      })
+      .collect(),
+  )
+}
+
+/// Cancels the common part of a credit and a debit.
+fn settle(credit: U512, debit: U512) -> (U512, U512) {
+  if credit > debit {
+    (credit - debit, U512::zero())
+  } else {
+    (U512::zero(), debit - credit)
+  }
+}
+
+fn paging(query: &EventQuery) -> (u32, u32) {
+  (
+    query.page.unwrap_or(1).max(1),
+    query.per_page.unwrap_or(20).clamp(1, 100),
+  )
+}
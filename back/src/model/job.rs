@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use shared::job::{Job, JobRequest, JobStatus};
+use sqlx::mysql::MySqlQueryResult;
+use sqlx::MySqlPool;
+
+use crate::errors::AppError;
+
+const SELECT_COLUMNS: &str =
+  "SELECT id, kind, status, progress, total, error, \
+  created_at, updated_at FROM jobs";
+
+#[derive(sqlx::FromRow)]
+struct JobRow {
+  id: u64,
+  kind: String,
+  status: String,
+  progress: u32,
+  total: u32,
+  error: Option<String>,
+  created_at: chrono::DateTime<chrono::Utc>,
+  updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl JobRow {
+  fn into_job(self) -> Result<Job, AppError> {
+    let status = self
+      .status
+      .parse::<JobStatus>()
+      .map_err(|e| AppError::Db(sqlx::Error::Decode(e.into())))?;
+
+    Ok(Job {
+      id: self.id,
+      kind: self.kind,
+      status,
+      progress: self.progress,
+      total: self.total,
+      error: self.error,
+      created_at: self.created_at,
+      updated_at: self.updated_at,
+    })
+  }
+}
+
+/// A worker's hold on a running job: its ID and the `claim` counter the
+/// worker claimed it at. Every write from the worker requires both, so once
+/// the lease lapses and another worker claims the job, the first one's
+/// writes match nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Claim {
+  pub id: u64,
+  pub token: u32,
+}
+
+pub fn kind(request: &JobRequest) -> &'static str {
+  match request {
+    JobRequest::Generate { .. } => "generate",
+  }
+}
+
+/// Units of work reported through `progress`.
+pub fn total(request: &JobRequest) -> u32 {
+  match request {
+    JobRequest::Generate { count, .. } => *count,
+  }
+}
+
+pub async fn create(
+  db: &MySqlPool,
+  request: &JobRequest,
+) -> Result<Job, AppError> {
+  let body = serde_json::to_string(request)
+    .map_err(|e| AppError::Db(sqlx::Error::Decode(e.into())))?;
+
+  let result =
+    sqlx::query("INSERT INTO jobs (kind, request, total) VALUES (?, ?, ?)")
+      .bind(kind(request))
+      .bind(body)
+      .bind(total(request))
+      .execute(db)
+      .await?;
+
+  find(db, result.last_insert_id()).await
+}
+
+pub async fn find(db: &MySqlPool, id: u64) -> Result<Job, AppError> {
+  sqlx::query_as::<_, JobRow>(&format!("{} WHERE id = ?", SELECT_COLUMNS))
+    .bind(id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("job {}", id)))?
+    .into_job()
+}
+
+/// Marks the oldest claimable job running under a `lease` and returns it
+/// with its request. Claimable means queued, or running with a lease that
+/// lapsed because its worker stopped renewing it (the process died).
+///
+/// `SKIP LOCKED` lets several workers (or instances) claim concurrently
+/// without handing out the same job twice.
+pub async fn claim_next(
+  db: &MySqlPool,
+  lease: Duration,
+) -> Result<Option<(Claim, JobRequest)>, AppError> {
+  let mut tx = db.begin().await?;
+
+  let row: Option<(u64, u32, String)> = sqlx::query_as(
+    "SELECT id, claim, CAST(request AS CHAR) FROM jobs WHERE status = 'queued' \
+      OR (status = 'running' AND (lease_until IS NULL OR lease_until < NOW(6))) \
+      ORDER BY id LIMIT 1 FOR UPDATE SKIP LOCKED",
+  )
+  .fetch_optional(&mut *tx)
+  .await?;
+
+  let Some((id, token, request)) = row else {
+    return Ok(None);
+  };
+  let claim = Claim {
+    id,
+    token: token.wrapping_add(1),
+  };
+
+  sqlx::query(
+    "UPDATE jobs SET status = 'running', progress = 0, error = NULL, \
+      lease_until = NOW(6) + INTERVAL ? MICROSECOND, claim = ? WHERE id = ?",
+  )
+  .bind(lease.as_micros() as u64)
+  .bind(claim.token)
+  .bind(id)
+  .execute(&mut *tx)
+  .await?;
+  tx.commit().await?;
+
+  match serde_json::from_str::<JobRequest>(&request) {
+    Ok(request) => Ok(Some((claim, request))),
+    Err(e) => {
+      fail(db, claim, &format!("unreadable request: {}", e)).await?;
+      Ok(None)
+    }
+  }
+}
+
+/// Heartbeat from the worker holding `claim`, pushing its lease out again.
+/// Fails with [`AppError::Conflict`] once another worker has claimed the job.
+pub async fn renew_lease(
+  db: &MySqlPool,
+  claim: Claim,
+  lease: Duration,
+) -> Result<(), AppError> {
+  let result = sqlx::query(
+    "UPDATE jobs SET lease_until = NOW(6) + INTERVAL ? MICROSECOND \
+      WHERE id = ? AND claim = ? AND status = 'running'",
+  )
+  .bind(lease.as_micros() as u64)
+  .bind(claim.id)
+  .bind(claim.token)
+  .execute(db)
+  .await?;
+
+  held(claim, result)
+}
+
+pub async fn set_progress(
+  db: &MySqlPool,
+  claim: Claim,
+  progress: u32,
+) -> Result<(), AppError> {
+  let result = sqlx::query(
+    "UPDATE jobs SET progress = ? \
+      WHERE id = ? AND claim = ? AND status = 'running'",
+  )
+  .bind(progress)
+  .bind(claim.id)
+  .bind(claim.token)
+  .execute(db)
+  .await?;
+
+  held(claim, result)
+}
+
+pub async fn finish(db: &MySqlPool, claim: Claim) -> Result<(), AppError> {
+  let result = sqlx::query(
+    "UPDATE jobs SET status = 'done', progress = total, lease_until = NULL \
+      WHERE id = ? AND claim = ? AND status = 'running'",
+  )
+  .bind(claim.id)
+  .bind(claim.token)
+  .execute(db)
+  .await?;
+
+  held(claim, result)
+}
+
+pub async fn fail(
+  db: &MySqlPool,
+  claim: Claim,
+  error: &str,
+) -> Result<(), AppError> {
+  let result = sqlx::query(
+    "UPDATE jobs SET status = 'failed', error = ?, lease_until = NULL \
+      WHERE id = ? AND claim = ? AND status = 'running'",
+  )
+  .bind(error)
+  .bind(claim.id)
+  .bind(claim.token)
+  .execute(db)
+  .await?;
+
+  held(claim, result)
+}
+
+/// Puts a job back in the queue, e.g. when a worker is stopped mid-run.
+pub async fn requeue(db: &MySqlPool, claim: Claim) -> Result<(), AppError> {
+  let result = sqlx::query(
+    "UPDATE jobs SET status = 'queued', progress = 0, lease_until = NULL \
+      WHERE id = ? AND claim = ? AND status = 'running'",
+  )
+  .bind(claim.id)
+  .bind(claim.token)
+  .execute(db)
+  .await?;
+
+  held(claim, result)
+}
+
+/// An update that matched no row means the job is no longer ours.
+fn held(claim: Claim, result: MySqlQueryResult) -> Result<(), AppError> {
+  if result.rows_affected() == 0 {
+    return Err(AppError::Conflict(format!(
+      "job {} was claimed by another worker",
+      claim.id
+    )));
+  }
+  Ok(())
+}
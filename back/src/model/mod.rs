@@ -1 +1,3 @@
-
+pub mod collection;
+pub mod indexer;
+pub mod job;
@@ -0,0 +1,125 @@
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+  routing::{get, post, put},
+  Json, Router,
+};
+use shared::collection::{
+  Collection, CollectionFields, CollectionPage, CollectionQuery, NewCollection,
+};
+
+use crate::config::AppState;
+use crate::errors::AppError;
+use crate::model::collection;
+
+pub fn collection_routes() -> Router<AppState> {
+  Router::new()
+    .route("/collections", get(list_collections))
+    .route("/collections/:slug", get(get_collection))
+}
+
+/// Creating, renaming and deleting collections; these need an API key.
+pub fn collection_write_routes() -> Router<AppState> {
+  Router::new()
+    .route("/collections", post(create_collection))
+    .route(
+      "/collections/:slug",
+      put(update_collection).delete(delete_collection),
+    )
+}
+
+#[utoipa::path(
+  get,
+  path = "/collections",
+  tag = "collections",
+  params(CollectionQuery),
+  responses(
+    (status = 200, description = "One page of collections, newest first", body = CollectionPage),
+  )
+)]
+async fn list_collections(
+  State(state): State<AppState>,
+  Query(query): Query<CollectionQuery>,
+) -> Result<Json<CollectionPage>, AppError> {
+  Ok(Json(collection::list(&state.db, &query).await?))
+}
+
+#[utoipa::path(
+  post,
+  path = "/collections",
+  tag = "collections",
+  request_body = NewCollection,
+  responses(
+    (status = 201, description = "Created", body = Collection),
+    (status = 400, description = "Invalid field", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+    (status = 409, description = "Slug already taken", body = ErrorResponse),
+  )
+)]
+async fn create_collection(
+  State(state): State<AppState>,
+  Json(new): Json<NewCollection>,
+) -> Result<(StatusCode, Json<Collection>), AppError> {
+  let collection = collection::create(&state.db, &new).await?;
+
+  Ok((StatusCode::CREATED, Json(collection)))
+}
+
+#[utoipa::path(
+  get,
+  path = "/collections/{slug}",
+  tag = "collections",
+  params(("slug" = String, Path, description = "Collection slug")),
+  responses(
+    (status = 200, description = "The collection", body = Collection),
+    (status = 404, description = "No such collection", body = ErrorResponse),
+  )
+)]
+async fn get_collection(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+) -> Result<Json<Collection>, AppError> {
+  Ok(Json(collection::find(&state.db, &slug).await?))
+}
+
+#[utoipa::path(
+  put,
+  path = "/collections/{slug}",
+  tag = "collections",
+  params(("slug" = String, Path, description = "Collection slug")),
+  request_body = CollectionFields,
+  responses(
+    (status = 200, description = "Updated", body = Collection),
+    (status = 400, description = "Invalid field", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+    (status = 404, description = "No such collection", body = ErrorResponse),
+  )
+)]
+async fn update_collection(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+  Json(fields): Json<CollectionFields>,
+) -> Result<Json<Collection>, AppError> {
+  Ok(Json(collection::update(&state.db, &slug, &fields).await?))
+}
+
+/// Removes the record only; metadata hosted under the slug stays on disk.
+#[utoipa::path(
+  delete,
+  path = "/collections/{slug}",
+  tag = "collections",
+  params(("slug" = String, Path, description = "Collection slug")),
+  responses(
+    (status = 204, description = "Deleted"),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+    (status = 404, description = "No such collection", body = ErrorResponse),
+  )
+)]
+async fn delete_collection(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+) -> Result<StatusCode, AppError> {
+  collection::delete(&state.db, &slug).await?;
+
+  Ok(StatusCode::NO_CONTENT)
+}
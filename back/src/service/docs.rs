@@ -0,0 +1,135 @@
+use axum::{
+  http::header::{CACHE_CONTROL, CONTENT_TYPE},
+  response::{Html, IntoResponse},
+  routing::get,
+  Json, Router,
+};
+use shared::collection::{
+  Collection, CollectionFields, CollectionPage, HolderPage, NewCollection,
+  TransferPage,
+};
+use shared::indexer::{
+  Holder, IndexedContract, NewIndexedContract, TokenStandard, TokenTransfer,
+};
+use shared::job::{Job, JobRequest, JobStatus};
+use shared::metadata::{Attribute, Metadata};
+use shared::reveal::RevealSettings;
+use utoipa::OpenApi;
+
+use crate::config::AppState;
+use crate::errors::ErrorResponse;
+use crate::service::{collection, health, indexer, job, metadata};
+
+/// OpenAPI 3 document for every route mounted by [`crate::app`].
+///
+/// `back/openapi.json` is the committed copy; `tests/openapi.rs` fails when
+/// it no longer matches. Regenerate it with
+/// `UPDATE_OPENAPI=1 cargo test -p back --test openapi`.
+#[derive(OpenApi)]
+#[openapi(
+  info(
+    title = "Toys backend",
+    description = "Metadata hosting and tooling API for the Toys front end"
+  ),
+  paths(
+    crate::get_json,
+    crate::middleware::metrics::handler,
+    collection::list_collections,
+    collection::create_collection,
+    collection::get_collection,
+    collection::update_collection,
+    collection::delete_collection,
+    indexer::list_contracts,
+    indexer::create_contract,
+    indexer::get_contract,
+    indexer::list_transfers,
+    indexer::list_holders,
+    job::create_job,
+    job::get_job,
+    job::get_job_result,
+    health::live,
+    health::ready,
+    metadata::get_token_metadata,
+    metadata::put_token_metadata,
+    metadata::get_placeholder,
+    metadata::put_placeholder,
+    metadata::get_reveal_settings,
+    metadata::put_reveal_settings,
+  ),
+  components(schemas(
+    Attribute,
+    Metadata,
+    Collection,
+    CollectionFields,
+    CollectionPage,
+    ErrorResponse,
+    Holder,
+    HolderPage,
+    IndexedContract,
+    Job,
+    JobRequest,
+    JobStatus,
+    NewCollection,
+    NewIndexedContract,
+    RevealSettings,
+    TokenStandard,
+    TokenTransfer,
+    TransferPage,
+    health::HealthReport,
+    health::CheckResult,
+    health::Status,
+  )),
+  tags(
+    (name = "collections", description = "Collection records"),
+    (name = "health", description = "Liveness and readiness"),
+    (name = "indexer", description = "Indexed ERC-20/ERC-721 events"),
+    (name = "jobs", description = "Background generation jobs"),
+    (name = "metadata", description = "Hosted token metadata and reveal"),
+    (name = "metrics", description = "Prometheus metrics"),
+  )
+)]
+pub struct ApiDoc;
+
+pub fn docs_routes() -> Router<AppState> {
+  Router::new()
+    .route("/openapi.json", get(openapi_json))
+    .route("/docs", get(docs_page))
+    .route("/docs/swagger-ui-bundle.js", get(swagger_ui_js))
+    .route("/docs/swagger-ui.css", get(swagger_ui_css))
+}
+
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+  Json(ApiDoc::openapi())
+}
+
+async fn docs_page() -> Html<&'static str> {
+  Html(include_str!("docs.html"))
+}
+
+// Swagger UI 5.17.14 from the `dist` folder of the upstream release,
+// vendored so the docs page runs no third-party script. Replace both files
+// together when upgrading.
+
+async fn swagger_ui_js() -> impl IntoResponse {
+  asset(
+    "text/javascript; charset=utf-8",
+    include_str!("swagger-ui/swagger-ui-bundle.js"),
+  )
+}
+
+async fn swagger_ui_css() -> impl IntoResponse {
+  asset(
+    "text/css; charset=utf-8",
+    include_str!("swagger-ui/swagger-ui.css"),
+  )
+}
+
+fn asset(content_type: &'static str, body: &'static str) -> impl IntoResponse {
+  (
+    [
+      (CONTENT_TYPE, content_type),
+      (CACHE_CONTROL, "public, max-age=86400"),
+    ],
+    body,
+  )
+}
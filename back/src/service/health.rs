@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::Instant;
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use ethers::providers::Middleware;
+use futures::future::join_all;
+
+use crate::config::AppState;
+
+pub fn health_routes() -> Router<AppState> {
+  Router::new()
+    .route("/health", get(live))
+    .route("/health/live", get(live))
+    .route("/health/ready", get(ready))
+}
+
+#[derive(
+  serde::Serialize,
+  serde::Deserialize,
+  utoipa::ToSchema,
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+  Ok,
+  Error,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug)]
+pub struct HealthReport {
+  pub status: Status,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub checks: BTreeMap<String, CheckResult>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema, Debug)]
+pub struct CheckResult {
+  pub status: Status,
+  pub latency_ms: u64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+}
+
+/// The process is up and serving; says nothing about its dependencies.
+#[utoipa::path(
+  get,
+  path = "/health/live",
+  tag = "health",
+  responses((status = 200, description = "Process is up", body = HealthReport))
+)]
+async fn live() -> Json<HealthReport> {
+  Json(HealthReport {
+    status: Status::Ok,
+    checks: BTreeMap::new(),
+  })
+}
+
+/// `200` when every check passes, `503` with the failing ones marked
+/// otherwise. Each check is bounded by `READY_CHECK_TIMEOUT_MS`.
+#[utoipa::path(
+  get,
+  path = "/health/ready",
+  tag = "health",
+  responses(
+    (status = 200, description = "All checks passed", body = HealthReport),
+    (status = 503, description = "At least one check failed", body = HealthReport),
+  )
+)]
+async fn ready(
+  State(state): State<AppState>,
+) -> (StatusCode, Json<HealthReport>) {
+  let mut checks = BTreeMap::new();
+
+  let (db, storage) = tokio::join!(
+    run_check(&state, check_db(&state)),
+    run_check(&state, check_storage(&state)),
+  );
+  checks.insert("db".to_string(), db);
+  checks.insert("storage".to_string(), storage);
+
+  if state.config.ready_check_rpc {
+    let chains = state.chains.names();
+    let results = join_all(
+      chains
+        .iter()
+        .map(|chain| run_check(&state, check_rpc(&state, chain))),
+    )
+    .await;
+    for (chain, result) in chains.iter().zip(results) {
+      checks.insert(format!("rpc:{}", chain), result);
+    }
+  }
+
+  let healthy = checks.values().all(|check| check.status == Status::Ok);
+  let (code, status) = if healthy {
+    (StatusCode::OK, Status::Ok)
+  } else {
+    (StatusCode::SERVICE_UNAVAILABLE, Status::Error)
+  };
+
+  (code, Json(HealthReport { status, checks }))
+}
+
+async fn run_check(
+  state: &AppState,
+  check: impl Future<Output = Result<(), String>>,
+) -> CheckResult {
+  let start = Instant::now();
+  let result =
+    match tokio::time::timeout(state.config.ready_check_timeout, check).await {
+      Ok(result) => result,
+      Err(_) => Err("timed out".to_string()),
+    };
+  let latency_ms = start.elapsed().as_millis() as u64;
+
+  match result {
+    Ok(()) => CheckResult {
+      status: Status::Ok,
+      latency_ms,
+      error: None,
+    },
+    Err(e) => {
+      tracing::warn!("Readiness check failed: {}", e);
+      CheckResult {
+        status: Status::Error,
+        latency_ms,
+        error: Some(e),
+      }
+    }
+  }
+}
+
+async fn check_db(state: &AppState) -> Result<(), String> {
+  sqlx::query("SELECT 1")
+    .execute(&state.db)
+    .await
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Writes and removes a probe file under the storage root.
+async fn check_storage(state: &AppState) -> Result<(), String> {
+  let root = state.storage.root();
+  let probe = root.join(".ready-probe");
+
+  async {
+    tokio::fs::create_dir_all(root).await?;
+    tokio::fs::write(&probe, b"ok").await?;
+    tokio::fs::remove_file(&probe).await
+  }
+  .await
+  .map_err(|e: std::io::Error| format!("{}: {}", root.display(), e))
+}
+
+async fn check_rpc(state: &AppState, chain: &str) -> Result<(), String> {
+  let provider = state
+    .chains
+    .provider(chain)
+    .ok_or_else(|| format!("unknown chain {}", chain))?;
+
+  provider
+    .get_block_number()
+    .await
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
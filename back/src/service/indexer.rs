@@ -0,0 +1,125 @@
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+  routing::{get, post},
+  Json, Router,
+};
+use shared::collection::{HolderPage, TransferPage};
+use shared::indexer::{EventQuery, IndexedContract, NewIndexedContract};
+
+use crate::config::AppState;
+use crate::errors::AppError;
+use crate::model::indexer;
+
+pub fn indexer_routes() -> Router<AppState> {
+  Router::new()
+    .route("/contracts", get(list_contracts))
+    .route("/contracts/:id", get(get_contract))
+    .route("/contracts/:id/transfers", get(list_transfers))
+    .route("/contracts/:id/holders", get(list_holders))
+}
+
+/// Registering contracts; each one costs the indexer RPC calls on every
+/// pass, so it needs an API key.
+pub fn indexer_write_routes() -> Router<AppState> {
+  Router::new().route("/contracts", post(create_contract))
+}
+
+#[utoipa::path(
+  get,
+  path = "/contracts",
+  tag = "indexer",
+  responses(
+    (status = 200, description = "Indexed contracts", body = Vec<IndexedContract>),
+  )
+)]
+async fn list_contracts(
+  State(state): State<AppState>,
+) -> Result<Json<Vec<IndexedContract>>, AppError> {
+  Ok(Json(indexer::list(&state.db).await?))
+}
+
+/// Registers a contract; the indexer starts scanning it on its next pass.
+#[utoipa::path(
+  post,
+  path = "/contracts",
+  tag = "indexer",
+  request_body = NewIndexedContract,
+  responses(
+    (status = 201, description = "Registered", body = IndexedContract),
+    (status = 400, description = "Unknown chain or bad address", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+    (status = 409, description = "Already registered", body = ErrorResponse),
+  )
+)]
+async fn create_contract(
+  State(state): State<AppState>,
+  Json(new): Json<NewIndexedContract>,
+) -> Result<(StatusCode, Json<IndexedContract>), AppError> {
+  if state.chains.provider(&new.chain).is_none() {
+    return Err(AppError::Input("chain".to_string(), new.chain));
+  }
+
+  let contract = indexer::create(&state.db, &new).await?;
+
+  Ok((StatusCode::CREATED, Json(contract)))
+}
+
+#[utoipa::path(
+  get,
+  path = "/contracts/{id}",
+  tag = "indexer",
+  params(("id" = u64, Path, description = "Indexed contract id")),
+  responses(
+    (status = 200, description = "The contract and its scan position", body = IndexedContract),
+    (status = 404, description = "No such contract", body = ErrorResponse),
+  )
+)]
+async fn get_contract(
+  State(state): State<AppState>,
+  Path(id): Path<u64>,
+) -> Result<Json<IndexedContract>, AppError> {
+  Ok(Json(indexer::find(&state.db, id).await?))
+}
+
+#[utoipa::path(
+  get,
+  path = "/contracts/{id}/transfers",
+  tag = "indexer",
+  params(("id" = u64, Path, description = "Indexed contract id"), EventQuery),
+  responses(
+    (status = 200, description = "Confirmed transfers, newest first", body = TransferPage),
+    (status = 404, description = "No such contract", body = ErrorResponse),
+  )
+)]
+async fn list_transfers(
+  State(state): State<AppState>,
+  Path(id): Path<u64>,
+  Query(query): Query<EventQuery>,
+) -> Result<Json<TransferPage>, AppError> {
+  let contract = indexer::find(&state.db, id).await?;
+
+  Ok(Json(
+    indexer::transfers(&state.db, contract.id, &query).await?,
+  ))
+}
+
+#[utoipa::path(
+  get,
+  path = "/contracts/{id}/holders",
+  tag = "indexer",
+  params(("id" = u64, Path, description = "Indexed contract id"), EventQuery),
+  responses(
+    (status = 200, description = "Holders as of the last confirmed block", body = HolderPage),
+    (status = 404, description = "No such contract", body = ErrorResponse),
+  )
+)]
+async fn list_holders(
+  State(state): State<AppState>,
+  Path(id): Path<u64>,
+  Query(query): Query<EventQuery>,
+) -> Result<Json<HolderPage>, AppError> {
+  let contract = indexer::find(&state.db, id).await?;
+
+  Ok(Json(indexer::holders(&state.db, &contract, &query).await?))
+}
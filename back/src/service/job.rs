@@ -0,0 +1,151 @@
+use axum::{
+  extract::{Path, State},
+  http::{header, StatusCode},
+  response::IntoResponse,
+  routing::{get, post},
+  Json, Router,
+};
+use shared::job::{Job, JobRequest, JobStatus};
+
+use crate::config::AppState;
+use crate::errors::AppError;
+use crate::model::{collection, job};
+use crate::storage::validate_slug;
+
+/// Largest `count` a single generate job accepts.
+const MAX_GENERATE_COUNT: u32 = 100_000;
+
+pub fn job_routes() -> Router<AppState> {
+  Router::new()
+    .route("/jobs/:id", get(get_job))
+    .route("/jobs/:id/result", get(get_job_result))
+}
+
+/// Queuing jobs; they can write hosted metadata, so like uploads they need
+/// an API key.
+pub fn job_write_routes() -> Router<AppState> {
+  Router::new().route("/jobs", post(create_job))
+}
+
+/// Queues a job; poll `GET /jobs/{id}` until it is `done` or `failed`. A
+/// generate job with a `slug` writes hosted metadata into that collection,
+/// which must already exist.
+#[utoipa::path(
+  post,
+  path = "/jobs",
+  tag = "jobs",
+  request_body = JobRequest,
+  responses(
+    (status = 202, description = "Queued", body = Job),
+    (status = 400, description = "Invalid request", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+    (status = 404, description = "No collection with that slug", body = ErrorResponse),
+  )
+)]
+async fn create_job(
+  State(state): State<AppState>,
+  Json(request): Json<JobRequest>,
+) -> Result<(StatusCode, Json<Job>), AppError> {
+  validate(&request)?;
+  match &request {
+    JobRequest::Generate {
+      slug: Some(slug), ..
+    } => {
+      collection::find(&state.db, slug).await?;
+    }
+    JobRequest::Generate { slug: None, .. } => {}
+  }
+
+  let job = job::create(&state.db, &request).await?;
+  state.jobs.wake();
+
+  Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+#[utoipa::path(
+  get,
+  path = "/jobs/{id}",
+  tag = "jobs",
+  params(("id" = u64, Path, description = "Job id")),
+  responses(
+    (status = 200, description = "Status and progress", body = Job),
+    (status = 404, description = "No such job", body = ErrorResponse),
+  )
+)]
+async fn get_job(
+  State(state): State<AppState>,
+  Path(id): Path<u64>,
+) -> Result<Json<Job>, AppError> {
+  Ok(Json(job::find(&state.db, id).await?))
+}
+
+#[utoipa::path(
+  get,
+  path = "/jobs/{id}/result",
+  tag = "jobs",
+  params(("id" = u64, Path, description = "Job id")),
+  responses(
+    (status = 200, description = "Zip archive", content_type = "application/zip", body = Vec<u8>),
+    (status = 404, description = "No such job", body = ErrorResponse),
+    (status = 409, description = "Job not done", body = ErrorResponse),
+  )
+)]
+async fn get_job_result(
+  State(state): State<AppState>,
+  Path(id): Path<u64>,
+) -> Result<impl IntoResponse, AppError> {
+  let job = job::find(&state.db, id).await?;
+  if job.status != JobStatus::Done {
+    return Err(AppError::Conflict(format!(
+      "job {} is {}",
+      id,
+      job.status.as_str()
+    )));
+  }
+
+  let bytes = state
+    .storage
+    .job_result(id)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("job {} result", id)))?;
+
+  Ok((
+    [
+      (header::CONTENT_TYPE, "application/zip".to_string()),
+      (
+        header::CONTENT_DISPOSITION,
+        format!("attachment; filename=\"job-{}.zip\"", id),
+      ),
+    ],
+    bytes,
+  ))
+}
+
+fn validate(request: &JobRequest) -> Result<(), AppError> {
+  match request {
+    JobRequest::Generate {
+      project_name,
+      count,
+      slug,
+      ..
+    } => {
+      if project_name.is_empty()
+        || project_name.len() > 64
+        || project_name.contains(['/', '\\'])
+      {
+        return Err(AppError::Input(
+          "project_name".to_string(),
+          project_name.clone(),
+        ));
+      }
+      if *count == 0 || *count > MAX_GENERATE_COUNT {
+        return Err(AppError::Input("count".to_string(), count.to_string()));
+      }
+      if let Some(slug) = slug {
+        validate_slug(slug)?;
+      }
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,239 @@
+use axum::{
+  extract::{Path, State},
+  http::StatusCode,
+  routing::{get, put},
+  Json, Router,
+};
+use chrono::Utc;
+use ethers::types::{Address, U256};
+use serde_json::Value;
+use shared::reveal::RevealSettings;
+
+use crate::config::AppState;
+use crate::errors::AppError;
+
+pub fn metadata_routes() -> Router<AppState> {
+  Router::new()
+    .route("/metadata/:slug/:token_id", get(get_token_metadata))
+    .route("/collections/:slug/placeholder", get(get_placeholder))
+    .route("/collections/:slug/reveal", get(get_reveal_settings))
+}
+
+/// Reveal changes; like uploads, these need an API key.
+pub fn settings_routes() -> Router<AppState> {
+  Router::new().route("/collections/:slug/reveal", put(put_reveal_settings))
+}
+
+/// Metadata uploads, mounted separately so they can take a larger body limit.
+/// They need an API key.
+pub fn upload_routes() -> Router<AppState> {
+  Router::new()
+    .route(
+      "/collections/:slug/tokens/:token_id",
+      put(put_token_metadata),
+    )
+    .route("/collections/:slug/placeholder", put(put_placeholder))
+}
+
+/// Public `tokenURI` target: the placeholder until the collection is revealed
+/// (and, if required, the token is minted), the real metadata afterwards.
+#[utoipa::path(
+  get,
+  path = "/metadata/{slug}/{token_id}",
+  tag = "metadata",
+  params(
+    ("slug" = String, Path, description = "Collection slug"),
+    ("token_id" = String, Path, description = "Decimal token id"),
+  ),
+  responses(
+    (status = 200, description = "Token or placeholder metadata", body = Object),
+    (status = 400, description = "Invalid slug or token id", body = ErrorResponse),
+    (status = 404, description = "Nothing uploaded for this token", body = ErrorResponse),
+    (status = 502, description = "ownerOf check failed", body = ErrorResponse),
+  )
+)]
+async fn get_token_metadata(
+  State(state): State<AppState>,
+  Path((slug, token_id)): Path<(String, String)>,
+) -> Result<Json<Value>, AppError> {
+  let token_id = parse_token_id(&token_id)?;
+  let settings = state.storage.reveal_settings(&slug).await?;
+
+  let metadata = if settings.is_revealed_at(Utc::now())
+    && is_minted(&state, &settings, token_id).await?
+  {
+    state
+      .storage
+      .token_metadata(&slug, token_id)
+      .await?
+      .ok_or_else(|| AppError::NotFound(format!("{}/{}", slug, token_id)))?
+  } else {
+    state
+      .storage
+      .placeholder(&slug)
+      .await?
+      .ok_or_else(|| AppError::NotFound(format!("{}/placeholder", slug)))?
+  };
+
+  // Counted only once something was served, so unknown slugs can't grow
+  // the label set.
+  state.metrics.metadata_read(&slug);
+
+  Ok(Json(metadata))
+}
+
+#[utoipa::path(
+  put,
+  path = "/collections/{slug}/tokens/{token_id}",
+  tag = "metadata",
+  params(
+    ("slug" = String, Path, description = "Collection slug"),
+    ("token_id" = String, Path, description = "Decimal token id"),
+  ),
+  request_body = Object,
+  responses(
+    (status = 204, description = "Stored"),
+    (status = 400, description = "Invalid slug or token id", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+  )
+)]
+async fn put_token_metadata(
+  State(state): State<AppState>,
+  Path((slug, token_id)): Path<(String, String)>,
+  Json(metadata): Json<Value>,
+) -> Result<StatusCode, AppError> {
+  let token_id = parse_token_id(&token_id)?;
+  state
+    .storage
+    .put_token_metadata(&slug, token_id, &metadata)
+    .await?;
+
+  Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+  get,
+  path = "/collections/{slug}/placeholder",
+  tag = "metadata",
+  params(("slug" = String, Path, description = "Collection slug")),
+  responses(
+    (status = 200, description = "Placeholder metadata", body = Object),
+    (status = 404, description = "No placeholder uploaded", body = ErrorResponse),
+  )
+)]
+async fn get_placeholder(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+) -> Result<Json<Value>, AppError> {
+  state
+    .storage
+    .placeholder(&slug)
+    .await?
+    .map(Json)
+    .ok_or_else(|| AppError::NotFound(format!("{}/placeholder", slug)))
+}
+
+#[utoipa::path(
+  put,
+  path = "/collections/{slug}/placeholder",
+  tag = "metadata",
+  params(("slug" = String, Path, description = "Collection slug")),
+  request_body = Object,
+  responses(
+    (status = 204, description = "Stored"),
+    (status = 400, description = "Invalid slug", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+  )
+)]
+async fn put_placeholder(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+  Json(metadata): Json<Value>,
+) -> Result<StatusCode, AppError> {
+  state.storage.put_placeholder(&slug, &metadata).await?;
+
+  Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+  get,
+  path = "/collections/{slug}/reveal",
+  tag = "metadata",
+  params(("slug" = String, Path, description = "Collection slug")),
+  responses(
+    (status = 200, description = "Reveal settings", body = RevealSettings),
+    (status = 400, description = "Invalid slug", body = ErrorResponse),
+  )
+)]
+async fn get_reveal_settings(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+) -> Result<Json<RevealSettings>, AppError> {
+  Ok(Json(state.storage.reveal_settings(&slug).await?))
+}
+
+#[utoipa::path(
+  put,
+  path = "/collections/{slug}/reveal",
+  tag = "metadata",
+  params(("slug" = String, Path, description = "Collection slug")),
+  request_body = RevealSettings,
+  responses(
+    (status = 200, description = "Stored reveal settings", body = RevealSettings),
+    (status = 400, description = "Invalid slug, chain or contract address", body = ErrorResponse),
+    (status = 401, description = "Missing or unknown API key", body = ErrorResponse),
+  )
+)]
+async fn put_reveal_settings(
+  State(state): State<AppState>,
+  Path(slug): Path<String>,
+  Json(settings): Json<RevealSettings>,
+) -> Result<Json<RevealSettings>, AppError> {
+  if settings.require_minted {
+    let chain = settings.chain.as_deref().unwrap_or_default();
+    if state.chains.provider(chain).is_none() {
+      return Err(AppError::Input("chain".to_string(), chain.to_string()));
+    }
+    parse_contract_address(&settings)?;
+  }
+
+  state.storage.put_reveal_settings(&slug, &settings).await?;
+
+  Ok(Json(settings))
+}
+
+async fn is_minted(
+  state: &AppState,
+  settings: &RevealSettings,
+  token_id: U256,
+) -> Result<bool, AppError> {
+  if !settings.require_minted {
+    return Ok(true);
+  }
+
+  let chain = settings.chain.as_deref().unwrap_or_default();
+  let contract_address = parse_contract_address(settings)?;
+
+  state
+    .chains
+    .is_minted(chain, contract_address, token_id)
+    .await
+}
+
+fn parse_token_id(token_id: &str) -> Result<U256, AppError> {
+  U256::from_dec_str(token_id)
+    .map_err(|_| AppError::Input("token_id".to_string(), token_id.to_string()))
+}
+
+fn parse_contract_address(
+  settings: &RevealSettings,
+) -> Result<Address, AppError> {
+  let contract_address =
+    settings.contract_address.as_deref().unwrap_or_default();
+  contract_address.parse::<Address>().map_err(|_| {
+    AppError::Input(
+      "contract_address".to_string(),
+      contract_address.to_string(),
+    )
+  })
+}
@@ -1,15 +1,6 @@
-use axum::{
-  http::{HeaderMap, Request},
-  middleware::{self, Next},
-  response::Response,
-  routing::{get, post},
-  Router,
-};
-
-use crate::config::AppState;
-
-// pub fn user_routes() -> Router<AppState> {
-//     Router::new()
-//       // Multipart
-//       .route("/collection", post(user::put_my_collection))
-// }
+pub mod collection;
+pub mod docs;
+pub mod health;
+pub mod indexer;
+pub mod job;
+pub mod metadata;
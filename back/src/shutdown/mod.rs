@@ -0,0 +1,97 @@
+use std::future::Future;
+use std::time::Duration;
+
+use axum::{
+  extract::Request,
+  http::StatusCode,
+  middleware::Next,
+  response::IntoResponse,
+  Router,
+};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+/// Long-running tasks that must stop before the process exits.
+///
+/// Workers receive a [`CancellationToken`] and should return soon after it
+/// fires; [`Background::shutdown`] cancels it and waits for them.
+#[derive(Clone, Default)]
+pub struct Background {
+  token: CancellationToken,
+  tracker: TaskTracker,
+}
+
+impl Background {
+  pub fn spawn<F, Fut>(&self, name: &'static str, worker: F)
+  where
+    F: FnOnce(CancellationToken) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+  {
+    let task = worker(self.token.clone());
+    self.tracker.spawn(async move {
+      task.await;
+      tracing::info!(worker = name, "Background worker stopped");
+    });
+  }
+
+  pub async fn shutdown(&self, timeout: Duration) {
+    tracing::info!(workers = self.tracker.len(), "Stopping background workers");
+    self.token.cancel();
+    self.tracker.close();
+
+    if tokio::time::timeout(timeout, self.tracker.wait())
+      .await
+      .is_err()
+    {
+      tracing::warn!(
+        workers = self.tracker.len(),
+        "Background workers did not stop within {:?}",
+        timeout
+      );
+    }
+  }
+}
+
+/// Races every request against `token`. Handlers still running when it
+/// fires are dropped and answered with `503`, which lets their connections
+/// finish closing instead of outliving the server task.
+pub fn cancel_requests_on(router: Router, token: CancellationToken) -> Router {
+  router.layer(axum::middleware::from_fn(
+    move |request: Request, next: Next| {
+      let token = token.clone();
+      async move {
+        tokio::select! {
+          response = next.run(request) => response,
+          _ = token.cancelled() => {
+            tracing::warn!("Request cancelled by shutdown");
+            StatusCode::SERVICE_UNAVAILABLE.into_response()
+          }
+        }
+      }
+    },
+  ))
+}
+
+/// Resolves on SIGINT (Ctrl-C) or, on unix, SIGTERM, returning its name.
+pub async fn signal() -> &'static str {
+  let ctrl_c = async {
+    tokio::signal::ctrl_c()
+      .await
+      .expect("Expect shutdown signal handler");
+  };
+
+  #[cfg(unix)]
+  let terminate = async {
+    tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+      .expect("Expect SIGTERM handler")
+      .recv()
+      .await;
+  };
+
+  #[cfg(not(unix))]
+  let terminate = std::future::pending::<()>();
+
+  tokio::select! {
+    _ = ctrl_c => "SIGINT",
+    _ = terminate => "SIGTERM",
+  }
+}
@@ -0,0 +1,176 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ethers::types::U256;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use shared::reveal::RevealSettings;
+
+use crate::errors::AppError;
+
+/// Hosted collection files on local disk.
+///
+/// ```text
+/// {root}/collections/{slug}/reveal.json
+/// {root}/collections/{slug}/placeholder.json
+/// {root}/collections/{slug}/tokens/{token_id}.json
+/// {root}/jobs/{id}.zip
+/// ```
+#[derive(Clone)]
+pub struct Storage {
+  root: Arc<PathBuf>,
+}
+
+impl Storage {
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self {
+      root: Arc::new(root.into()),
+    }
+  }
+
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  pub async fn reveal_settings(
+    &self,
+    slug: &str,
+  ) -> Result<RevealSettings, AppError> {
+    let path = self.collection_dir(slug)?.join("reveal.json");
+    Ok(read_json(&path).await?.unwrap_or_default())
+  }
+
+  pub async fn put_reveal_settings(
+    &self,
+    slug: &str,
+    settings: &RevealSettings,
+  ) -> Result<(), AppError> {
+    let path = self.collection_dir(slug)?.join("reveal.json");
+    write_json(&path, settings).await
+  }
+
+  pub async fn placeholder(
+    &self,
+    slug: &str,
+  ) -> Result<Option<Value>, AppError> {
+    let path = self.collection_dir(slug)?.join("placeholder.json");
+    read_json(&path).await
+  }
+
+  pub async fn put_placeholder(
+    &self,
+    slug: &str,
+    metadata: &Value,
+  ) -> Result<(), AppError> {
+    let path = self.collection_dir(slug)?.join("placeholder.json");
+    write_json(&path, metadata).await
+  }
+
+  pub async fn token_metadata(
+    &self,
+    slug: &str,
+    token_id: U256,
+  ) -> Result<Option<Value>, AppError> {
+    read_json(&self.token_path(slug, token_id)?).await
+  }
+
+  pub async fn put_token_metadata(
+    &self,
+    slug: &str,
+    token_id: U256,
+    metadata: &Value,
+  ) -> Result<(), AppError> {
+    write_json(&self.token_path(slug, token_id)?, metadata).await
+  }
+
+  pub async fn job_result(&self, id: u64) -> Result<Option<Vec<u8>>, AppError> {
+    match tokio::fs::read(self.job_result_path(id)).await {
+      Ok(bytes) => Ok(Some(bytes)),
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  pub async fn put_job_result(
+    &self,
+    id: u64,
+    bytes: &[u8],
+  ) -> Result<(), AppError> {
+    write_atomic(&self.job_result_path(id), bytes).await
+  }
+
+  fn job_result_path(&self, id: u64) -> PathBuf {
+    self.root.join("jobs").join(format!("{}.zip", id))
+  }
+
+  fn token_path(
+    &self,
+    slug: &str,
+    token_id: U256,
+  ) -> Result<PathBuf, AppError> {
+    Ok(
+      self
+        .collection_dir(slug)?
+        .join("tokens")
+        .join(format!("{}.json", token_id)),
+    )
+  }
+
+  fn collection_dir(&self, slug: &str) -> Result<PathBuf, AppError> {
+    validate_slug(slug)?;
+    Ok(self.root.join("collections").join(slug))
+  }
+}
+
+/// Slugs become directory names, so only `[A-Za-z0-9_-]` is accepted.
+pub fn validate_slug(slug: &str) -> Result<(), AppError> {
+  let valid = !slug.is_empty()
+    && slug.len() <= 64
+    && slug
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+  if valid {
+    Ok(())
+  } else {
+    Err(AppError::Input("slug".to_string(), slug.to_string()))
+  }
+}
+
+async fn read_json<T: DeserializeOwned>(
+  path: &Path,
+) -> Result<Option<T>, AppError> {
+  let bytes = match tokio::fs::read(path).await {
+    Ok(bytes) => bytes,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(e) => return Err(e.into()),
+  };
+
+  serde_json::from_slice(&bytes)
+    .map(Some)
+    .map_err(|e| AppError::Storage(e.into()))
+}
+
+async fn write_json<T: Serialize>(
+  path: &Path,
+  value: &T,
+) -> Result<(), AppError> {
+  let bytes = serde_json::to_vec_pretty(value)
+    .map_err(|e| AppError::Storage(e.into()))?;
+
+  write_atomic(path, &bytes).await
+}
+
+/// Writes to a sibling temp file first so readers never see a partial file.
+async fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), AppError> {
+  if let Some(parent) = path.parent() {
+    tokio::fs::create_dir_all(parent).await?;
+  }
+
+  let mut tmp = path.as_os_str().to_owned();
+  tmp.push(".tmp");
+  tokio::fs::write(&tmp, bytes).await?;
+  tokio::fs::rename(&tmp, path).await?;
+
+  Ok(())
+}
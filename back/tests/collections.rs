@@ -0,0 +1,219 @@
+mod common;
+
+use axum::{
+  body::Body,
+  http::{header, Request, StatusCode},
+};
+use tower::ServiceExt;
+
+// The default test pool points at a closed port, so the tests that run by
+// default only cover what is rejected before the database is touched. The
+// rest need MySQL and are ignored unless run with `--ignored`.
+
+async fn post(body: &str) -> (StatusCode, serde_json::Value) {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  let request = Request::post("/collections")
+    .header(header::CONTENT_TYPE, "application/json")
+    .header("x-api-key", common::API_KEY)
+    .body(Body::from(body.to_string()))
+    .unwrap();
+  let response = app.oneshot(request).await.unwrap();
+  let status = response.status();
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+
+  (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn rejects_bad_slugs() {
+  let (status, body) = post(r#"{"slug":"../etc","name":"Drop"}"#).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert_eq!(body["error"]["input"], "slug");
+}
+
+#[tokio::test]
+async fn rejects_bad_fields() {
+  let (status, body) = post(r#"{"slug":"drop","name":" "}"#).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert_eq!(body["error"]["input"], "name");
+
+  let (status, body) =
+    post(r#"{"slug":"drop","name":"Drop","contract_address":"0x12"}"#).await;
+  assert_eq!(status, StatusCode::BAD_REQUEST);
+  assert_eq!(body["error"]["input"], "contract_address");
+}
+
+#[tokio::test]
+async fn writes_need_an_api_key() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+  let body = r#"{"slug":"drop","name":"Drop"}"#;
+
+  for (method, uri) in [
+    ("POST", "/collections"),
+    ("PUT", "/collections/drop"),
+    ("DELETE", "/collections/drop"),
+  ] {
+    for key in [None, Some("wrong")] {
+      let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json");
+      if let Some(key) = key {
+        request = request.header("x-api-key", key);
+      }
+      let request = request.body(Body::from(body)).unwrap();
+      let response = app.clone().oneshot(request).await.unwrap();
+      assert_eq!(
+        response.status(),
+        StatusCode::UNAUTHORIZED,
+        "{} {} {:?}",
+        method,
+        uri,
+        key
+      );
+    }
+  }
+
+  // Reads stay public; without a database they fail past the key check.
+  for uri in ["/collections", "/collections/drop"] {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_ne!(response.status(), StatusCode::UNAUTHORIZED, "{}", uri);
+  }
+}
+
+#[test]
+fn search_terms_match_literally() {
+  use back::model::collection::contains_pattern;
+
+  assert_eq!(contains_pattern("drop"), "%drop%");
+  assert_eq!(contains_pattern("100%"), "%100\\%%");
+  assert_eq!(contains_pattern("my_drop"), "%my\\_drop%");
+  assert_eq!(contains_pattern("a\\b"), "%a\\\\b%");
+}
+
+async fn call(
+  app: &axum::Router,
+  method: &str,
+  uri: &str,
+  body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+  let request = Request::builder()
+    .method(method)
+    .uri(uri)
+    .header(header::CONTENT_TYPE, "application/json")
+    .header("x-api-key", common::API_KEY)
+    .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+    .unwrap();
+  let response = app.clone().oneshot(request).await.unwrap();
+  let status = response.status();
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+
+  (
+    status,
+    serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+  )
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn creates_updates_and_deletes() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::mysql_state(dir.path()).await);
+  let slug = common::unique_slug("crud");
+  let uri = format!("/collections/{}", slug);
+
+  let (status, created) = call(
+    &app,
+    "POST",
+    "/collections",
+    Some(serde_json::json!({"slug": slug, "name": "Drop", "chain": "sepolia"})),
+  )
+  .await;
+  assert_eq!(status, StatusCode::CREATED);
+  assert_eq!(created["slug"], slug.as_str());
+  assert_eq!(created["name"], "Drop");
+
+  let (status, body) = call(
+    &app,
+    "POST",
+    "/collections",
+    Some(serde_json::json!({"slug": slug, "name": "Again"})),
+  )
+  .await;
+  assert_eq!(status, StatusCode::CONFLICT);
+  assert_eq!(body["error"]["msg"], "already exists");
+
+  let (status, updated) = call(
+    &app,
+    "PUT",
+    &uri,
+    Some(serde_json::json!({"name": "Renamed", "description": "d"})),
+  )
+  .await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(updated["name"], "Renamed");
+  assert_eq!(updated["chain"], serde_json::Value::Null);
+  assert_eq!(updated["id"], created["id"]);
+
+  let (status, fetched) = call(&app, "GET", &uri, None).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(fetched, updated);
+
+  let (status, _) = call(&app, "DELETE", &uri, None).await;
+  assert_eq!(status, StatusCode::NO_CONTENT);
+  let (status, _) = call(&app, "GET", &uri, None).await;
+  assert_eq!(status, StatusCode::NOT_FOUND);
+  let (status, _) = call(&app, "DELETE", &uri, None).await;
+  assert_eq!(status, StatusCode::NOT_FOUND);
+  let (status, _) =
+    call(&app, "PUT", &uri, Some(serde_json::json!({"name": "Gone"}))).await;
+  assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn search_does_not_treat_wildcards_as_patterns() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::mysql_state(dir.path()).await);
+  let prefix = common::unique_slug("like");
+
+  for (suffix, name) in [("a", "100% pure"), ("b", "100 pure")] {
+    let (status, _) = call(
+      &app,
+      "POST",
+      "/collections",
+      Some(serde_json::json!({"slug": format!("{}-{}", prefix, suffix), "name": name})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+  }
+
+  let (_, page) =
+    call(&app, "GET", "/collections?q=100%25&per_page=100", None).await;
+  let slugs = page["items"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|item| item["slug"].as_str().unwrap().to_string())
+    .filter(|slug| slug.starts_with(&prefix))
+    .collect::<Vec<_>>();
+  assert_eq!(slugs, vec![format!("{}-a", prefix)]);
+
+  for suffix in ["a", "b"] {
+    call(
+      &app,
+      "DELETE",
+      &format!("/collections/{}-{}", prefix, suffix),
+      None,
+    )
+    .await;
+  }
+}
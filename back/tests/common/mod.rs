@@ -0,0 +1,69 @@
+// Each test binary uses a different subset of these helpers.
+#![allow(dead_code)]
+
+use back::chain::Chains;
+use back::config::{AppState, Config, MovieState};
+use back::jobs::Jobs;
+use back::middleware::metrics::Metrics;
+use back::shutdown::Background;
+use back::storage::Storage;
+use sqlx::mysql::{MySqlConnectOptions, MySqlPool, MySqlPoolOptions};
+use std::sync::Arc;
+
+/// Key [`app_state`] accepts for the routes that need one.
+pub const API_KEY: &str = "admin";
+
+/// State backed by a temp storage root and a lazy pool pointing at a port
+/// nothing listens on, so tests never need MySQL or an RPC node.
+pub fn app_state(storage_root: &std::path::Path) -> AppState {
+  let config = Config {
+    api_keys: vec![API_KEY.to_string()],
+    ..Config::default()
+  };
+  app_state_with(storage_root, config)
+}
+
+pub fn app_state_with(
+  storage_root: &std::path::Path,
+  config: Config,
+) -> AppState {
+  let db = MySqlPoolOptions::new()
+    .acquire_timeout(std::time::Duration::from_millis(200))
+    .connect_lazy_with(MySqlConnectOptions::new().host("127.0.0.1").port(1));
+
+  AppState {
+    movie_state: MovieState {},
+    rate_limiter: config.rate_limiter(),
+    config: Arc::new(config),
+    storage: Storage::new(storage_root),
+    chains: Chains::default(),
+    db,
+    metrics: Metrics::new(),
+    background: Background::default(),
+    jobs: Jobs::default(),
+  }
+}
+
+/// State on the MySQL at `TEST_DATABASE_URL` with `back/migrations`
+/// applied. Tests using it are `#[ignore]`d; run them with
+/// `TEST_DATABASE_URL=mysql://.. cargo test -p back -- --ignored`.
+pub async fn mysql_state(storage_root: &std::path::Path) -> AppState {
+  let url = std::env::var("TEST_DATABASE_URL")
+    .expect("TEST_DATABASE_URL must point at a scratch MySQL database");
+  let db = MySqlPool::connect(&url).await.unwrap();
+  sqlx::migrate!("./migrations").run(&db).await.unwrap();
+
+  AppState {
+    db,
+    ..app_state(storage_root)
+  }
+}
+
+/// Slug no earlier run of the same test can have left behind.
+pub fn unique_slug(prefix: &str) -> String {
+  let nanos = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .unwrap()
+    .as_nanos();
+  format!("{}-{}", prefix, nanos)
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use axum::{
+  body::Body,
+  http::{Request, StatusCode},
+  routing::post,
+  Json, Router,
+};
+use back::chain::Chains;
+use back::config::Config;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, Value) {
+  let response = app
+    .clone()
+    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+    .await
+    .unwrap();
+  let status = response.status();
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+
+  (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn block_number(Json(request): Json<Value>) -> Json<Value> {
+  Json(json!({"jsonrpc": "2.0", "id": request["id"], "result": "0x10"}))
+}
+
+/// `up` answers `eth_blockNumber`; `down` points at a closed port.
+async fn chains() -> Chains {
+  let app = Router::new().route("/", post(block_number));
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let url = format!("http://{}", listener.local_addr().unwrap());
+  tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+  Chains::parse(&format!("up={},down=http://127.0.0.1:1", url)).unwrap()
+}
+
+#[tokio::test]
+async fn live_is_ok_without_checks() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  for uri in ["/health", "/health/live"] {
+    let (status, body) = get(&app, uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({"status": "ok"}));
+  }
+}
+
+#[tokio::test]
+async fn ready_reports_each_dependency() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  let (status, body) = get(&app, "/health/ready").await;
+
+  // Nothing listens where the test pool points.
+  assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(body["status"], "error");
+  assert_eq!(body["checks"]["db"]["status"], "error");
+  assert!(body["checks"]["db"]["error"].is_string());
+  assert_eq!(body["checks"]["storage"]["status"], "ok");
+  assert!(body["checks"]["storage"].get("error").is_none());
+  assert!(body["checks"]["storage"]["latency_ms"].is_u64());
+  assert!(!dir.path().join(".ready-probe").exists());
+}
+
+#[tokio::test]
+async fn ready_fails_when_storage_is_not_writable() {
+  let dir = tempfile::tempdir().unwrap();
+  let root = dir.path().join("not-a-dir");
+  std::fs::write(&root, b"").unwrap();
+  let app = back::app(common::app_state(&root));
+
+  let (status, body) = get(&app, "/health/ready").await;
+
+  assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+  assert_eq!(body["checks"]["storage"]["status"], "error");
+}
+
+#[tokio::test]
+async fn ready_checks_rpc_only_when_enabled() {
+  let dir = tempfile::tempdir().unwrap();
+  let mut state = common::app_state(dir.path());
+  state.chains = chains().await;
+  let (_, body) = get(&back::app(state), "/health/ready").await;
+  assert!(body["checks"].get("rpc:up").is_none());
+
+  let config = Config {
+    ready_check_rpc: true,
+    ..Config::default()
+  };
+  let mut state = common::app_state_with(dir.path(), config);
+  state.chains = chains().await;
+  let (_, body) = get(&back::app(state), "/health/ready").await;
+
+  assert_eq!(body["checks"]["rpc:up"]["status"], "ok");
+  assert_eq!(body["checks"]["rpc:down"]["status"], "error");
+}
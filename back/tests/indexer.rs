@@ -0,0 +1,646 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+  body::Body,
+  extract::State,
+  http::{header, Request, StatusCode},
+  routing::post,
+  Json, Router,
+};
+use back::indexer::{step, Batch, Cursor, Step, TokenApproval};
+use back::model::indexer;
+use ethers::{
+  providers::{Http, Provider},
+  types::{Address, H256, U256, U512},
+  utils::keccak256,
+};
+use serde_json::{json, Value};
+use shared::collection::Page;
+use shared::indexer::{
+  EventQuery, Holder, NewIndexedContract, TokenStandard, TokenTransfer,
+};
+use tower::ServiceExt;
+
+const CONFIRMATIONS: u64 = 5;
+
+/// Canned chain answering the handful of JSON-RPC methods the indexer uses.
+#[derive(Default)]
+struct MockChain {
+  head: u64,
+  hashes: HashMap<u64, H256>,
+  logs: Vec<(u64, Value)>,
+}
+
+impl MockChain {
+  fn new(head: u64) -> Self {
+    let hashes = (0..=head)
+      .map(|n| (n, H256::from_low_u64_be(0x1000 + n)))
+      .collect();
+    Self {
+      head,
+      hashes,
+      logs: Vec::new(),
+    }
+  }
+
+  fn log(&mut self, block: u64, log_index: u64, topics: Vec<H256>, data: U256) {
+    let mut bytes = [0u8; 32];
+    data.to_big_endian(&mut bytes);
+    self.logs.push((
+      block,
+      json!({
+        "address": contract(),
+        "topics": topics,
+        "data": format!("0x{}", ethers::utils::hex::encode(bytes)),
+        "blockNumber": format!("{:#x}", block),
+        "blockHash": self.hashes[&block],
+        "transactionHash": H256::from_low_u64_be(block * 100 + log_index),
+        "transactionIndex": "0x0",
+        "logIndex": format!("{:#x}", log_index),
+        "removed": false,
+      }),
+    ));
+  }
+}
+
+async fn rpc(
+  State(chain): State<Arc<Mutex<MockChain>>>,
+  Json(request): Json<Value>,
+) -> Json<Value> {
+  let chain = chain.lock().unwrap();
+  let params = &request["params"];
+  let block_param = |value: &Value| {
+    u64::from_str_radix(value.as_str().unwrap().trim_start_matches("0x"), 16)
+      .unwrap()
+  };
+
+  let result = match request["method"].as_str().unwrap() {
+    "eth_blockNumber" => json!(format!("{:#x}", chain.head)),
+    "eth_getBlockByNumber" => {
+      let number = block_param(&params[0]);
+      match chain.hashes.get(&number) {
+        Some(hash) => json!({
+          "number": format!("{:#x}", number),
+          "hash": hash,
+          "transactions": [],
+        }),
+        None => Value::Null,
+      }
+    }
+    "eth_getLogs" => {
+      let from = block_param(&params[0]["fromBlock"]);
+      let to = block_param(&params[0]["toBlock"]);
+      json!(chain
+        .logs
+        .iter()
+        .filter(|(block, _)| (from..=to).contains(block))
+        .map(|(_, log)| log.clone())
+        .collect::<Vec<_>>())
+    }
+    method => panic!("unexpected {}", method),
+  };
+
+  Json(json!({"jsonrpc": "2.0", "id": request["id"], "result": result}))
+}
+
+async fn serve(chain: MockChain) -> Provider<Http> {
+  let app = Router::new()
+    .route("/", post(rpc))
+    .with_state(Arc::new(Mutex::new(chain)));
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let url = format!("http://{}", listener.local_addr().unwrap());
+  tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+  Provider::<Http>::try_from(url).unwrap()
+}
+
+fn contract() -> Address {
+  Address::from_low_u64_be(0xc0ffee)
+}
+
+fn holder(n: u64) -> Address {
+  Address::from_low_u64_be(n)
+}
+
+fn topic(signature: &str) -> H256 {
+  keccak256(signature).into()
+}
+
+fn word(value: impl Into<U256>) -> H256 {
+  let mut bytes = [0u8; 32];
+  value.into().to_big_endian(&mut bytes);
+  H256(bytes)
+}
+
+fn address_word(address: Address) -> H256 {
+  address.into()
+}
+
+fn cursor(next_block: u64, last_block_hash: Option<H256>) -> Cursor {
+  Cursor {
+    start_block: 0,
+    next_block,
+    last_block_hash,
+    checkpoints: Vec::new(),
+  }
+}
+
+fn erc721_chain() -> MockChain {
+  let mut chain = MockChain::new(20);
+  let transfer = topic("Transfer(address,address,uint256)");
+  let approval = topic("Approval(address,address,uint256)");
+
+  // Mint of token 7 to holder 1, then an approval, then a transfer that is
+  // not yet confirmed.
+  chain.log(
+    10,
+    0,
+    vec![
+      transfer,
+      address_word(Address::zero()),
+      address_word(holder(1)),
+      word(7),
+    ],
+    U256::zero(),
+  );
+  chain.log(
+    12,
+    3,
+    vec![
+      approval,
+      address_word(holder(1)),
+      address_word(holder(2)),
+      word(7),
+    ],
+    U256::zero(),
+  );
+  chain.log(
+    18,
+    0,
+    vec![
+      transfer,
+      address_word(holder(1)),
+      address_word(holder(2)),
+      word(7),
+    ],
+    U256::zero(),
+  );
+  chain
+}
+
+#[tokio::test]
+async fn scans_only_confirmed_blocks() {
+  let chain = erc721_chain();
+  let expected_hash = chain.hashes[&15];
+  let provider = serve(chain).await;
+
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc721,
+    &cursor(0, None),
+    CONFIRMATIONS,
+    100,
+  )
+  .await
+  .unwrap();
+
+  let Step::Batch(batch) = step else {
+    panic!("expected a batch, got {:?}", step);
+  };
+  assert_eq!((batch.from, batch.to), (0, 15));
+  assert_eq!(batch.block_hash, expected_hash);
+
+  assert_eq!(batch.transfers.len(), 1);
+  let transfer = &batch.transfers[0];
+  assert_eq!(transfer.block_number, 10);
+  assert_eq!(transfer.from_address, format!("{:#x}", Address::zero()));
+  assert_eq!(transfer.to_address, format!("{:#x}", holder(1)));
+  assert_eq!(transfer.token_id.as_deref(), Some("7"));
+  assert_eq!(transfer.value, None);
+
+  assert_eq!(batch.approvals.len(), 1);
+  assert_eq!(
+    batch.approvals[0].spender_address,
+    format!("{:#x}", holder(2))
+  );
+  assert_eq!(batch.approvals[0].log_index, 3);
+}
+
+#[tokio::test]
+async fn caps_the_block_range() {
+  let provider = serve(erc721_chain()).await;
+
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc721,
+    &cursor(8, None),
+    CONFIRMATIONS,
+    4,
+  )
+  .await
+  .unwrap();
+
+  let Step::Batch(batch) = step else {
+    panic!("expected a batch, got {:?}", step);
+  };
+  assert_eq!((batch.from, batch.to), (8, 11));
+  assert_eq!(batch.transfers.len(), 1);
+  assert!(batch.approvals.is_empty());
+}
+
+#[tokio::test]
+async fn idles_until_blocks_are_confirmed() {
+  let chain = erc721_chain();
+  let last = chain.hashes[&15];
+  let provider = serve(chain).await;
+
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc721,
+    &cursor(16, Some(last)),
+    CONFIRMATIONS,
+    100,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(step, Step::Idle);
+}
+
+#[tokio::test]
+async fn rewinds_to_the_newest_canonical_checkpoint() {
+  let chain = erc721_chain();
+  let hashes = chain.hashes.clone();
+  let provider = serve(chain).await;
+
+  let stale = H256::from_low_u64_be(0xdead);
+  let checkpoints = vec![(15, stale), (10, hashes[&10]), (5, hashes[&5])];
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc721,
+    &Cursor {
+      checkpoints,
+      ..cursor(16, Some(stale))
+    },
+    CONFIRMATIONS,
+    100,
+  )
+  .await
+  .unwrap();
+
+  assert_eq!(
+    step,
+    Step::Reorg(Cursor {
+      checkpoints: vec![(10, hashes[&10]), (5, hashes[&5])],
+      ..cursor(11, Some(hashes[&10]))
+    })
+  );
+}
+
+#[tokio::test]
+async fn walks_back_past_every_reorged_checkpoint() {
+  let chain = erc721_chain();
+  let hashes = chain.hashes.clone();
+  let provider = serve(chain).await;
+
+  // Deeper than `CONFIRMATIONS`: blocks 9 and 12 were replaced as well.
+  let checkpoints = vec![
+    (15, H256::from_low_u64_be(0xdead)),
+    (12, H256::from_low_u64_be(0xbeef)),
+    (9, H256::from_low_u64_be(0xcafe)),
+    (4, hashes[&4]),
+  ];
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc721,
+    &Cursor {
+      checkpoints,
+      ..cursor(16, Some(H256::from_low_u64_be(0xdead)))
+    },
+    CONFIRMATIONS,
+    100,
+  )
+  .await
+  .unwrap();
+
+  let Step::Reorg(rewound) = step else {
+    panic!("expected a reorg, got {:?}", step);
+  };
+  assert_eq!(rewound.next_block, 5);
+  assert_eq!(rewound.last_block_hash, Some(hashes[&4]));
+}
+
+#[tokio::test]
+async fn rescans_from_the_start_block_without_a_canonical_checkpoint() {
+  let provider = serve(erc721_chain()).await;
+
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc721,
+    &Cursor {
+      start_block: 3,
+      next_block: 16,
+      last_block_hash: Some(H256::from_low_u64_be(0xdead)),
+      // Older than `start_block`, so never a rewind target.
+      checkpoints: vec![(2, H256::from_low_u64_be(0x1002))],
+    },
+    CONFIRMATIONS,
+    100,
+  )
+  .await
+  .unwrap();
+
+  let Step::Reorg(rewound) = step else {
+    panic!("expected a reorg, got {:?}", step);
+  };
+  assert_eq!(
+    rewound,
+    Cursor {
+      start_block: 3,
+      ..cursor(3, None)
+    }
+  );
+}
+
+#[tokio::test]
+async fn decodes_erc20_amounts() {
+  let mut chain = MockChain::new(20);
+  chain.log(
+    3,
+    1,
+    vec![
+      topic("Transfer(address,address,uint256)"),
+      address_word(holder(1)),
+      address_word(holder(2)),
+    ],
+    U256::exp10(18) * 5,
+  );
+  let provider = serve(chain).await;
+
+  let step = step(
+    &provider,
+    contract(),
+    TokenStandard::Erc20,
+    &cursor(0, None),
+    CONFIRMATIONS,
+    100,
+  )
+  .await
+  .unwrap();
+
+  let Step::Batch(batch) = step else {
+    panic!("expected a batch, got {:?}", step);
+  };
+  assert_eq!(batch.transfers.len(), 1);
+  assert_eq!(
+    batch.transfers[0].value.as_deref(),
+    Some("5000000000000000000")
+  );
+  assert_eq!(batch.transfers[0].token_id, None);
+}
+
+fn erc20_transfer(from: Address, to: Address, value: U256) -> TokenTransfer {
+  TokenTransfer {
+    block_number: 1,
+    tx_hash: format!("{:#x}", H256::zero()),
+    log_index: 0,
+    from_address: format!("{:#x}", from),
+    to_address: format!("{:#x}", to),
+    token_id: None,
+    value: Some(value.to_string()),
+  }
+}
+
+#[test]
+fn sums_erc20_balances_past_uint256() {
+  let transfers = [
+    erc20_transfer(Address::zero(), holder(1), U256::MAX),
+    erc20_transfer(Address::zero(), holder(1), U256::MAX),
+    erc20_transfer(holder(1), holder(2), U256::from(7)),
+    erc20_transfer(holder(2), holder(3), U256::from(7)),
+  ];
+
+  let changes = indexer::erc20_changes(&transfers).unwrap();
+
+  // 2 * (2^256 - 1) - 7; holder 2 is back where it was and the mint address
+  // is never listed.
+  let expected = U512::from(U256::MAX) * U512::from(2) - U512::from(7);
+  assert_eq!(
+    changes,
+    HashMap::from([
+      (format!("{:#x}", holder(1)), (expected, U512::zero())),
+      (format!("{:#x}", holder(3)), (U512::from(7), U512::zero())),
+    ])
+  );
+
+  let sent = indexer::erc20_changes(&transfers[2..3]).unwrap();
+  assert_eq!(
+    sent[&format!("{:#x}", holder(1))],
+    (U512::zero(), U512::from(7))
+  );
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn stores_uint256_max_amounts() {
+  let dir = tempfile::tempdir().unwrap();
+  let db = common::mysql_state(dir.path()).await.db;
+  let address = Address::from_slice(
+    &keccak256(common::unique_slug("erc20").as_bytes())[12..],
+  );
+  let contract = indexer::create(
+    &db,
+    &NewIndexedContract {
+      chain: "test".to_string(),
+      address: format!("{:#x}", address),
+      standard: TokenStandard::Erc20,
+      start_block: 0,
+    },
+  )
+  .await
+  .unwrap();
+
+  let batch = Batch {
+    from: 0,
+    to: 1,
+    block_hash: H256::from_low_u64_be(0x1001),
+    transfers: vec![erc20_transfer(Address::zero(), holder(1), U256::MAX)],
+    approvals: vec![TokenApproval {
+      block_number: 1,
+      tx_hash: format!("{:#x}", H256::zero()),
+      log_index: 1,
+      owner_address: format!("{:#x}", holder(1)),
+      spender_address: format!("{:#x}", holder(2)),
+      token_id: None,
+      value: Some(U256::MAX.to_string()),
+    }],
+  };
+  indexer::store_batch(&db, contract.id, &batch)
+    .await
+    .unwrap();
+
+  let approved: String = sqlx::query_scalar(
+    "SELECT value FROM token_approvals WHERE contract_id = ?",
+  )
+  .bind(contract.id)
+  .fetch_one(&db)
+  .await
+  .unwrap();
+  assert_eq!(approved, U256::MAX.to_string());
+
+  let transfers = indexer::transfers(&db, contract.id, &EventQuery::default())
+    .await
+    .unwrap();
+  assert_eq!(transfers.items, batch.transfers);
+
+  let holders = indexer::holders(&db, &contract, &EventQuery::default())
+    .await
+    .unwrap();
+  assert_eq!(holders.total, 1);
+  assert_eq!(holders.items[0].balance, U256::MAX.to_string());
+
+  let cursor = indexer::cursor(&db, contract.id).await.unwrap();
+  assert_eq!(cursor.next_block, 2);
+  assert_eq!(cursor.checkpoints, vec![(1, batch.block_hash)]);
+}
+
+#[tokio::test]
+async fn rejects_contracts_on_unknown_chains() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  let request = Request::post("/contracts")
+    .header(header::CONTENT_TYPE, "application/json")
+    .header("x-api-key", common::API_KEY)
+    .body(Body::from(
+      json!({
+        "chain": "nowhere",
+        "address": format!("{:#x}", contract()),
+        "standard": "erc721",
+      })
+      .to_string(),
+    ))
+    .unwrap();
+  let response = app.oneshot(request).await.unwrap();
+  assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+  let body: Value = serde_json::from_slice(&body).unwrap();
+  assert_eq!(body["error"]["input"], "chain");
+}
+
+#[tokio::test]
+async fn registering_contracts_needs_an_api_key() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+  let body = json!({
+    "chain": "nowhere",
+    "address": format!("{:#x}", contract()),
+    "standard": "erc721",
+  })
+  .to_string();
+
+  for key in [None, Some("wrong")] {
+    let mut request = Request::post("/contracts")
+      .header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = key {
+      request = request.header("x-api-key", key);
+    }
+    let request = request.body(Body::from(body.clone())).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{:?}", key);
+  }
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn keeps_erc20_balances_through_rewinds() {
+  let dir = tempfile::tempdir().unwrap();
+  let db = common::mysql_state(dir.path()).await.db;
+  let address = Address::from_slice(
+    &keccak256(common::unique_slug("erc20").as_bytes())[12..],
+  );
+  let contract = indexer::create(
+    &db,
+    &NewIndexedContract {
+      chain: "test".to_string(),
+      address: format!("{:#x}", address),
+      standard: TokenStandard::Erc20,
+      start_block: 0,
+    },
+  )
+  .await
+  .unwrap();
+
+  let minted = Batch {
+    from: 0,
+    to: 1,
+    block_hash: H256::from_low_u64_be(0x1001),
+    transfers: vec![
+      erc20_transfer(Address::zero(), holder(1), U256::from(100)),
+      // Holder 3 held tokens from before `start_block`.
+      erc20_transfer(holder(3), holder(2), U256::from(5)),
+    ],
+    approvals: vec![],
+  };
+  indexer::store_batch(&db, contract.id, &minted)
+    .await
+    .unwrap();
+
+  let mut moved = erc20_transfer(holder(1), holder(2), U256::from(60));
+  moved.block_number = 2;
+  let batch = Batch {
+    from: 2,
+    to: 2,
+    block_hash: H256::from_low_u64_be(0x1002),
+    transfers: vec![moved],
+    approvals: vec![],
+  };
+  indexer::store_batch(&db, contract.id, &batch)
+    .await
+    .unwrap();
+
+  let balances = |holders: Page<Holder>| {
+    holders
+      .items
+      .into_iter()
+      .map(|holder| (holder.address, holder.balance))
+      .collect::<Vec<_>>()
+  };
+  let query = EventQuery {
+    per_page: Some(1),
+    ..Default::default()
+  };
+  let holders = indexer::holders(&db, &contract, &query).await.unwrap();
+  assert_eq!(holders.total, 2);
+  assert_eq!(
+    balances(holders),
+    vec![(format!("{:#x}", holder(2)), "65".to_string())]
+  );
+
+  // Block 2 was reorged away: its transfer comes back out of the balances.
+  indexer::rewind(&db, contract.id, &cursor(2, Some(minted.block_hash)))
+    .await
+    .unwrap();
+  let holders = indexer::holders(&db, &contract, &EventQuery::default())
+    .await
+    .unwrap();
+  assert_eq!(
+    balances(holders),
+    vec![
+      (format!("{:#x}", holder(1)), "100".to_string()),
+      (format!("{:#x}", holder(2)), "5".to_string()),
+    ]
+  );
+}
@@ -0,0 +1,262 @@
+mod common;
+
+use std::io::Read;
+use std::time::Duration;
+
+use axum::{
+  body::Body,
+  http::{header, Request, StatusCode},
+};
+use back::errors::AppError;
+use back::jobs::generate::Archive;
+use back::model::job;
+use ethers::utils::{hex, keccak256};
+use shared::job::{JobRequest, JobStatus};
+use shared::metadata::{numbered, Attribute, Metadata};
+use tower::ServiceExt;
+
+fn template() -> Metadata {
+  Metadata {
+    name: "Toy".to_string(),
+    description: "A toy, boxed".to_string(),
+    image: "ipfs://cid/toy.png".to_string(),
+    attributes: vec![Attribute {
+      trait_type: "Color".to_string(),
+      value: "Red".to_string(),
+    }],
+  }
+}
+
+fn read(
+  zip: &mut zip::ZipArchive<std::io::Cursor<Vec<u8>>>,
+  name: &str,
+) -> String {
+  let mut contents = String::new();
+  zip
+    .by_name(name)
+    .unwrap()
+    .read_to_string(&mut contents)
+    .unwrap();
+  contents
+}
+
+#[test]
+fn archive_holds_tokens_csv_and_manifest() {
+  let template = template();
+  let mut archive = Archive::new("toys", &template);
+  let tokens: Vec<Vec<u8>> = (0..3)
+    .map(|i| archive.add(&numbered(&template, i)).unwrap())
+    .collect();
+  let provenance = archive.provenance();
+  let bytes = archive.finish().unwrap();
+
+  let mut zip = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+  assert_eq!(zip.len(), 5);
+
+  let token: Metadata =
+    serde_json::from_str(&read(&mut zip, "1.json")).unwrap();
+  assert_eq!(token.name, "Toy #1");
+  assert_eq!(token.attributes, template.attributes);
+
+  let csv = read(&mut zip, "toys.csv");
+  let lines: Vec<&str> = csv.lines().collect();
+  assert_eq!(
+    lines[0],
+    "token_id,name,description,image,attributes.trait_type_0,attributes.value_0"
+  );
+  assert_eq!(
+    lines[1],
+    r#"0,Toy #0,"A toy, boxed",ipfs://cid/toy.png,Color,Red"#
+  );
+  assert_eq!(lines.len(), 4);
+
+  let hashes: Vec<[u8; 32]> = tokens.iter().map(keccak256).collect();
+  assert_eq!(provenance, keccak256(hashes.concat()));
+
+  let manifest: serde_json::Value =
+    serde_json::from_str(&read(&mut zip, "manifest.json")).unwrap();
+  assert_eq!(manifest["count"], 3);
+  assert_eq!(manifest["provenance"], hex::encode_prefixed(provenance));
+  assert_eq!(
+    manifest["tokens"][2]["keccak256"],
+    hex::encode_prefixed(hashes[2])
+  );
+}
+
+#[tokio::test]
+async fn rejects_invalid_generate_requests() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  for (body, input) in [
+    (
+      r#"{"kind":"generate","project_name":"toys","template":{},"count":0}"#,
+      "count",
+    ),
+    (
+      r#"{"kind":"generate","project_name":"","template":{},"count":1}"#,
+      "project_name",
+    ),
+    (
+      r#"{"kind":"generate","project_name":"toys","template":{},"count":1,"slug":"a/b"}"#,
+      "slug",
+    ),
+  ] {
+    let request = Request::post("/jobs")
+      .header(header::CONTENT_TYPE, "application/json")
+      .header("x-api-key", common::API_KEY)
+      .body(Body::from(body))
+      .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+      .await
+      .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"]["input"], input);
+  }
+}
+
+#[tokio::test]
+async fn queuing_jobs_needs_an_api_key() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+  let body = serde_json::to_vec(&generate(1, Some("drop"))).unwrap();
+
+  for key in [None, Some("wrong")] {
+    let mut request =
+      Request::post("/jobs").header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = key {
+      request = request.header("x-api-key", key);
+    }
+    let request = request.body(Body::from(body.clone())).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{:?}", key);
+  }
+  assert!(!dir.path().join("collections").join("drop").exists());
+}
+
+fn generate(count: u32, slug: Option<&str>) -> JobRequest {
+  JobRequest::Generate {
+    project_name: "toys".to_string(),
+    template: template(),
+    count,
+    slug: slug.map(str::to_string),
+  }
+}
+
+/// Claims until `id` comes up; older jobs other runs left queued in the
+/// scratch database are failed out of the way.
+async fn claim(
+  db: &sqlx::MySqlPool,
+  id: u64,
+  lease: Duration,
+) -> (job::Claim, JobRequest) {
+  loop {
+    let (claimed, request) = job::claim_next(db, lease).await.unwrap().unwrap();
+    if claimed.id == id {
+      return (claimed, request);
+    }
+    job::fail(db, claimed, "cleared by tests").await.unwrap();
+  }
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn claimed_jobs_move_through_their_statuses() {
+  let dir = tempfile::tempdir().unwrap();
+  let db = common::mysql_state(dir.path()).await.db;
+  let lease = Duration::from_secs(60);
+
+  let created = job::create(&db, &generate(3, None)).await.unwrap();
+  assert_eq!(created.status, JobStatus::Queued);
+  assert_eq!(created.total, 3);
+
+  let (claimed, request) = claim(&db, created.id, lease).await;
+  assert_eq!(request, generate(3, None));
+  assert_eq!(
+    job::find(&db, created.id).await.unwrap().status,
+    JobStatus::Running
+  );
+
+  job::set_progress(&db, claimed, 2).await.unwrap();
+  assert_eq!(job::find(&db, created.id).await.unwrap().progress, 2);
+
+  // Shutdown hands it back.
+  job::requeue(&db, claimed).await.unwrap();
+  let requeued = job::find(&db, created.id).await.unwrap();
+  assert_eq!(requeued.status, JobStatus::Queued);
+  assert_eq!(requeued.progress, 0);
+
+  let (claimed, _) = claim(&db, created.id, lease).await;
+  job::finish(&db, claimed).await.unwrap();
+  let done = job::find(&db, created.id).await.unwrap();
+  assert_eq!(done.status, JobStatus::Done);
+  assert_eq!(done.progress, 3);
+
+  let failing = job::create(&db, &generate(1, None)).await.unwrap();
+  let (claimed, _) = claim(&db, failing.id, lease).await;
+  job::fail(&db, claimed, "boom").await.unwrap();
+  let failed = job::find(&db, failing.id).await.unwrap();
+  assert_eq!(failed.status, JobStatus::Failed);
+  assert_eq!(failed.error.as_deref(), Some("boom"));
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn running_jobs_are_only_reclaimed_once_their_lease_lapses() {
+  let dir = tempfile::tempdir().unwrap();
+  let db = common::mysql_state(dir.path()).await.db;
+
+  let created = job::create(&db, &generate(1, None)).await.unwrap();
+  let (first, _) = claim(&db, created.id, Duration::from_millis(300)).await;
+
+  // Leased: nobody else gets it, heartbeat or not.
+  job::renew_lease(&db, first, Duration::from_millis(300))
+    .await
+    .unwrap();
+  let others = job::claim_next(&db, Duration::from_secs(60)).await.unwrap();
+  assert!(others.map_or(true, |(claimed, _)| claimed.id != created.id));
+
+  // Its worker stalled: the lease runs out and another worker takes over.
+  tokio::time::sleep(Duration::from_millis(500)).await;
+  let (second, _) = claim(&db, created.id, Duration::from_secs(60)).await;
+  assert_ne!(first, second);
+
+  // The stalled worker can no longer touch the job.
+  let lease = Duration::from_secs(60);
+  let lost = |r: Result<(), AppError>| matches!(r, Err(AppError::Conflict(_)));
+  assert!(lost(job::renew_lease(&db, first, lease).await));
+  assert!(lost(job::set_progress(&db, first, 1).await));
+  assert!(lost(job::fail(&db, first, "stale").await));
+  assert!(lost(job::requeue(&db, first).await));
+  assert!(lost(job::finish(&db, first).await));
+  assert_eq!(
+    job::find(&db, created.id).await.unwrap().status,
+    JobStatus::Running
+  );
+
+  job::finish(&db, second).await.unwrap();
+  assert!(lost(job::finish(&db, second).await));
+}
+
+#[tokio::test]
+#[ignore = "needs MySQL at TEST_DATABASE_URL"]
+async fn rejects_generate_jobs_for_unknown_collections() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::mysql_state(dir.path()).await);
+  let slug = common::unique_slug("missing");
+
+  let request = Request::post("/jobs")
+    .header(header::CONTENT_TYPE, "application/json")
+    .header("x-api-key", common::API_KEY)
+    .body(Body::from(
+      serde_json::to_vec(&generate(1, Some(&slug))).unwrap(),
+    ))
+    .unwrap();
+  let response = app.oneshot(request).await.unwrap();
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+  assert!(!dir.path().join("collections").join(&slug).exists());
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use axum::{
+  body::Body,
+  extract::ConnectInfo,
+  http::{header, Request, StatusCode},
+  Router,
+};
+use back::config::Config;
+use back::middleware::rate_limit::RateLimit;
+use tower::ServiceExt;
+
+fn config() -> Config {
+  Config {
+    rate_limit_ip: RateLimit {
+      burst: 2,
+      per_second: 1.0,
+    },
+    rate_limit_api_key: RateLimit {
+      burst: 4,
+      per_second: 1.0,
+    },
+    api_keys: vec!["partner".to_string()],
+    body_limit: 64,
+    upload_body_limit: 1024,
+    ..Config::default()
+  }
+}
+
+fn get(uri: &str, ip: [u8; 4]) -> Request<Body> {
+  let mut request = Request::get(uri).body(Body::empty()).unwrap();
+  request
+    .extensions_mut()
+    .insert(ConnectInfo(SocketAddr::from((ip, 40000))));
+  request
+}
+
+fn put_json(uri: &str, size: usize) -> Request<Body> {
+  let body = format!(r#"{{"name":"{}"}}"#, "x".repeat(size));
+  let mut request = Request::put(uri)
+    .header(header::CONTENT_TYPE, "application/json")
+    .header("x-api-key", "partner")
+    .header(header::CONTENT_LENGTH, body.len())
+    .body(Body::from(body))
+    .unwrap();
+  request
+    .extensions_mut()
+    .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 40000))));
+  request
+}
+
+async fn send(app: &Router, request: Request<Body>) -> StatusCode {
+  app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test(start_paused = true)]
+async fn limits_each_ip_to_its_burst() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state_with(dir.path(), config()));
+
+  let uri = "/collections/drop/reveal";
+  assert_eq!(send(&app, get(uri, [10, 0, 0, 1])).await, StatusCode::OK);
+  assert_eq!(send(&app, get(uri, [10, 0, 0, 1])).await, StatusCode::OK);
+
+  let response = app.clone().oneshot(get(uri, [10, 0, 0, 1])).await.unwrap();
+  assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+  assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+  let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+  assert_eq!(body["error"]["msg"], "too many requests");
+
+  // Another client still has its own bucket.
+  assert_eq!(send(&app, get(uri, [10, 0, 0, 2])).await, StatusCode::OK);
+
+  tokio::time::advance(std::time::Duration::from_secs(1)).await;
+  assert_eq!(send(&app, get(uri, [10, 0, 0, 1])).await, StatusCode::OK);
+}
+
+#[tokio::test(start_paused = true)]
+async fn known_api_keys_get_their_own_bucket() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state_with(dir.path(), config()));
+
+  let with_key = |key: &str| {
+    let mut request = get("/collections/drop/reveal", [10, 0, 0, 1]);
+    request
+      .headers_mut()
+      .insert("x-api-key", key.parse().unwrap());
+    request
+  };
+
+  for _ in 0..4 {
+    assert_eq!(send(&app, with_key("partner")).await, StatusCode::OK);
+  }
+  assert_eq!(
+    send(&app, with_key("partner")).await,
+    StatusCode::TOO_MANY_REQUESTS
+  );
+
+  // An unknown key falls back to the IP bucket, which is still full.
+  assert_eq!(send(&app, with_key("guess")).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn health_and_metrics_are_not_limited() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state_with(dir.path(), config()));
+
+  for _ in 0..5 {
+    assert_eq!(
+      send(&app, get("/health/live", [10, 0, 0, 1])).await,
+      StatusCode::OK
+    );
+    assert_eq!(
+      send(&app, get("/metrics", [10, 0, 0, 1])).await,
+      StatusCode::OK
+    );
+  }
+}
+
+#[tokio::test]
+async fn rejects_bodies_over_the_route_limit() {
+  let dir = tempfile::tempdir().unwrap();
+  let config = Config {
+    rate_limit_ip: Config::default().rate_limit_ip,
+    ..config()
+  };
+  let app = back::app(common::app_state_with(dir.path(), config));
+
+  let response = app
+    .clone()
+    .oneshot(put_json("/collections/drop/reveal", 100))
+    .await
+    .unwrap();
+  assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+  let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+  assert_eq!(body["error"]["limit"], 64);
+
+  // Uploads take the larger limit...
+  assert_eq!(
+    send(&app, put_json("/collections/drop/placeholder", 100)).await,
+    StatusCode::NO_CONTENT
+  );
+  // ...up to their own.
+  assert_eq!(
+    send(&app, put_json("/collections/drop/tokens/1", 2000)).await,
+    StatusCode::PAYLOAD_TOO_LARGE
+  );
+}
+
+#[tokio::test]
+async fn rejects_chunked_bodies_over_the_limit_as_json() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state_with(dir.path(), config()));
+
+  let chunked = |size: usize| {
+    let body = format!(r#"{{"revealed":true,"pad":"{}"}}"#, "x".repeat(size));
+    let chunks = body
+      .into_bytes()
+      .chunks(16)
+      .map(|chunk| Ok::<_, std::io::Error>(chunk.to_vec()))
+      .collect::<Vec<_>>();
+    let mut request = Request::put("/collections/drop/reveal")
+      .header(header::CONTENT_TYPE, "application/json")
+      .header("x-api-key", "partner")
+      .body(Body::from_stream(futures::stream::iter(chunks)))
+      .unwrap();
+    request
+      .extensions_mut()
+      .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 40000))));
+    request
+  };
+
+  let response = app.clone().oneshot(chunked(100)).await.unwrap();
+  assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+  let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+  assert_eq!(body["error"]["limit"], 64);
+
+  // Under the limit the buffered body still reaches the handler.
+  assert_eq!(send(&app, chunked(0)).await, StatusCode::OK);
+}
@@ -0,0 +1,256 @@
+mod common;
+
+use axum::{
+  body::Body,
+  extract::State,
+  http::{header, Request, StatusCode},
+  routing::post,
+  Json, Router,
+};
+use back::chain::Chains;
+use back::config::{AppState, Config};
+use back::storage::Storage;
+use ethers::types::{Address, U256};
+use serde_json::{json, Value};
+use shared::reveal::RevealSettings;
+use tower::ServiceExt;
+
+const API_KEY: &str = "admin";
+
+fn config() -> Config {
+  Config {
+    api_keys: vec![API_KEY.to_string()],
+    ..Config::default()
+  }
+}
+
+async fn call(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+  let response = app.clone().oneshot(request).await.unwrap();
+  let status = response.status();
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+  let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+  (status, body)
+}
+
+fn get(uri: &str) -> Request<Body> {
+  Request::get(uri).body(Body::empty()).unwrap()
+}
+
+fn put(uri: &str, api_key: Option<&str>, body: Value) -> Request<Body> {
+  let mut request =
+    Request::put(uri).header(header::CONTENT_TYPE, "application/json");
+  if let Some(key) = api_key {
+    request = request.header("x-api-key", key);
+  }
+  request.body(Body::from(body.to_string())).unwrap()
+}
+
+/// `ownerOf` answered with a fixed JSON-RPC response.
+async fn rpc(
+  State(answer): State<Value>,
+  Json(request): Json<Value>,
+) -> Json<Value> {
+  let mut response = answer.clone();
+  response["jsonrpc"] = json!("2.0");
+  response["id"] = request["id"].clone();
+  Json(response)
+}
+
+async fn chain(answer: Value) -> Chains {
+  let app = Router::new().route("/", post(rpc)).with_state(answer);
+  let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let url = format!("http://{}", listener.local_addr().unwrap());
+  tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+  Chains::parse(&format!("test={}", url)).unwrap()
+}
+
+fn owner_word(owner: Address) -> Value {
+  json!({"result": format!("0x{:0>64}", ethers::utils::hex::encode(owner))})
+}
+
+async fn minted_drop(dir: &std::path::Path, answer: Value) -> AppState {
+  let mut state = common::app_state_with(dir, config());
+  state.chains = chain(answer).await;
+  state
+    .storage
+    .put_placeholder("drop", &json!({"name": "?"}))
+    .await
+    .unwrap();
+  state
+    .storage
+    .put_token_metadata("drop", U256::from(1), &json!({"name": "#1"}))
+    .await
+    .unwrap();
+  state
+    .storage
+    .put_reveal_settings(
+      "drop",
+      &RevealSettings {
+        revealed: true,
+        require_minted: true,
+        chain: Some("test".to_string()),
+        contract_address: Some(format!("{:?}", Address::repeat_byte(0xc0))),
+        ..RevealSettings::default()
+      },
+    )
+    .await
+    .unwrap();
+  state
+}
+
+#[tokio::test]
+async fn storage_round_trips_collection_files() {
+  let dir = tempfile::tempdir().unwrap();
+  let storage = Storage::new(dir.path());
+
+  assert_eq!(
+    storage.reveal_settings("drop").await.unwrap(),
+    RevealSettings::default()
+  );
+  assert_eq!(storage.placeholder("drop").await.unwrap(), None);
+
+  storage
+    .put_placeholder("drop", &json!({"name": "?"}))
+    .await
+    .unwrap();
+  storage
+    .put_token_metadata("drop", U256::from(7), &json!({"name": "#7"}))
+    .await
+    .unwrap();
+
+  assert_eq!(
+    storage.placeholder("drop").await.unwrap(),
+    Some(json!({"name": "?"}))
+  );
+  assert_eq!(
+    storage.token_metadata("drop", U256::from(7)).await.unwrap(),
+    Some(json!({"name": "#7"}))
+  );
+  assert!(dir.path().join("collections/drop/tokens/7.json").exists());
+  assert!(!dir
+    .path()
+    .join("collections/drop/tokens/7.json.tmp")
+    .exists());
+}
+
+#[tokio::test]
+async fn storage_rejects_slugs_outside_the_root() {
+  let dir = tempfile::tempdir().unwrap();
+  let storage = Storage::new(dir.path());
+
+  for slug in ["", "../etc", "a/b", "a.b"] {
+    assert!(storage.placeholder(slug).await.is_err(), "{:?}", slug);
+  }
+}
+
+#[tokio::test]
+async fn serves_the_placeholder_until_revealed() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state_with(dir.path(), config()));
+
+  let (status, _) = call(
+    &app,
+    put(
+      "/collections/drop/placeholder",
+      Some(API_KEY),
+      json!({"name": "?"}),
+    ),
+  )
+  .await;
+  assert_eq!(status, StatusCode::NO_CONTENT);
+  let (status, _) = call(
+    &app,
+    put(
+      "/collections/drop/tokens/1",
+      Some(API_KEY),
+      json!({"name": "#1"}),
+    ),
+  )
+  .await;
+  assert_eq!(status, StatusCode::NO_CONTENT);
+
+  let (status, body) = call(&app, get("/metadata/drop/1")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, json!({"name": "?"}));
+
+  let (status, body) = call(
+    &app,
+    put(
+      "/collections/drop/reveal",
+      Some(API_KEY),
+      json!({"reveal_at": "2000-01-01T00:00:00Z"}),
+    ),
+  )
+  .await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body["reveal_at"], "2000-01-01T00:00:00Z");
+
+  let (_, body) = call(&app, get("/metadata/drop/1")).await;
+  assert_eq!(body, json!({"name": "#1"}));
+
+  // Revealed, but nothing uploaded for this token.
+  let (status, _) = call(&app, get("/metadata/drop/2")).await;
+  assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn writes_need_a_configured_api_key() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state_with(dir.path(), config()));
+
+  for key in [None, Some("guess")] {
+    for uri in [
+      "/collections/drop/placeholder",
+      "/collections/drop/tokens/1",
+      "/collections/drop/reveal",
+    ] {
+      let (status, body) = call(&app, put(uri, key, json!({}))).await;
+      assert_eq!(status, StatusCode::UNAUTHORIZED, "{} {:?}", uri, key);
+      assert_eq!(body["error"]["msg"], "api key required");
+    }
+  }
+
+  // Reads stay public.
+  let (status, _) = call(&app, get("/collections/drop/reveal")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert!(!dir.path().join("collections/drop").exists());
+}
+
+#[tokio::test]
+async fn reveals_minted_tokens() {
+  let dir = tempfile::tempdir().unwrap();
+  let state =
+    minted_drop(dir.path(), owner_word(Address::repeat_byte(0x11))).await;
+  let app = back::app(state);
+
+  let (status, body) = call(&app, get("/metadata/drop/1")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, json!({"name": "#1"}));
+}
+
+#[tokio::test]
+async fn keeps_the_placeholder_when_owner_of_reverts() {
+  let dir = tempfile::tempdir().unwrap();
+  let revert = json!({"error": {"code": 3, "message": "execution reverted", "data": "0x"}});
+  let app = back::app(minted_drop(dir.path(), revert).await);
+
+  let (status, body) = call(&app, get("/metadata/drop/1")).await;
+  assert_eq!(status, StatusCode::OK);
+  assert_eq!(body, json!({"name": "?"}));
+}
+
+#[tokio::test]
+async fn reports_other_rpc_errors_instead_of_hiding_metadata() {
+  let dir = tempfile::tempdir().unwrap();
+  let outage =
+    json!({"error": {"code": -32000, "message": "header not found"}});
+  let app = back::app(minted_drop(dir.path(), outage).await);
+
+  let (status, body) = call(&app, get("/metadata/drop/1")).await;
+  assert_eq!(status, StatusCode::BAD_GATEWAY);
+  assert_eq!(body["error"]["msg"], "rpc error");
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use axum::{
+  body::Body,
+  http::{Request, StatusCode},
+  Router,
+};
+use tower::ServiceExt;
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+  let response = app
+    .clone()
+    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+    .await
+    .unwrap();
+  let status = response.status();
+  let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+    .await
+    .unwrap();
+
+  (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn counts_requests_by_route_and_status() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  get(&app, "/health").await;
+  get(&app, "/health").await;
+  get(&app, "/metadata/drop/1").await;
+
+  let (status, body) = get(&app, "/metrics").await;
+  assert_eq!(status, StatusCode::OK);
+  assert!(body.contains(
+    r#"http_requests_total{method="GET",route="/health",status="200"} 2"#
+  ));
+  assert!(body.contains(
+    r#"http_requests_total{method="GET",route="/metadata/:slug/:token_id",status="404"} 1"#
+  ));
+  assert!(body.contains(
+    r#"http_request_duration_seconds_count{method="GET",route="/health",status="200"} 2"#
+  ));
+}
+
+#[tokio::test]
+async fn counts_metadata_reads_per_collection() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = common::app_state(dir.path());
+  state
+    .storage
+    .put_placeholder("drop", &serde_json::json!({"name": "?"}))
+    .await
+    .unwrap();
+  let app = back::app(state);
+
+  for token_id in 0..3 {
+    let (status, _) = get(&app, &format!("/metadata/drop/{}", token_id)).await;
+    assert_eq!(status, StatusCode::OK);
+  }
+  get(&app, "/metadata/other/0").await;
+
+  let (_, body) = get(&app, "/metrics").await;
+  assert!(body.contains(r#"metadata_reads_total{collection="drop"} 3"#));
+  // Nothing hosted under "other", so it never becomes a label.
+  assert!(!body.contains(r#"collection="other""#));
+}
+
+#[tokio::test]
+async fn reports_db_pool_connections() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  let (_, body) = get(&app, "/metrics").await;
+  assert!(body.contains(r#"db_pool_connections{state="idle"} 0"#));
+  assert!(body.contains(r#"db_pool_connections{state="in_use"} 0"#));
+  assert!(body.contains(r#"db_pool_connections{state="max"} 10"#));
+}
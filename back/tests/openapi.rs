@@ -0,0 +1,85 @@
+mod common;
+
+use back::service::docs::ApiDoc;
+use utoipa::OpenApi;
+
+const SPEC_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+/// Fails when a handler or schema changed without regenerating the committed
+/// spec. Run with `UPDATE_OPENAPI=1` to rewrite `back/openapi.json`.
+#[test]
+fn committed_spec_matches_handlers() {
+  let generated = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+
+  if std::env::var_os("UPDATE_OPENAPI").is_some() {
+    std::fs::write(SPEC_PATH, &generated).unwrap();
+    return;
+  }
+
+  let committed = std::fs::read_to_string(SPEC_PATH).unwrap_or_default();
+  assert!(
+    committed == generated,
+    "back/openapi.json is out of date; regenerate it with \
+     `UPDATE_OPENAPI=1 cargo test -p back --test openapi`"
+  );
+}
+
+#[test]
+fn every_route_is_documented() {
+  let spec = ApiDoc::openapi();
+
+  for path in [
+    "/json",
+    "/collections",
+    "/collections/{slug}",
+    "/contracts",
+    "/contracts/{id}",
+    "/contracts/{id}/transfers",
+    "/contracts/{id}/holders",
+    "/jobs",
+    "/jobs/{id}",
+    "/jobs/{id}/result",
+    "/metrics",
+    "/health/live",
+    "/health/ready",
+    "/metadata/{slug}/{token_id}",
+    "/collections/{slug}/tokens/{token_id}",
+    "/collections/{slug}/placeholder",
+    "/collections/{slug}/reveal",
+  ] {
+    assert!(spec.paths.paths.contains_key(path), "{} is missing", path);
+  }
+}
+
+#[tokio::test]
+async fn docs_page_loads_only_bundled_assets() {
+  use axum::{body::Body, http::Request};
+  use tower::ServiceExt;
+
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+  let get = |uri: &'static str| {
+    app
+      .clone()
+      .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+  };
+
+  let page = get("/docs").await.unwrap();
+  let page = axum::body::to_bytes(page.into_body(), usize::MAX)
+    .await
+    .unwrap();
+  let page = String::from_utf8(page.to_vec()).unwrap();
+  assert!(!page.contains("https://"), "{}", page);
+
+  for (uri, content_type) in [
+    (
+      "/docs/swagger-ui-bundle.js",
+      "text/javascript; charset=utf-8",
+    ),
+    ("/docs/swagger-ui.css", "text/css; charset=utf-8"),
+  ] {
+    let response = get(uri).await.unwrap();
+    assert_eq!(response.status(), 200, "{}", uri);
+    assert_eq!(response.headers()["content-type"], content_type);
+  }
+}
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+  body::Body,
+  http::{Request, StatusCode},
+  routing::get,
+  Router,
+};
+use back::shutdown::{cancel_requests_on, Background};
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+
+fn get_request(uri: &str) -> Request<Body> {
+  Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn cancels_requests_still_running() {
+  let token = CancellationToken::new();
+  let app = cancel_requests_on(
+    Router::new()
+      .route("/fast", get("ok"))
+      .route("/stuck", get(std::future::pending::<&'static str>)),
+    token.clone(),
+  );
+
+  let response = app.clone().oneshot(get_request("/fast")).await.unwrap();
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let stuck = tokio::spawn(app.oneshot(get_request("/stuck")));
+  tokio::time::sleep(Duration::from_millis(10)).await;
+  assert!(!stuck.is_finished());
+
+  token.cancel();
+  let response = stuck.await.unwrap().unwrap();
+  assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test(start_paused = true)]
+async fn background_shutdown_waits_for_workers() {
+  let background = Background::default();
+  let stopped = Arc::new(AtomicBool::new(false));
+
+  let flag = stopped.clone();
+  background.spawn("worker", |token| async move {
+    token.cancelled().await;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    flag.store(true, Ordering::SeqCst);
+  });
+
+  background.shutdown(Duration::from_secs(5)).await;
+  assert!(stopped.load(Ordering::SeqCst));
+}
+
+#[tokio::test(start_paused = true)]
+async fn background_shutdown_gives_up_after_the_timeout() {
+  let background = Background::default();
+  background.spawn("stuck", |_| std::future::pending::<()>());
+
+  let started = tokio::time::Instant::now();
+  background.shutdown(Duration::from_secs(5)).await;
+  assert_eq!(started.elapsed(), Duration::from_secs(5));
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use axum::{body::Body, http::Request, Router};
+use tower::ServiceExt;
+
+async fn request_id(app: &Router, request: Request<Body>) -> Option<String> {
+  let response = app.clone().oneshot(request).await.unwrap();
+  response
+    .headers()
+    .get("x-request-id")
+    .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn generates_a_request_id() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  let first =
+    request_id(&app, Request::get("/health").body(Body::empty()).unwrap())
+      .await
+      .unwrap();
+  let second =
+    request_id(&app, Request::get("/health").body(Body::empty()).unwrap())
+      .await
+      .unwrap();
+
+  assert_eq!(first.len(), 36, "{}", first);
+  assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn echoes_the_client_request_id() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  let request = Request::get("/health")
+    .header("x-request-id", "client-123")
+    .body(Body::empty())
+    .unwrap();
+
+  assert_eq!(
+    request_id(&app, request).await.as_deref(),
+    Some("client-123")
+  );
+}
+
+#[tokio::test]
+async fn keeps_the_request_id_on_errors() {
+  let dir = tempfile::tempdir().unwrap();
+  let app = back::app(common::app_state(dir.path()));
+
+  // 400 from a handler, and a 404 from the router itself.
+  for uri in ["/metadata/bad.slug/1", "/nowhere"] {
+    let request = Request::get(uri)
+      .header("x-request-id", "client-456")
+      .body(Body::empty())
+      .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+
+    assert!(response.status().is_client_error(), "{}", uri);
+    assert_eq!(response.headers()["x-request-id"], "client-456", "{}", uri);
+  }
+}
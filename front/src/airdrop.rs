@@ -0,0 +1,690 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use ethers::{
+  contract::ContractError,
+  types::{Address, TxHash, U256},
+};
+use poll_promise::Promise;
+use shared::contract_abis::ierc721::IERC721;
+
+use crate::disperse::is_header;
+use crate::rpc::{self, RpcProvider};
+use crate::signer::{Outcome, SignerWindow, TxRequest};
+use crate::tx_history::{self, MAX_UNSEEN_POLLS, POLL_INTERVAL};
+
+/// Largest `count` one mint row may ask for.
+const MAX_MINT_PER_ROW: usize = 1000;
+/// Receipt polls running at once; the rest wait for the next frame.
+const MAX_RECEIPT_POLLS: usize = 8;
+
+/// Dry-run result for one item: the owner to transfer from, or why it
+/// would fail.
+type Check = Result<Option<Address>, String>;
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum AirdropMode {
+  /// `recipient,tokenId` rows sent with `safeTransferFrom`.
+  Transfer,
+  /// `recipient,count` rows minted with consecutive token IDs.
+  Mint,
+}
+
+#[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ItemStatus {
+  Queued,
+  /// Broadcast by `from`, waiting for its receipt.
+  Pending {
+    hash: TxHash,
+    from: Address,
+  },
+  /// Mined with status 1.
+  Sent(TxHash),
+  Failed(String),
+}
+
+/// One token going to one recipient. Kept with the app state, so sent
+/// items are skipped when an interrupted airdrop is resumed.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Item {
+  pub line: usize,
+  pub recipient: Address,
+  pub token_id: U256,
+  pub status: ItemStatus,
+
+  #[serde(skip)]
+  check: Option<Check>,
+  /// The signer request paying this item and the account it was sent from.
+  #[serde(skip)]
+  outcome: Option<(Outcome, Address)>,
+  #[serde(skip)]
+  poll: Option<Promise<Result<tx_history::Outcome, String>>>,
+  #[serde(skip)]
+  next_poll: f64,
+  #[serde(skip)]
+  nonce: Option<U256>,
+  #[serde(skip)]
+  unseen_polls: u32,
+}
+
+/// Batch `safeTransferFrom` / `mint` for one ERC-721 contract.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Airdrop {
+  pub mode: AirdropMode,
+  pub csv: String,
+  /// First token ID for [`AirdropMode::Mint`].
+  pub first_token_id: String,
+  pub items: Vec<Item>,
+
+  #[serde(skip)]
+  parse_errors: Vec<String>,
+  #[serde(skip)]
+  progress: Arc<Mutex<usize>>,
+  #[serde(skip)]
+  dry_run: Option<Promise<Vec<Check>>>,
+  #[serde(skip)]
+  error: Option<String>,
+}
+
+impl Default for Airdrop {
+  fn default() -> Self {
+    Self {
+      mode: AirdropMode::Transfer,
+      csv: "".to_string(),
+      first_token_id: "0".to_string(),
+      items: vec![],
+
+      parse_errors: vec![],
+      progress: Arc::new(Mutex::new(0)),
+      dry_run: None,
+      error: None,
+    }
+  }
+}
+
+impl Airdrop {
+  pub fn ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    contract_name: &str,
+    contract_address: &str,
+    rpc_url: &str,
+    signer: &mut SignerWindow,
+  ) {
+    self.sync_outcomes();
+    self.poll_receipts(ui.ctx(), rpc_url);
+
+    ui.horizontal(|ui| {
+      ui.radio_value(
+        &mut self.mode,
+        AirdropMode::Transfer,
+        "safeTransferFrom (recipient,tokenId)",
+      );
+      ui.radio_value(
+        &mut self.mode,
+        AirdropMode::Mint,
+        "mint (recipient,count)",
+      );
+    });
+    if self.mode == AirdropMode::Mint {
+      ui.horizontal(|ui| {
+        ui.label("시작 토큰 ID");
+        ui.text_edit_singleline(&mut self.first_token_id);
+      });
+    }
+    ui.add(
+      egui::TextEdit::multiline(&mut self.csv)
+        .code_editor()
+        .desired_rows(5)
+        .desired_width(f32::INFINITY),
+    );
+
+    let sending = self.items.iter().any(Item::in_flight);
+    let running = self.dry_run.is_some();
+    // Replacing the list would stop tracking broadcast items.
+    let mining = self
+      .items
+      .iter()
+      .any(|item| matches!(item.status, ItemStatus::Pending { .. }));
+    let locked = sending || running || mining;
+    ui.horizontal(|ui| {
+      if ui
+        .add_enabled(!locked, egui::Button::new("CSV 불러오기"))
+        .clicked()
+      {
+        self.load();
+      }
+      if !self.items.is_empty()
+        && ui
+          .add_enabled(!locked, egui::Button::new("초기화"))
+          .clicked()
+      {
+        self.items.clear();
+        self.parse_errors.clear();
+      }
+    });
+    for e in &self.parse_errors {
+      ui.colored_label(egui::Color32::from_rgb(255, 0, 0), e);
+    }
+    if self.items.is_empty() {
+      return;
+    }
+
+    let contract = match contract(contract_address, rpc_url) {
+      Ok(contract) => contract,
+      Err(e) => {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+        return;
+      }
+    };
+    let sender = signer.address();
+
+    let sent = self
+      .items
+      .iter()
+      .filter(|item| matches!(item.status, ItemStatus::Sent(_)))
+      .count();
+    let pending = self
+      .items
+      .iter()
+      .filter(|item| matches!(item.status, ItemStatus::Pending { .. }))
+      .count();
+    ui.label(format!(
+      "전송 {} / {} (채굴 대기 {})",
+      sent,
+      self.items.len(),
+      pending
+    ));
+    let failed_checks = self
+      .items
+      .iter()
+      .filter(|item| item.unsent() && matches!(item.check, Some(Err(_))))
+      .count();
+    if failed_checks > 0 {
+      ui.label(format!(
+        "드라이런에 실패한 {}건은 보내지 않습니다",
+        failed_checks
+      ));
+    }
+
+    ui.horizontal(|ui| {
+      if ui
+        .add_enabled(
+          sender.is_some() && !running && !sending,
+          egui::Button::new("드라이런"),
+        )
+        .clicked()
+      {
+        self.start_dry_run(contract.clone(), sender.unwrap());
+      }
+      if ui
+        .add_enabled(
+          sender.is_some() && !running && !sending,
+          egui::Button::new("남은 항목 보내기"),
+        )
+        .clicked()
+      {
+        self.send(&contract, rpc_url, sender.unwrap(), signer);
+      }
+      if ui.button("결과 보고서").clicked() {
+        if let Err(e) = crate::download::save(
+          &format!("{}_airdrop.csv", contract_name),
+          "text/csv",
+          self.report().as_bytes(),
+        ) {
+          self.error = Some(e);
+        }
+      }
+    });
+    if sender.is_none() {
+      ui.label("서명 지갑을 먼저 불러오세요");
+    }
+
+    self.dry_run_ui(ui);
+    self.items_ui(ui);
+
+    if let Some(e) = &self.error {
+      ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+  }
+
+  /// Parses the CSV into items, replacing the current list. The first
+  /// non-empty line is skipped if [`is_header`].
+  pub fn load(&mut self) {
+    self.items.clear();
+    self.parse_errors.clear();
+
+    let mut next_id = match U256::from_dec_str(self.first_token_id.trim()) {
+      Ok(id) => id,
+      Err(_) if self.mode == AirdropMode::Mint => {
+        self.parse_errors.push("잘못된 시작 토큰 ID".to_string());
+        return;
+      }
+      Err(_) => U256::zero(),
+    };
+    let mut seen = HashSet::new();
+    let mut lines = self
+      .csv
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .peekable();
+    lines.next_if(|(_, line)| is_header(line));
+
+    for (i, line) in lines {
+      let line_no = i + 1;
+      let mut fields = line.split(',').map(str::trim);
+      let (Some(recipient), Some(value)) = (fields.next(), fields.next())
+      else {
+        self
+          .parse_errors
+          .push(format!("{}번째 줄: 열이 부족합니다", line_no));
+        continue;
+      };
+      let Ok(recipient) = recipient.parse::<Address>() else {
+        self
+          .parse_errors
+          .push(format!("{}번째 줄: 잘못된 주소 {}", line_no, recipient));
+        continue;
+      };
+      let Ok(value) = U256::from_dec_str(value) else {
+        self
+          .parse_errors
+          .push(format!("{}번째 줄: 잘못된 숫자 {}", line_no, value));
+        continue;
+      };
+
+      let token_ids = match self.mode {
+        AirdropMode::Transfer => vec![value],
+        AirdropMode::Mint if value > U256::from(MAX_MINT_PER_ROW) => {
+          self.parse_errors.push(format!(
+            "{}번째 줄: 한 줄에 최대 {}개까지 발행할 수 있습니다",
+            line_no, MAX_MINT_PER_ROW
+          ));
+          continue;
+        }
+        AirdropMode::Mint => {
+          let Some(end) = next_id.checked_add(value) else {
+            self.parse_errors.push(format!(
+              "{}번째 줄: 토큰 ID가 uint256 범위를 넘습니다",
+              line_no
+            ));
+            continue;
+          };
+          // Below `end`, so none of these overflow.
+          let ids = (0..value.as_usize()).map(|n| next_id + n).collect();
+          next_id = end;
+          ids
+        }
+      };
+      for token_id in token_ids {
+        if !seen.insert(token_id) {
+          self.parse_errors.push(format!(
+            "{}번째 줄: 토큰 {}이 중복됩니다",
+            line_no, token_id
+          ));
+          continue;
+        }
+        self.items.push(Item {
+          line: line_no,
+          recipient,
+          token_id,
+          status: ItemStatus::Queued,
+          check: None,
+          outcome: None,
+          poll: None,
+          next_poll: 0.0,
+          nonce: None,
+          unseen_polls: 0,
+        });
+      }
+    }
+  }
+
+  /// What [`Airdrop::load`] could not parse, one message per line.
+  pub fn parse_errors(&self) -> &[String] {
+    &self.parse_errors
+  }
+
+  /// Checks `ownerOf`, approvals and an `eth_call` of every unsent item
+  /// without sending anything.
+  fn start_dry_run(&mut self, contract: IERC721<RpcProvider>, sender: Address) {
+    let mode = self.mode;
+    let targets = self
+      .items
+      .iter()
+      .map(|item| item.unsent().then_some((item.recipient, item.token_id)))
+      .collect::<Vec<_>>();
+    let progress = self.progress.clone();
+    *progress.lock().unwrap() = 0;
+
+    self.dry_run = Some(Promise::spawn_local(async move {
+      let mut results = Vec::with_capacity(targets.len());
+      for target in targets {
+        let result = match target {
+          None => Ok(None),
+          Some((to, token_id)) => match mode {
+            AirdropMode::Transfer => {
+              check_transfer(&contract, sender, to, token_id)
+                .await
+                .map(Some)
+            }
+            AirdropMode::Mint => check_mint(&contract, sender, to, token_id)
+              .await
+              .map(|_| None),
+          },
+        };
+        results.push(result);
+        *progress.lock().unwrap() += 1;
+      }
+      results
+    }));
+  }
+
+  fn dry_run_ui(&mut self, ui: &mut egui::Ui) {
+    let Some(dry_run) = &self.dry_run else {
+      return;
+    };
+    match dry_run.ready() {
+      None => {
+        ui.horizontal(|ui| {
+          ui.spinner();
+          ui.label(format!(
+            "드라이런 {} / {}",
+            self.progress.lock().unwrap(),
+            self.items.len()
+          ));
+        });
+      }
+      Some(results) => {
+        for (item, result) in self.items.iter_mut().zip(results) {
+          item.check = Some(result.clone());
+        }
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        self.error =
+          (failed > 0).then(|| format!("드라이런 실패 {}건", failed));
+        self.dry_run = None;
+      }
+    }
+  }
+
+  /// Queues every item that is not sent yet (or failed) with the signer,
+  /// except those the last dry run found would fail.
+  fn send(
+    &mut self,
+    contract: &IERC721<RpcProvider>,
+    rpc_url: &str,
+    sender: Address,
+    signer: &mut SignerWindow,
+  ) {
+    for item in &mut self.items {
+      if !item.unsent() || matches!(item.check, Some(Err(_))) {
+        continue;
+      }
+
+      let (label, call) = match self.mode {
+        AirdropMode::Transfer => {
+          let from = match &item.check {
+            Some(Ok(Some(owner))) => *owner,
+            _ => sender,
+          };
+          (
+            format!(
+              "safeTransferFrom({:#x}, {:#x}, {})",
+              from, item.recipient, item.token_id
+            ),
+            contract.safe_transfer_from(from, item.recipient, item.token_id),
+          )
+        }
+        AirdropMode::Mint => (
+          format!("mint({:#x}, {})", item.recipient, item.token_id),
+          contract.mint(item.recipient, item.token_id),
+        ),
+      };
+
+      let outcome: Outcome = Arc::new(Mutex::new(None));
+      item.status = ItemStatus::Queued;
+      item.outcome = Some((outcome.clone(), sender));
+      signer.request(TxRequest {
+        label,
+        rpc_url: rpc_url.to_string(),
+        to: contract.address(),
+        data: call.calldata().unwrap_or_default(),
+        value: U256::zero(),
+        outcome: Some(outcome),
+      });
+    }
+  }
+
+  /// Copies finished signer outcomes into the persisted statuses; a
+  /// broadcast item waits for its receipt in [`Airdrop::poll_receipts`].
+  fn sync_outcomes(&mut self) {
+    for item in &mut self.items {
+      let Some((outcome, from)) = &item.outcome else {
+        continue;
+      };
+      let result = outcome.lock().unwrap().clone();
+      if let Some(result) = result {
+        item.status = match result {
+          Ok(hash) => ItemStatus::Pending { hash, from: *from },
+          Err(e) => ItemStatus::Failed(e),
+        };
+        item.outcome = None;
+      }
+    }
+  }
+
+  /// Polls the receipts of broadcast items the way the transaction history
+  /// does; only a mined status 1 marks an item sent.
+  fn poll_receipts(&mut self, ctx: &egui::Context, rpc_url: &str) {
+    let now = ctx.input(|i| i.time);
+    let mut running =
+      self.items.iter().filter(|item| item.poll.is_some()).count();
+    let mut waiting = false;
+
+    for item in &mut self.items {
+      let ItemStatus::Pending { hash, from } = item.status else {
+        continue;
+      };
+      waiting = true;
+
+      match item.poll.as_ref().map(Promise::ready) {
+        None if now >= item.next_poll && running < MAX_RECEIPT_POLLS => {
+          let rpc_url = rpc_url.to_string();
+          let nonce = item.nonce;
+          item.poll = Some(Promise::spawn_local(async move {
+            tx_history::fetch_outcome(&rpc_url, hash, from, nonce).await
+          }));
+          running += 1;
+        }
+        Some(Some(_)) => {
+          match item.poll.take().and_then(|p| p.try_take().ok()) {
+            Some(Ok(tx_history::Outcome::Mined { receipt, revert })) => {
+              item.status = if receipt.status.map(|s| s.as_u64()) == Some(1) {
+                ItemStatus::Sent(hash)
+              } else {
+                ItemStatus::Failed(match revert {
+                  Some(data) => tx_history::decode_revert(&data, &[]),
+                  None => "트랜잭션 실패".to_string(),
+                })
+              };
+            }
+            Some(Ok(tx_history::Outcome::Waiting { nonce: Some(nonce) })) => {
+              item.nonce = Some(nonce);
+              item.unseen_polls = 0;
+            }
+            Some(Ok(tx_history::Outcome::Waiting { nonce: None })) => {
+              item.unseen_polls += 1;
+              if item.unseen_polls >= MAX_UNSEEN_POLLS {
+                item.status = ItemStatus::Failed(
+                  "노드가 트랜잭션을 찾지 못합니다 (멤풀에서 제외됨)"
+                    .to_string(),
+                );
+              }
+            }
+            Some(Ok(tx_history::Outcome::Replaced)) => {
+              item.status = ItemStatus::Failed(
+                "같은 nonce의 다른 트랜잭션이 먼저 채굴되었습니다".to_string(),
+              );
+            }
+            // An RPC error; the next poll tries again.
+            Some(Err(_)) | None => {}
+          }
+          item.next_poll = now + POLL_INTERVAL;
+          running -= 1;
+        }
+        _ => {}
+      }
+    }
+
+    if waiting {
+      ctx.request_repaint_after(std::time::Duration::from_secs_f64(
+        POLL_INTERVAL,
+      ));
+    }
+  }
+
+  fn items_ui(&self, ui: &mut egui::Ui) {
+    egui::Grid::new("airdrop_items")
+      .striped(true)
+      .show(ui, |ui| {
+        ui.label("줄");
+        ui.label("받는 주소");
+        ui.label("토큰 ID");
+        ui.label("드라이런");
+        ui.label("상태");
+        ui.end_row();
+
+        for item in &self.items {
+          ui.label(item.line.to_string());
+          ui.monospace(format!("{:#x}", item.recipient));
+          ui.label(item.token_id.to_string());
+          match &item.check {
+            None => ui.label(""),
+            Some(Ok(_)) => ui.label("✔"),
+            Some(Err(e)) => {
+              ui.colored_label(egui::Color32::from_rgb(255, 0, 0), e)
+            }
+          };
+          match (&item.status, item.in_flight()) {
+            (_, true) => ui.label("확인 대기"),
+            (ItemStatus::Queued, false) => ui.label(""),
+            (ItemStatus::Pending { hash, .. }, _) => {
+              ui.monospace(format!("⏳ {:#x}", hash))
+            }
+            (ItemStatus::Sent(hash), _) => ui.monospace(format!("{:#x}", hash)),
+            (ItemStatus::Failed(e), _) => {
+              ui.colored_label(ui.visuals().error_fg_color, e)
+            }
+          };
+          ui.end_row();
+        }
+      });
+  }
+
+  /// `line,recipient,token_id,dry_run,status,detail`, one row per item.
+  fn report(&self) -> String {
+    let mut csv = "line,recipient,token_id,dry_run,status,detail\n".to_string();
+    for item in &self.items {
+      let dry_run = match &item.check {
+        None => "".to_string(),
+        Some(Ok(_)) => "ok".to_string(),
+        Some(Err(e)) => e.replace(',', ";"),
+      };
+      let (status, detail) = match &item.status {
+        ItemStatus::Queued => ("queued", "".to_string()),
+        ItemStatus::Pending { hash, .. } => ("pending", format!("{:#x}", hash)),
+        ItemStatus::Sent(hash) => ("sent", format!("{:#x}", hash)),
+        ItemStatus::Failed(e) => ("failed", e.replace(',', ";")),
+      };
+      csv.push_str(&format!(
+        "{},{:#x},{},{},{},{}\n",
+        item.line, item.recipient, item.token_id, dry_run, status, detail
+      ));
+    }
+    csv
+  }
+}
+
+impl Item {
+  /// Queued with the signer and not sent or cancelled yet.
+  fn in_flight(&self) -> bool {
+    self.outcome.is_some()
+  }
+
+  /// Neither mined nor broadcast and waiting to be.
+  fn unsent(&self) -> bool {
+    matches!(self.status, ItemStatus::Queued | ItemStatus::Failed(_))
+  }
+}
+
+fn contract(
+  address: &str,
+  rpc_url: &str,
+) -> Result<IERC721<RpcProvider>, String> {
+  let address = address
+    .trim()
+    .parse::<Address>()
+    .map_err(|_| format!("잘못된 주소: {}", address))?;
+  let provider = rpc::provider(rpc_url)?;
+
+  Ok(IERC721::new(address, provider))
+}
+
+/// The owner `sender` can move `token_id` from, after simulating the call.
+async fn check_transfer(
+  contract: &IERC721<RpcProvider>,
+  sender: Address,
+  to: Address,
+  token_id: U256,
+) -> Result<Address, String> {
+  let owner = contract
+    .owner_of(token_id)
+    .call()
+    .await
+    .map_err(|e| format!("ownerOf 실패: {}", reason(e)))?;
+
+  if owner != sender {
+    let approved = contract.get_approved(token_id).call().await.ok()
+      == Some(sender)
+      || contract
+        .is_approved_for_all(owner, sender)
+        .call()
+        .await
+        .unwrap_or(false);
+    if !approved {
+      return Err(format!("소유자 {:#x}, 권한 없음", owner));
+    }
+  }
+
+  contract
+    .safe_transfer_from(owner, to, token_id)
+    .from(sender)
+    .call()
+    .await
+    .map_err(|e| format!("시뮬레이션 실패: {}", reason(e)))?;
+  Ok(owner)
+}
+
+async fn check_mint(
+  contract: &IERC721<RpcProvider>,
+  sender: Address,
+  to: Address,
+  token_id: U256,
+) -> Result<(), String> {
+  if let Ok(owner) = contract.owner_of(token_id).call().await {
+    return Err(format!("이미 발행됨 ({:#x})", owner));
+  }
+
+  contract
+    .mint(to, token_id)
+    .from(sender)
+    .call()
+    .await
+    .map_err(|e| format!("시뮬레이션 실패: {}", reason(e)))
+}
+
+fn reason(e: ContractError<RpcProvider>) -> String {
+  e.decode_revert::<String>().unwrap_or_else(|| e.to_string())
+}
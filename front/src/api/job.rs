@@ -0,0 +1,126 @@
+use poll_promise::Promise;
+use shared::job::{Job, JobRequest, JobStatus};
+
+use super::{ApiClient, ApiError};
+
+/// Seconds between `GET /jobs/{id}` while a job is queued or running.
+const POLL_INTERVAL: f64 = 1.0;
+
+/// Submits one backend job and polls it until it is done or failed.
+pub struct JobWatcher {
+  client: ApiClient,
+  job: Option<Job>,
+  pending: Option<Promise<Result<Job, ApiError>>>,
+  error: Option<ApiError>,
+  next_poll: f64,
+}
+
+impl JobWatcher {
+  pub fn submit(client: ApiClient, request: JobRequest) -> Self {
+    let pending = {
+      let client = client.clone();
+      Promise::spawn_local(async move { client.create_job(&request).await })
+    };
+
+    Self {
+      client,
+      job: None,
+      pending: Some(pending),
+      error: None,
+      next_poll: 0.0,
+    }
+  }
+
+  pub fn is_active(&self) -> bool {
+    self.error.is_none()
+      && !self
+        .job
+        .as_ref()
+        .is_some_and(|job| job.status.is_finished())
+  }
+
+  /// Collects a finished request and starts the next poll when due. Call
+  /// once per frame.
+  pub fn update(&mut self, ctx: &egui::Context) {
+    let now = ctx.input(|i| i.time);
+
+    if let Some(pending) = self.pending.take() {
+      match pending.try_take() {
+        Ok(Ok(job)) => {
+          self.job = Some(job);
+          self.next_poll = now + POLL_INTERVAL;
+        }
+        Ok(Err(e)) => self.error = Some(e),
+        Err(pending) => self.pending = Some(pending),
+      }
+    }
+
+    if !self.is_active() {
+      return;
+    }
+
+    if self.pending.is_none() && now >= self.next_poll {
+      if let Some(id) = self.job.as_ref().map(|job| job.id) {
+        let client = self.client.clone();
+        self.pending =
+          Some(Promise::spawn_local(
+            async move { client.get_job(id).await },
+          ));
+      }
+    }
+
+    ctx
+      .request_repaint_after(std::time::Duration::from_secs_f64(POLL_INTERVAL));
+  }
+
+  pub fn ui(&self, ui: &mut egui::Ui) {
+    if let Some(e) = &self.error {
+      ui.colored_label(ui.visuals().error_fg_color, e.to_string());
+      return;
+    }
+
+    let Some(job) = &self.job else {
+      ui.horizontal(|ui| {
+        ui.spinner();
+        ui.label("작업 요청 중...");
+      });
+      return;
+    };
+
+    match job.status {
+      JobStatus::Queued => {
+        ui.horizontal(|ui| {
+          ui.spinner();
+          ui.label(format!("작업 #{} 대기 중", job.id));
+        });
+      }
+      JobStatus::Running => {
+        let fraction = if job.total == 0 {
+          0.0
+        } else {
+          job.progress as f32 / job.total as f32
+        };
+        ui.add(
+          egui::ProgressBar::new(fraction)
+            .text(format!("{} / {}", job.progress, job.total)),
+        );
+      }
+      JobStatus::Done => {
+        ui.hyperlink_to(
+          format!("작업 #{} 결과 다운로드", job.id),
+          self.client.job_result_url(job.id),
+        );
+      }
+      JobStatus::Failed => {
+        ui.colored_label(
+          ui.visuals().error_fg_color,
+          format!(
+            "작업 #{} 실패: {}",
+            job.id,
+            job.error.as_deref().unwrap_or_default()
+          ),
+        );
+      }
+    }
+  }
+}
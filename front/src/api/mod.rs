@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Serialize};
+use shared::collection::{Collection, NewCollection};
+use shared::job::{Job, JobRequest};
+
+mod job;
+
+pub use job::JobWatcher;
+
+/// Tries per request while the server answers `429 Too Many Requests`.
+const RATE_LIMIT_ATTEMPTS: u32 = 6;
+
+/// Why a backend call failed, in a form that can be shown in a window.
+#[derive(Clone, Debug)]
+pub enum ApiError {
+  /// The request never got a response (bad URL, server down, CORS).
+  Network(String),
+  /// The server answered with an error status and its `error.msg`.
+  Server { status: u16, msg: String },
+  /// The response body was not what the client expected.
+  Decode(String),
+}
+
+impl fmt::Display for ApiError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ApiError::Network(e) => write!(f, "서버에 연결할 수 없습니다: {}", e),
+      ApiError::Server { status, msg } => {
+        write!(f, "서버 오류 {}: {}", status, msg)
+      }
+      ApiError::Decode(e) => write!(f, "응답을 읽을 수 없습니다: {}", e),
+    }
+  }
+}
+
+/// Typed client for the `back` REST API.
+#[derive(Clone, Debug)]
+pub struct ApiClient {
+  base_url: String,
+  api_key: String,
+}
+
+impl ApiClient {
+  pub fn new(base_url: &str, api_key: &str) -> Self {
+    Self {
+      base_url: base_url.trim().trim_end_matches('/').to_string(),
+      api_key: api_key.trim().to_string(),
+    }
+  }
+
+  pub async fn get_collection(
+    &self,
+    slug: &str,
+  ) -> Result<Collection, ApiError> {
+    let request = self.request("GET", &format!("/collections/{}", slug), None);
+
+    decode(self.send(request).await?)
+  }
+
+  pub async fn create_collection(
+    &self,
+    new: &NewCollection,
+  ) -> Result<Collection, ApiError> {
+    let request = self.request("POST", "/collections", Some(encode(new)?));
+
+    decode(self.send(request).await?)
+  }
+
+  pub async fn put_token_metadata<M: Serialize>(
+    &self,
+    slug: &str,
+    token_id: u64,
+    metadata: &M,
+  ) -> Result<(), ApiError> {
+    let request = self.request(
+      "PUT",
+      &format!("/collections/{}/tokens/{}", slug, token_id),
+      Some(encode(metadata)?),
+    );
+    self.send(request).await?;
+
+    Ok(())
+  }
+
+  pub async fn create_job(
+    &self,
+    request: &JobRequest,
+  ) -> Result<Job, ApiError> {
+    let request = self.request("POST", "/jobs", Some(encode(request)?));
+
+    decode(self.send(request).await?)
+  }
+
+  pub async fn get_job(&self, id: u64) -> Result<Job, ApiError> {
+    let request = self.request("GET", &format!("/jobs/{}", id), None);
+
+    decode(self.send(request).await?)
+  }
+
+  /// Download link for a finished job's archive.
+  pub fn job_result_url(&self, id: u64) -> String {
+    format!("{}/jobs/{}/result", self.base_url, id)
+  }
+
+  fn request(
+    &self,
+    method: &str,
+    path: &str,
+    body: Option<Vec<u8>>,
+  ) -> ehttp::Request {
+    let mut headers = BTreeMap::new();
+    headers.insert("Accept".to_string(), "application/json".to_string());
+    if body.is_some() {
+      headers
+        .insert("Content-Type".to_string(), "application/json".to_string());
+    }
+    if !self.api_key.is_empty() {
+      headers.insert("X-Api-Key".to_string(), self.api_key.clone());
+    }
+
+    ehttp::Request {
+      method: method.to_string(),
+      url: format!("{}{}", self.base_url, path),
+      body: body.unwrap_or_default(),
+      headers,
+    }
+  }
+
+  /// Sends `request`, waiting and retrying while the server rate limits it.
+  async fn send(
+    &self,
+    request: ehttp::Request,
+  ) -> Result<ehttp::Response, ApiError> {
+    let mut attempt = 1;
+
+    loop {
+      let response = ehttp::fetch_async(request.clone())
+        .await
+        .map_err(ApiError::Network)?;
+
+      if response.ok {
+        return Ok(response);
+      }
+      if response.status != 429 || attempt >= RATE_LIMIT_ATTEMPTS {
+        return Err(server_error(&response));
+      }
+
+      futures_timer::Delay::new(retry_after(&response, attempt)).await;
+      attempt += 1;
+    }
+  }
+}
+
+/// As long as `Retry-After` asks, or 1s, 2s, 4s.. when it is missing.
+fn retry_after(response: &ehttp::Response, attempt: u32) -> Duration {
+  response
+    .headers
+    .iter()
+    .find(|(name, _)| name.eq_ignore_ascii_case("retry-after"))
+    .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+    .map(Duration::from_secs)
+    .unwrap_or_else(|| Duration::from_secs(1 << (attempt - 1).min(5)))
+}
+
+fn encode<T: Serialize>(body: &T) -> Result<Vec<u8>, ApiError> {
+  serde_json::to_vec(body).map_err(|e| ApiError::Decode(e.to_string()))
+}
+
+fn decode<T: DeserializeOwned>(
+  response: ehttp::Response,
+) -> Result<T, ApiError> {
+  serde_json::from_slice(&response.bytes)
+    .map_err(|e| ApiError::Decode(e.to_string()))
+}
+
+/// Errors come back as `{"error": {"msg": ..}}`, with the offending field in
+/// `input` for validation failures.
+fn server_error(response: &ehttp::Response) -> ApiError {
+  let body: serde_json::Value =
+    serde_json::from_slice(&response.bytes).unwrap_or_default();
+  let error = &body["error"];
+
+  let msg = match (error["msg"].as_str(), error["input"].as_str()) {
+    (Some(msg), Some(input)) => format!("{} ({})", msg, input),
+    (Some(msg), None) => msg.to_string(),
+    _ => response.status_text.clone(),
+  };
+
+  ApiError::Server {
+    status: response.status,
+    msg,
+  }
+}
@@ -1,11 +1,16 @@
 use crate::chain_settings::ChainSettingsWindow;
+use crate::contract_abi::AbiContractWindow;
 use crate::contract_erc721::ERC721ContractWindow;
+use crate::disperse::DisperseWindow;
 // use crate::contract_erc721::ERC721ContractWindow;
 use crate::metadata::SingleMetadataWindow;
+use crate::server_settings::ServerSettingsWindow;
+use crate::signer::SignerWindow;
+use crate::tx_history::TxHistoryWindow;
 use crate::wallet_balance::WalletBalanceWindow;
 use crate::wallet_settings::WalletSettingsWindow;
 
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Default, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct ToyApp {
   metadata: SingleMetadataWindow,
@@ -13,28 +18,17 @@ pub struct ToyApp {
 
   wallet_settings: WalletSettingsWindow,
   chain_settings: ChainSettingsWindow,
+  server_settings: ServerSettingsWindow,
+  signer: SignerWindow,
+  tx_history: TxHistoryWindow,
 
   erc20_contract: ERC721ContractWindow,
+  abi_contract: AbiContractWindow,
+  disperse: DisperseWindow,
 
   settings: bool,
 }
 
-impl Default for ToyApp {
-  fn default() -> Self {
-    Self {
-      metadata: SingleMetadataWindow::default(),
-      wallet_balance: WalletBalanceWindow::default(),
-
-      wallet_settings: WalletSettingsWindow::default(),
-      chain_settings: ChainSettingsWindow::default(),
-
-      erc20_contract: ERC721ContractWindow::default(),
-
-      settings: false,
-    }
-  }
-}
-
 impl ToyApp {
   /// Called once before the first frame.
   pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
@@ -80,6 +74,13 @@ impl eframe::App for ToyApp {
         }
 
         egui::widgets::global_dark_light_mode_buttons(ui);
+
+        ui.with_layout(
+          egui::Layout::right_to_left(egui::Align::Center),
+          |ui| {
+            self.signer.status_ui(ui);
+          },
+        );
       });
     });
 
@@ -100,14 +101,19 @@ impl eframe::App for ToyApp {
           ui.collapsing("지갑", |ui| {
             self.wallet_balance.show(ui);
             self.wallet_settings.show(ui);
+            self.signer.show(ui);
+            self.tx_history.show(ui);
           });
 
           ui.collapsing("컨트랙트 콜", |ui| {
             self.erc20_contract.show(ui);
+            self.abi_contract.show(ui);
+            self.disperse.show(ui);
             // self.erc721_contract.show(ui);
           });
 
           self.chain_settings.show(ui);
+          self.server_settings.show(ui);
         });
 
         ui.separator();
@@ -121,18 +127,35 @@ impl eframe::App for ToyApp {
       });
     });
 
-    self.metadata.update(ctx, _frame);
+    self.metadata.update(ctx, _frame, &self.server_settings);
     self.wallet_settings.update(ctx, _frame);
     self.chain_settings.update(ctx, _frame);
+    self.server_settings.update(ctx, _frame);
     self.wallet_balance.update(
       ctx,
       _frame,
       &self.chain_settings,
       &self.wallet_settings,
     );
+    self.erc20_contract.update(
+      ctx,
+      _frame,
+      &mut self.chain_settings,
+      &mut self.signer,
+    );
+    self.abi_contract.update(
+      ctx,
+      _frame,
+      &self.chain_settings,
+      &mut self.signer,
+    );
+    self
+      .disperse
+      .update(ctx, _frame, &self.chain_settings, &mut self.signer);
     self
-      .erc20_contract
-      .update(ctx, _frame, &mut self.chain_settings);
+      .signer
+      .update(ctx, _frame, &self.chain_settings, &mut self.tx_history);
+    self.tx_history.update(ctx, _frame, &self.abi_contract);
     egui::Window::new("🔧 Settings")
       .open(&mut self.settings)
       .vscroll(true)
@@ -1,3 +1,129 @@
+use std::collections::HashMap;
+
+use ethers::providers::{Http, Middleware, Provider};
+use poll_promise::Promise;
+
+use crate::rpc;
+
+/// Seconds between health probes of every configured RPC endpoint.
+const PROBE_INTERVAL: f64 = 30.0;
+
+/// A well-known chain the add form can be filled from.
+pub struct ChainPreset {
+  pub chain_id: u64,
+  pub name: &'static str,
+  pub native_symbol: &'static str,
+  pub decimals: u8,
+  pub explorer_url: &'static str,
+  /// A public RPC endpoint; rate limited, so swap in your own for real use.
+  pub rpc_url: &'static str,
+}
+
+pub const CHAIN_PRESETS: [ChainPreset; 12] = [
+  ChainPreset {
+    chain_id: 1,
+    name: "Ethereum",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://etherscan.io",
+    rpc_url: "https://ethereum-rpc.publicnode.com",
+  },
+  ChainPreset {
+    chain_id: 11155111,
+    name: "Sepolia",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://sepolia.etherscan.io",
+    rpc_url: "https://ethereum-sepolia-rpc.publicnode.com",
+  },
+  ChainPreset {
+    chain_id: 17000,
+    name: "Holesky",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://holesky.etherscan.io",
+    rpc_url: "https://ethereum-holesky-rpc.publicnode.com",
+  },
+  ChainPreset {
+    chain_id: 137,
+    name: "Polygon",
+    native_symbol: "POL",
+    decimals: 18,
+    explorer_url: "https://polygonscan.com",
+    rpc_url: "https://polygon-rpc.com",
+  },
+  ChainPreset {
+    chain_id: 80002,
+    name: "Polygon Amoy",
+    native_symbol: "POL",
+    decimals: 18,
+    explorer_url: "https://amoy.polygonscan.com",
+    rpc_url: "https://rpc-amoy.polygon.technology",
+  },
+  ChainPreset {
+    chain_id: 42161,
+    name: "Arbitrum One",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://arbiscan.io",
+    rpc_url: "https://arb1.arbitrum.io/rpc",
+  },
+  ChainPreset {
+    chain_id: 10,
+    name: "Optimism",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://optimistic.etherscan.io",
+    rpc_url: "https://mainnet.optimism.io",
+  },
+  ChainPreset {
+    chain_id: 8453,
+    name: "Base",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://basescan.org",
+    rpc_url: "https://mainnet.base.org",
+  },
+  ChainPreset {
+    chain_id: 84532,
+    name: "Base Sepolia",
+    native_symbol: "ETH",
+    decimals: 18,
+    explorer_url: "https://sepolia.basescan.org",
+    rpc_url: "https://sepolia.base.org",
+  },
+  ChainPreset {
+    chain_id: 56,
+    name: "BNB Smart Chain",
+    native_symbol: "BNB",
+    decimals: 18,
+    explorer_url: "https://bscscan.com",
+    rpc_url: "https://bsc-dataseed.bnbchain.org",
+  },
+  ChainPreset {
+    chain_id: 43114,
+    name: "Avalanche C-Chain",
+    native_symbol: "AVAX",
+    decimals: 18,
+    explorer_url: "https://snowtrace.io",
+    rpc_url: "https://api.avax.network/ext/bc/C/rpc",
+  },
+  ChainPreset {
+    chain_id: 100,
+    name: "Gnosis",
+    native_symbol: "xDAI",
+    decimals: 18,
+    explorer_url: "https://gnosisscan.io",
+    rpc_url: "https://rpc.gnosischain.com",
+  },
+];
+
+pub fn preset(chain_id: u64) -> Option<&'static ChainPreset> {
+  CHAIN_PRESETS
+    .iter()
+    .find(|preset| preset.chain_id == chain_id)
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct ChainSettingsWindow {
@@ -5,6 +131,27 @@ pub struct ChainSettingsWindow {
 
   pub chain_settings: Vec<ChainSettings>,
   pub add_chain_settings: ChainSettings,
+  /// Expected chain ID typed into the add form; empty trusts the RPC.
+  pub add_chain_id: String,
+  /// Backup RPC URLs for the add form, one per line.
+  pub add_backup_rpc_urls: String,
+  pub rpc_config: rpc::RpcConfig,
+
+  /// `eth_chainId` requests in flight (or failed), by RPC URL.
+  #[serde(skip)]
+  checks: HashMap<String, Promise<Result<u64, String>>>,
+  /// Whether the chains restored with the app state were checked yet.
+  #[serde(skip)]
+  checked_saved: bool,
+  #[serde(skip)]
+  add_error: Option<String>,
+  /// "백업 추가" input of each listed chain.
+  #[serde(skip)]
+  backup_inputs: Vec<String>,
+  #[serde(skip)]
+  probes: Vec<Promise<()>>,
+  #[serde(skip)]
+  next_probe: f64,
 }
 
 impl Default for ChainSettingsWindow {
@@ -14,6 +161,16 @@ impl Default for ChainSettingsWindow {
 
       chain_settings: vec![],
       add_chain_settings: ChainSettings::default(),
+      add_chain_id: "".to_string(),
+      add_backup_rpc_urls: "".to_string(),
+      rpc_config: rpc::RpcConfig::default(),
+
+      checks: HashMap::new(),
+      checked_saved: false,
+      add_error: None,
+      backup_inputs: vec![],
+      probes: vec![],
+      next_probe: 0.0,
     }
   }
 }
@@ -23,53 +180,251 @@ impl ChainSettingsWindow {
     ui.checkbox(&mut self.is_open, "체인 설정");
   }
 
+  /// The chain saved under `chain_name`, as currently configured.
+  pub fn chain(&self, chain_name: &str) -> Option<&ChainSettings> {
+    self
+      .chain_settings
+      .iter()
+      .find(|chain| chain.chain_name == chain_name)
+  }
+
+  /// The chain `rpc_url` is the primary or a backup RPC of.
+  pub fn chain_by_rpc_url(&self, rpc_url: &str) -> Option<&ChainSettings> {
+    self
+      .chain_settings
+      .iter()
+      .find(|chain| chain.rpc_urls().iter().any(|url| url == rpc_url))
+  }
+
   pub fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    if !self.checked_saved {
+      // A saved chain may have been pointed at another network since.
+      for chain in &self.chain_settings {
+        if !self.checks.contains_key(&chain.rpc_url) {
+          Self::check(&mut self.checks, &chain.rpc_url);
+        }
+      }
+      self.checked_saved = true;
+    }
+    self.apply_checks();
+    self.probe(ctx);
+
+    // The form is saved after the window releases `is_open`.
+    let mut added = None;
     egui::Window::new("체인 설정")
       .open(&mut self.is_open)
       .vscroll(true)
       .show(ctx, |ui| {
         ui.collapsing("체인 설정", |ui| {
+          egui::ComboBox::from_label("프리셋")
+            .selected_text("선택")
+            .show_ui(ui, |ui| {
+              for preset in &CHAIN_PRESETS {
+                let label = format!("{} ({})", preset.name, preset.chain_id);
+                if ui.selectable_label(false, label).clicked() {
+                  self.add_chain_settings = ChainSettings::from(preset);
+                  self.add_chain_id = preset.chain_id.to_string();
+                }
+              }
+            });
+
           ui.horizontal(|ui| {
             ui.label("체인 이름");
             ui.text_edit_singleline(&mut self.add_chain_settings.chain_name);
             ui.label("체인 RPC URL");
             ui.text_edit_singleline(&mut self.add_chain_settings.rpc_url);
           });
+          ui.horizontal(|ui| {
+            ui.label("체인 ID");
+            let valid = self.add_chain_id.trim().is_empty()
+              || self.add_chain_id.trim().parse::<u64>().is_ok();
+            let color = if valid {
+              egui::Color32::from_rgb(140, 140, 140)
+            } else {
+              egui::Color32::from_rgb(255, 0, 0)
+            };
+            ui.add(
+              egui::TextEdit::singleline(&mut self.add_chain_id)
+                .hint_text("비우면 RPC 값 사용")
+                .text_color(color),
+            );
+            ui.label("심볼");
+            ui.text_edit_singleline(&mut self.add_chain_settings.native_symbol);
+            ui.label("익스플로러");
+            ui.text_edit_singleline(&mut self.add_chain_settings.explorer_url);
+          });
+          ui.horizontal(|ui| {
+            ui.label("백업 RPC URL");
+            ui.add(
+              egui::TextEdit::multiline(&mut self.add_backup_rpc_urls)
+                .hint_text("한 줄에 하나씩")
+                .desired_rows(2),
+            );
+          });
 
           if ui.button("체인 RPC URL 추가").clicked() {
-            let setting = ChainSettings {
-              chain_name: self.add_chain_settings.chain_name.clone(),
-              rpc_url: self.add_chain_settings.rpc_url.clone(),
-            };
-            self.chain_settings.push(setting);
-            self.add_chain_settings = ChainSettings::default();
+            match self.add_chain_id.trim() {
+              "" => added = Some(None),
+              id => match id.parse::<u64>() {
+                Ok(chain_id) => added = Some(Some(chain_id)),
+                Err(_) => {
+                  self.add_error = Some(format!("잘못된 체인 ID: {}", id))
+                }
+              },
+            }
+          }
+          if let Some(e) = &self.add_error {
+            ui.colored_label(ui.visuals().error_fg_color, e);
           }
 
-          for i in 0..self.chain_settings.len() {
-            let res = ui.horizontal(|ui| {
-              ui.colored_label(
-                egui::Color32::from_rgb(255, 255, 255),
-                format!("체인 이름: {}", self.chain_settings[i].chain_name),
-              );
-              ui.colored_label(
-                egui::Color32::from_rgb(255, 255, 255),
-                format!("체인 RPC URL: {}", self.chain_settings[i].rpc_url),
-              );
-
-              if ui.button("삭제").clicked() {
-                self.chain_settings.remove(i);
-                false
-              } else {
-                true
+          self
+            .backup_inputs
+            .resize(self.chain_settings.len(), "".to_string());
+          let mut removed = None;
+          egui::Grid::new("chain_settings_list")
+            .striped(true)
+            .show(ui, |ui| {
+              ui.label("체인 이름");
+              ui.label("체인 RPC URL (상태)");
+              ui.label("쿼럼");
+              ui.label("체인 ID");
+              ui.label("심볼");
+              ui.label("확인");
+              ui.end_row();
+
+              for (i, chain) in self.chain_settings.iter_mut().enumerate() {
+                ui.label(&chain.chain_name);
+                if chain.endpoints_ui(ui, &mut self.backup_inputs[i]) {
+                  self.next_probe = 0.0;
+                }
+                let endpoints = chain.rpc_urls().len();
+                ui.add(
+                  egui::DragValue::new(&mut chain.quorum)
+                    .clamp_range(1..=endpoints),
+                )
+                .on_hover_text("읽기 요청에 같은 응답을 줘야 하는 RPC 수");
+                ui.label(
+                  chain.chain_id.map(|id| id.to_string()).unwrap_or_default(),
+                );
+                ui.label(&chain.native_symbol);
+                match self.checks.get(&chain.rpc_url).map(Promise::ready) {
+                  Some(None) => {
+                    ui.spinner();
+                  }
+                  Some(Some(Err(e))) => {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                  }
+                  _ => chain.status_ui(ui),
+                }
+                ui.horizontal(|ui| {
+                  if ui.button("다시 확인").clicked() {
+                    Self::check(&mut self.checks, &chain.rpc_url);
+                  }
+                  if ui.button("삭제").clicked() {
+                    removed = Some(i);
+                  }
+                });
+                ui.end_row();
               }
             });
-
-            if res.inner == false {
-              break;
-            }
+          if let Some(i) = removed {
+            self.chain_settings.remove(i);
+            self.backup_inputs.remove(i);
           }
         });
+
+        ui.collapsing("RPC 요청", |ui| {
+          self.rpc_config.ui(ui);
+          ui.label("엔드포인트마다 타임아웃을 적용하고, 모두 실패하면 백오프 후 다시 돕니다");
+        });
       });
+    if let Some(chain_id) = added {
+      self.add(chain_id);
+    }
+  }
+
+  /// Saves the add form as a new chain and checks its RPC.
+  fn add(&mut self, chain_id: Option<u64>) {
+    let mut setting = self.add_chain_settings.clone();
+    setting.chain_id = chain_id;
+    setting.rpc_chain_id = None;
+    setting.backup_rpc_urls = self
+      .add_backup_rpc_urls
+      .lines()
+      .map(str::trim)
+      .filter(|url| !url.is_empty())
+      .map(str::to_string)
+      .collect();
+    Self::check(&mut self.checks, &setting.rpc_url);
+    self.chain_settings.push(setting);
+    self.next_probe = 0.0;
+
+    self.add_chain_settings = ChainSettings::default();
+    self.add_chain_id = "".to_string();
+    self.add_backup_rpc_urls = "".to_string();
+    self.add_error = None;
+  }
+
+  /// Asks `rpc_url` for its chain ID; the answer lands in every chain using
+  /// that URL via [`ChainSettingsWindow::apply_checks`].
+  fn check(
+    checks: &mut HashMap<String, Promise<Result<u64, String>>>,
+    rpc_url: &str,
+  ) {
+    let url = rpc_url.to_string();
+    checks.insert(
+      url.clone(),
+      Promise::spawn_local(async move {
+        let provider = Provider::<Http>::try_from(url.as_str())
+          .map_err(|_| format!("잘못된 RPC URL: {}", url))?;
+        let chain_id = provider
+          .get_chainid()
+          .await
+          .map_err(|e| format!("RPC 오류: {}", e))?;
+        Ok(chain_id.as_u64())
+      }),
+    );
+  }
+
+  /// Registers the chains with the shared RPC pool and, every
+  /// [`PROBE_INTERVAL`] seconds, probes each of their endpoints.
+  fn probe(&mut self, ctx: &egui::Context) {
+    rpc::register(&self.chain_settings, &self.rpc_config);
+
+    self.probes.retain(|probe| probe.ready().is_none());
+    let now = ctx.input(|i| i.time);
+    if now >= self.next_probe {
+      self.next_probe = now + PROBE_INTERVAL;
+      for chain in &self.chain_settings {
+        for url in chain.rpc_urls() {
+          self.probes.push(Promise::spawn_local(rpc::probe(url)));
+        }
+      }
+    }
+
+    let wait = if self.probes.is_empty() {
+      self.next_probe - now
+    } else {
+      0.5
+    };
+    ctx.request_repaint_after(std::time::Duration::from_secs_f64(wait));
+  }
+
+  fn apply_checks(&mut self) {
+    let mut done = vec![];
+    for (rpc_url, check) in &self.checks {
+      if let Some(Ok(chain_id)) = check.ready() {
+        for chain in &mut self.chain_settings {
+          if &chain.rpc_url == rpc_url {
+            chain.set_rpc_chain_id(*chain_id);
+          }
+        }
+        done.push(rpc_url.clone());
+      }
+    }
+    for rpc_url in done {
+      self.checks.remove(&rpc_url);
+    }
   }
 }
 
@@ -78,6 +433,17 @@ impl ChainSettingsWindow {
 pub struct ChainSettings {
   pub chain_name: String,
   pub rpc_url: String,
+  /// Tried after `rpc_url` when it fails or falls behind.
+  pub backup_rpc_urls: Vec<String>,
+  /// How many endpoints must return the same answer to a read.
+  pub quorum: usize,
+  /// The chain this entry is meant to be; `None` until known.
+  pub chain_id: Option<u64>,
+  pub native_symbol: String,
+  pub decimals: u8,
+  pub explorer_url: String,
+  /// What `eth_chainId` on `rpc_url` answered last time it was checked.
+  pub rpc_chain_id: Option<u64>,
 }
 
 impl Default for ChainSettings {
@@ -85,6 +451,170 @@ impl Default for ChainSettings {
     Self {
       chain_name: "".to_string(),
       rpc_url: "".to_string(),
+      backup_rpc_urls: vec![],
+      quorum: 1,
+      chain_id: None,
+      native_symbol: "ETH".to_string(),
+      decimals: 18,
+      explorer_url: "".to_string(),
+      rpc_chain_id: None,
+    }
+  }
+}
+
+impl From<&ChainPreset> for ChainSettings {
+  fn from(preset: &ChainPreset) -> Self {
+    Self {
+      chain_name: preset.name.to_string(),
+      rpc_url: preset.rpc_url.to_string(),
+      backup_rpc_urls: vec![],
+      quorum: 1,
+      chain_id: Some(preset.chain_id),
+      native_symbol: preset.native_symbol.to_string(),
+      decimals: preset.decimals,
+      explorer_url: preset.explorer_url.to_string(),
+      rpc_chain_id: None,
+    }
+  }
+}
+
+impl ChainSettings {
+  /// `rpc_url` followed by the backups.
+  pub fn rpc_urls(&self) -> Vec<String> {
+    std::iter::once(&self.rpc_url)
+      .chain(&self.backup_rpc_urls)
+      .cloned()
+      .collect()
+  }
+  /// `true` when the RPC answered with a different chain than expected.
+  pub fn is_mismatch(&self) -> bool {
+    matches!(
+      (self.chain_id, self.rpc_chain_id),
+      (Some(expected), Some(actual)) if expected != actual
+    )
+  }
+
+  /// Records an `eth_chainId` answer; a chain added without an ID adopts
+  /// it, along with the preset's symbol and explorer when they are unset.
+  pub fn set_rpc_chain_id(&mut self, chain_id: u64) {
+    self.rpc_chain_id = Some(chain_id);
+    if self.chain_id.is_some() {
+      return;
+    }
+
+    self.chain_id = Some(chain_id);
+    if let Some(preset) = preset(chain_id) {
+      if self.explorer_url.is_empty() {
+        self.explorer_url = preset.explorer_url.to_string();
+      }
+      if self.native_symbol.is_empty() || self.native_symbol == "ETH" {
+        self.native_symbol = preset.native_symbol.to_string();
+      }
+    }
+  }
+
+  /// Lists the endpoints with their health, and lets backups be added and
+  /// removed. Returns `true` when the list changed.
+  fn endpoints_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    backup_input: &mut String,
+  ) -> bool {
+    let tip = self
+      .rpc_urls()
+      .iter()
+      .filter_map(|url| rpc::health(url)?.block_number)
+      .max()
+      .unwrap_or_default();
+
+    let mut changed = false;
+    ui.vertical(|ui| {
+      ui.horizontal(|ui| {
+        ui.label(&self.rpc_url);
+        self.health_ui(ui, &self.rpc_url, tip);
+      });
+
+      let mut removed = None;
+      for (i, url) in self.backup_rpc_urls.iter().enumerate() {
+        ui.horizontal(|ui| {
+          ui.label(url);
+          self.health_ui(ui, url, tip);
+          if ui.small_button("✕").clicked() {
+            removed = Some(i);
+          }
+        });
+      }
+      if let Some(i) = removed {
+        self.backup_rpc_urls.remove(i);
+        self.quorum = self.quorum.min(self.backup_rpc_urls.len() + 1);
+        changed = true;
+      }
+
+      ui.horizontal(|ui| {
+        ui.add(
+          egui::TextEdit::singleline(backup_input)
+            .hint_text("백업 RPC URL")
+            .desired_width(160.0),
+        );
+        if ui.small_button("백업 추가").clicked()
+          && !backup_input.trim().is_empty()
+        {
+          self.backup_rpc_urls.push(backup_input.trim().to_string());
+          backup_input.clear();
+          changed = true;
+        }
+      });
+    });
+    changed
+  }
+
+  fn health_ui(&self, ui: &mut egui::Ui, url: &str, tip: u64) {
+    let Some(health) = rpc::health(url) else {
+      ui.label("대기 중");
+      return;
+    };
+    let error_color = ui.visuals().error_fg_color;
+    match (health.error, health.chain_id, health.block_number) {
+      (Some(e), _, _) => {
+        ui.colored_label(error_color, "✖ 실패").on_hover_text(e);
+      }
+      (None, Some(actual), _)
+        if self.chain_id.is_some_and(|expected| expected != actual) =>
+      {
+        ui.colored_label(error_color, format!("⚠ 체인 {}", actual));
+      }
+      (None, _, Some(block_number)) => {
+        let text = format!(
+          "#{} · {}ms",
+          block_number,
+          health.latency_ms.unwrap_or_default()
+        );
+        if block_number + rpc::MAX_LAG < tip {
+          ui.colored_label(error_color, format!("{} (지연)", text));
+        } else {
+          ui.label(format!("✔ {}", text));
+        }
+      }
+      (None, _, None) => {
+        ui.label("대기 중");
+      }
+    }
+  }
+
+  fn status_ui(&self, ui: &mut egui::Ui) {
+    match self.rpc_chain_id {
+      None => {
+        ui.label("미확인");
+      }
+      Some(actual) if self.is_mismatch() => {
+        ui.colored_label(
+          egui::Color32::from_rgb(255, 0, 0),
+          format!("⚠ 불일치 (RPC: {})", actual),
+        );
+      }
+      Some(_) => {
+        ui.label("✔ 확인됨");
+      }
     }
   }
 }
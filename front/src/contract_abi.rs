@@ -0,0 +1,689 @@
+use ethers::{
+  abi::{
+    token::{LenientTokenizer, Tokenizer},
+    Abi, Function, ParamType, StateMutability, Token,
+  },
+  providers::Middleware,
+  types::{Address, Bytes, TransactionRequest, I256, U256},
+  utils::parse_ether,
+};
+use poll_promise::Promise;
+use shared::contract_abis::{ierc20::IERC20_ABI, ierc721::IERC721_ABI};
+
+use crate::chain_settings::{ChainSettings, ChainSettingsWindow};
+use crate::rpc;
+use crate::signer::{SignerWindow, TxRequest};
+
+/// Any contract, driven by a JSON ABI instead of generated bindings.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AbiContractWindow {
+  pub is_open: bool,
+
+  pub contract_name: String,
+  pub contract_address: String,
+  pub abi_json: String,
+  pub abi_url: String,
+  pub selected: usize,
+
+  contracts: Vec<AbiContract>,
+
+  #[serde(skip)]
+  abi_download: Option<Promise<Result<String, String>>>,
+  #[serde(skip)]
+  error: Option<String>,
+}
+
+impl Default for AbiContractWindow {
+  fn default() -> Self {
+    Self {
+      is_open: false,
+
+      contract_name: "".to_string(),
+      contract_address: "".to_string(),
+      abi_json: "".to_string(),
+      abi_url: "".to_string(),
+      selected: 0,
+
+      contracts: vec![],
+
+      abi_download: None,
+      error: None,
+    }
+  }
+}
+
+impl AbiContractWindow {
+  pub fn show(&mut self, ui: &mut egui::Ui) {
+    ui.checkbox(&mut self.is_open, "ABI");
+  }
+
+  /// The preset ABIs plus every added contract's, for decoding events and
+  /// custom errors.
+  pub fn known_abis(&self) -> Vec<Abi> {
+    let mut abis = vec![IERC20_ABI.clone(), IERC721_ABI.clone()];
+    abis.extend(
+      self
+        .contracts
+        .iter()
+        .filter_map(|contract| parse_abi(&contract.abi_json).ok()),
+    );
+    abis
+  }
+
+  pub fn update(
+    &mut self,
+    ctx: &egui::Context,
+    _frame: &mut eframe::Frame,
+    chain_settings: &ChainSettingsWindow,
+    signer: &mut SignerWindow,
+  ) {
+    if let Some(download) = &self.abi_download {
+      if let Some(result) = download.ready() {
+        match result {
+          Ok(json) => self.abi_json = json.clone(),
+          Err(e) => self.error = Some(e.clone()),
+        }
+        self.abi_download = None;
+      }
+    }
+
+    egui::Window::new("ABI 컨트랙트")
+      .open(&mut self.is_open)
+      .vscroll(true)
+      .show(ctx, |ui| {
+        ui.collapsing("컨트랙트 추가", |ui| {
+          ui.horizontal(|ui| {
+            ui.label("컨트랙트 이름");
+            ui.text_edit_singleline(&mut self.contract_name);
+          });
+          ui.horizontal(|ui| {
+            ui.label("컨트랙트 주소");
+            ui.text_edit_singleline(&mut self.contract_address);
+          });
+
+          let selected_chain = chain_settings
+            .chain_settings
+            .get(self.selected)
+            .map(|chain| chain.chain_name.clone())
+            .unwrap_or_default();
+          egui::ComboBox::from_label("체인 선택")
+            .selected_text(selected_chain)
+            .show_ui(ui, |ui| {
+              for (i, chain) in chain_settings.chain_settings.iter().enumerate()
+              {
+                ui.selectable_value(&mut self.selected, i, &chain.chain_name);
+              }
+            });
+
+          ui.horizontal(|ui| {
+            ui.label("ABI URL");
+            ui.text_edit_singleline(&mut self.abi_url);
+            let downloading = self.abi_download.is_some();
+            if ui
+              .add_enabled(!downloading, egui::Button::new("불러오기"))
+              .clicked()
+            {
+              self.abi_download = Some(download_abi(self.abi_url.trim()));
+            }
+            if downloading {
+              ui.spinner();
+            }
+          });
+
+          ui.horizontal(|ui| {
+            ui.label("기본 ABI");
+            if ui.button("IERC20").clicked() {
+              self.abi_json = abi_to_json(&IERC20_ABI);
+            }
+            if ui.button("IERC721").clicked() {
+              self.abi_json = abi_to_json(&IERC721_ABI);
+            }
+          });
+
+          ui.label("ABI JSON (배열 또는 Hardhat/Foundry 아티팩트)");
+          ui.add(
+            egui::TextEdit::multiline(&mut self.abi_json)
+              .code_editor()
+              .desired_rows(6)
+              .desired_width(f32::INFINITY),
+          );
+
+          if ui.button("컨트랙트 추가").clicked() {
+            let chain = chain_settings.chain_settings.get(self.selected);
+            match AbiContract::new(
+              &self.contract_name,
+              &self.contract_address,
+              &self.abi_json,
+              chain,
+            ) {
+              Ok(contract) => {
+                self.contracts.push(contract);
+                self.contract_name = "".to_string();
+                self.contract_address = "".to_string();
+                self.abi_json = "".to_string();
+                self.error = None;
+              }
+              Err(e) => self.error = Some(e),
+            }
+          }
+
+          if let Some(e) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, e);
+          }
+        });
+
+        let mut removed = None;
+        for (i, contract) in self.contracts.iter_mut().enumerate() {
+          ui.collapsing(
+            format!("{} ({})", contract.name, contract.chain_name),
+            |ui| {
+              contract.ui(ui, chain_settings, signer);
+
+              if ui.button("컨트랙트 삭제").clicked() {
+                removed = Some(i);
+              }
+            },
+          );
+        }
+        if let Some(i) = removed {
+          self.contracts.remove(i);
+        }
+      });
+  }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AbiContract {
+  pub name: String,
+  pub address: String,
+  /// RPC comes from the chain settings at call time, so edits there apply.
+  pub chain_name: String,
+  pub abi_json: String,
+
+  /// Parsed from `abi_json` on first use, one form per function.
+  #[serde(skip)]
+  functions: Option<Result<Vec<FunctionForm>, String>>,
+}
+
+impl Default for AbiContract {
+  fn default() -> Self {
+    Self {
+      name: "".to_string(),
+      address: "".to_string(),
+      chain_name: "".to_string(),
+      abi_json: "".to_string(),
+
+      functions: None,
+    }
+  }
+}
+
+impl AbiContract {
+  fn new(
+    name: &str,
+    address: &str,
+    abi_json: &str,
+    chain: Option<&ChainSettings>,
+  ) -> Result<Self, String> {
+    let chain = chain.ok_or_else(|| "체인을 먼저 추가해 주세요".to_string())?;
+    address
+      .trim()
+      .parse::<Address>()
+      .map_err(|_| format!("잘못된 주소: {}", address))?;
+    parse_abi(abi_json)?;
+
+    Ok(Self {
+      name: name.to_string(),
+      address: address.trim().to_string(),
+      chain_name: chain.chain_name.clone(),
+      abi_json: abi_json.to_string(),
+      ..Default::default()
+    })
+  }
+
+  fn ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    chain_settings: &ChainSettingsWindow,
+    signer: &mut SignerWindow,
+  ) {
+    ui.label(format!("주소: {}", self.address));
+
+    let Some(chain) = chain_settings.chain(&self.chain_name) else {
+      ui.colored_label(
+        ui.visuals().error_fg_color,
+        format!("체인 설정에 {}이 없습니다", self.chain_name),
+      );
+      return;
+    };
+
+    let functions = self.functions.get_or_insert_with(|| {
+      parse_abi(&self.abi_json)
+        .map(|abi| abi.functions().cloned().map(FunctionForm::new).collect())
+    });
+
+    let functions = match functions {
+      Ok(functions) => functions,
+      Err(e) => {
+        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+        return;
+      }
+    };
+
+    for form in functions {
+      ui.push_id(form.function.signature(), |ui| {
+        form.ui(ui, &self.address, &chain.rpc_url, signer);
+      });
+    }
+  }
+}
+
+struct FunctionForm {
+  function: Function,
+  inputs: Vec<Input>,
+  /// ETH sent with payable functions.
+  value: String,
+  calldata: Option<Result<Bytes, String>>,
+  result: Option<Promise<Result<String, String>>>,
+}
+
+impl FunctionForm {
+  fn new(function: Function) -> Self {
+    let inputs = function
+      .inputs
+      .iter()
+      .map(|param| Input::new(&param.kind))
+      .collect();
+
+    Self {
+      function,
+      inputs,
+      value: "".to_string(),
+      calldata: None,
+      result: None,
+    }
+  }
+
+  fn is_view(&self) -> bool {
+    matches!(
+      self.function.state_mutability,
+      StateMutability::View | StateMutability::Pure
+    )
+  }
+
+  fn ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    address: &str,
+    rpc_url: &str,
+    signer: &mut SignerWindow,
+  ) {
+    let title = format!(
+      "{}{}",
+      self.function.signature(),
+      if self.is_view() { "" } else { " ✏" }
+    );
+
+    ui.collapsing(title, |ui| {
+      for (i, (param, input)) in self
+        .function
+        .inputs
+        .iter()
+        .zip(&mut self.inputs)
+        .enumerate()
+      {
+        let label = if param.name.is_empty() {
+          format!("arg{}", i)
+        } else {
+          param.name.clone()
+        };
+        input.ui(ui, &label, &param.kind);
+      }
+
+      if self.function.state_mutability == StateMutability::Payable {
+        ui.horizontal(|ui| {
+          ui.label("value (ETH)");
+          ui.text_edit_singleline(&mut self.value);
+        });
+      }
+
+      ui.horizontal(|ui| {
+        let label = if self.is_view() {
+          "호출"
+        } else {
+          "eth_call 시뮬레이션"
+        };
+        if ui.button(label).clicked() {
+          let calldata = self.encode();
+          self.result = Some(match (&calldata, self.value()) {
+            (Ok(calldata), Ok(value)) => call(
+              self.function.clone(),
+              address,
+              rpc_url,
+              calldata.clone(),
+              value,
+            ),
+            (Err(e), _) => Promise::from_ready(Err(e.clone())),
+            (_, Err(e)) => Promise::from_ready(Err(e)),
+          });
+          self.calldata = Some(calldata);
+        }
+        if !self.is_view() && ui.button("트랜잭션 보내기").clicked() {
+          self.result = None;
+          self.calldata = Some(self.encode());
+          if let Err(e) = self.send(address, rpc_url, signer) {
+            self.calldata = Some(Err(e));
+          }
+        }
+        if ui.button("calldata").clicked() {
+          self.calldata = Some(self.encode());
+          self.result = None;
+        }
+      });
+
+      if let Some(Ok(calldata)) = &self.calldata {
+        let mut calldata = calldata.to_string();
+        ui.add(
+          egui::TextEdit::multiline(&mut calldata)
+            .code_editor()
+            .desired_rows(1)
+            .desired_width(f32::INFINITY),
+        );
+      }
+
+      let error = match (&self.calldata, &self.result) {
+        (Some(Err(e)), _) => Some(e),
+        (_, Some(result)) => match result.ready() {
+          None => {
+            ui.spinner();
+            None
+          }
+          Some(Ok(output)) => {
+            ui.label(output);
+            None
+          }
+          Some(Err(e)) => Some(e),
+        },
+        _ => None,
+      };
+      if let Some(e) = error {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+      }
+    });
+  }
+
+  /// Queues the call with the signer, which asks for confirmation.
+  fn send(
+    &self,
+    address: &str,
+    rpc_url: &str,
+    signer: &mut SignerWindow,
+  ) -> Result<(), String> {
+    let to = address
+      .parse::<Address>()
+      .map_err(|_| format!("잘못된 주소: {}", address))?;
+
+    signer.request(TxRequest {
+      label: self.function.signature(),
+      rpc_url: rpc_url.to_string(),
+      to,
+      data: self.encode()?,
+      value: self.value()?,
+      outcome: None,
+    });
+
+    Ok(())
+  }
+
+  fn value(&self) -> Result<U256, String> {
+    match self.value.trim() {
+      "" => Ok(U256::zero()),
+      value => {
+        parse_ether(value).map_err(|_| format!("잘못된 ETH 값: {}", value))
+      }
+    }
+  }
+
+  fn encode(&self) -> Result<Bytes, String> {
+    let tokens = self
+      .function
+      .inputs
+      .iter()
+      .zip(&self.inputs)
+      .map(|(param, input)| input.to_token(&param.kind))
+      .collect::<Result<Vec<_>, _>>()?;
+
+    self
+      .function
+      .encode_input(&tokens)
+      .map(Bytes::from)
+      .map_err(|e| e.to_string())
+  }
+}
+
+/// Form state mirroring a `ParamType`: scalars are typed in as text, bools
+/// are checkboxes and tuples/arrays nest.
+pub enum Input {
+  Text(String),
+  Bool(bool),
+  Tuple(Vec<Input>),
+  Array(Vec<Input>),
+}
+
+impl Input {
+  pub fn new(kind: &ParamType) -> Self {
+    match kind {
+      ParamType::Bool => Input::Bool(false),
+      ParamType::Tuple(kinds) => {
+        Input::Tuple(kinds.iter().map(Input::new).collect())
+      }
+      ParamType::FixedArray(kind, len) => {
+        Input::Array((0..*len).map(|_| Input::new(kind)).collect())
+      }
+      ParamType::Array(_) => Input::Array(vec![]),
+      _ => Input::Text("".to_string()),
+    }
+  }
+
+  fn ui(&mut self, ui: &mut egui::Ui, label: &str, kind: &ParamType) {
+    match (self, kind) {
+      (Input::Bool(value), _) => {
+        ui.checkbox(value, format!("{}: bool", label));
+      }
+      (Input::Tuple(fields), ParamType::Tuple(kinds)) => {
+        ui.label(format!("{}: {}", label, kind));
+        ui.indent(label, |ui| {
+          for (i, (field, kind)) in fields.iter_mut().zip(kinds).enumerate() {
+            field.ui(ui, &format!("{}.{}", label, i), kind);
+          }
+        });
+      }
+      (Input::Array(items), ParamType::Array(item_kind)) => {
+        ui.horizontal(|ui| {
+          ui.label(format!("{}: {}", label, kind));
+          if ui.small_button("+").clicked() {
+            items.push(Input::new(item_kind));
+          }
+          if ui.small_button("-").clicked() {
+            items.pop();
+          }
+        });
+        ui.indent(label, |ui| {
+          for (i, item) in items.iter_mut().enumerate() {
+            item.ui(ui, &format!("{}[{}]", label, i), item_kind);
+          }
+        });
+      }
+      (Input::Array(items), ParamType::FixedArray(item_kind, _)) => {
+        ui.label(format!("{}: {}", label, kind));
+        ui.indent(label, |ui| {
+          for (i, item) in items.iter_mut().enumerate() {
+            item.ui(ui, &format!("{}[{}]", label, i), item_kind);
+          }
+        });
+      }
+      (Input::Text(text), kind) => {
+        let valid = text.is_empty()
+          || LenientTokenizer::tokenize(kind, text.trim()).is_ok();
+        ui.horizontal(|ui| {
+          let color = if valid {
+            egui::Color32::from_rgb(140, 140, 140)
+          } else {
+            egui::Color32::from_rgb(255, 0, 0)
+          };
+          ui.colored_label(color, format!("{}: {}", label, kind));
+          ui.text_edit_singleline(text);
+        });
+      }
+      _ => {}
+    }
+  }
+
+  pub fn to_token(&self, kind: &ParamType) -> Result<Token, String> {
+    match (self, kind) {
+      (Input::Bool(value), _) => Ok(Token::Bool(*value)),
+      (Input::Tuple(fields), ParamType::Tuple(kinds)) => fields
+        .iter()
+        .zip(kinds)
+        .map(|(field, kind)| field.to_token(kind))
+        .collect::<Result<_, _>>()
+        .map(Token::Tuple),
+      (Input::Array(items), ParamType::Array(item_kind)) => items
+        .iter()
+        .map(|item| item.to_token(item_kind))
+        .collect::<Result<_, _>>()
+        .map(Token::Array),
+      (Input::Array(items), ParamType::FixedArray(item_kind, _)) => items
+        .iter()
+        .map(|item| item.to_token(item_kind))
+        .collect::<Result<_, _>>()
+        .map(Token::FixedArray),
+      (Input::Text(text), kind) => {
+        LenientTokenizer::tokenize(kind, text.trim())
+          .map_err(|e| format!("{} 값 오류 ({}): {}", kind, text, e))
+      }
+      _ => Err(format!("{} 입력 형식이 맞지 않습니다", kind)),
+    }
+  }
+}
+
+/// `eth_call`s `calldata` against `address` and decodes the output.
+fn call(
+  function: Function,
+  address: &str,
+  rpc_url: &str,
+  calldata: Bytes,
+  value: U256,
+) -> Promise<Result<String, String>> {
+  let address = match address.parse::<Address>() {
+    Ok(address) => address,
+    Err(_) => {
+      return Promise::from_ready(Err(format!("잘못된 주소: {}", address)))
+    }
+  };
+  let provider = match rpc::provider(rpc_url) {
+    Ok(provider) => provider,
+    Err(e) => return Promise::from_ready(Err(e)),
+  };
+
+  Promise::spawn_local(async move {
+    let tx = TransactionRequest::new()
+      .to(address)
+      .data(calldata)
+      .value(value)
+      .into();
+    let output = provider.call(&tx, None).await.map_err(|e| e.to_string())?;
+    let tokens = function
+      .decode_output(&output)
+      .map_err(|e| format!("반환값 디코딩 실패 ({}): {}", output, e))?;
+
+    Ok(
+      function
+        .outputs
+        .iter()
+        .zip(&tokens)
+        .map(|(param, token)| {
+          let value = format_token(token, &param.kind);
+          if param.name.is_empty() {
+            format!("{}: {}", param.kind, value)
+          } else {
+            format!("{} {}: {}", param.kind, param.name, value)
+          }
+        })
+        .collect::<Vec<_>>()
+        .join("\n"),
+    )
+  })
+}
+
+pub fn format_token(token: &Token, kind: &ParamType) -> String {
+  match (token, kind) {
+    (Token::Address(address), _) => format!("{:#x}", address),
+    (Token::Int(value), _) => I256::from_raw(*value).to_string(),
+    (Token::Uint(value), _) => value.to_string(),
+    (Token::Bool(value), _) => value.to_string(),
+    (Token::String(value), _) => format!("{:?}", value),
+    (Token::Bytes(bytes), _) | (Token::FixedBytes(bytes), _) => {
+      Bytes::from(bytes.clone()).to_string()
+    }
+    (Token::Array(items), ParamType::Array(item_kind))
+    | (Token::FixedArray(items), ParamType::FixedArray(item_kind, _)) => {
+      let items = items
+        .iter()
+        .map(|item| format_token(item, item_kind))
+        .collect::<Vec<_>>();
+      format!("[{}]", items.join(", "))
+    }
+    (Token::Tuple(fields), ParamType::Tuple(kinds)) => {
+      let fields = fields
+        .iter()
+        .zip(kinds)
+        .map(|(field, kind)| format_token(field, kind))
+        .collect::<Vec<_>>();
+      format!("({})", fields.join(", "))
+    }
+    (token, _) => token.to_string(),
+  }
+}
+
+/// Accepts a bare ABI array, a Hardhat/Foundry artifact (`{"abi": [...]}`),
+/// or an Etherscan `getabi` response (`{"result": "<abi json>"}`).
+pub fn parse_abi(json: &str) -> Result<Abi, String> {
+  let value: serde_json::Value = serde_json::from_str(json.trim())
+    .map_err(|e| format!("ABI JSON 오류: {}", e))?;
+
+  let abi = match value {
+    serde_json::Value::Object(mut object) => {
+      match object.remove("abi").or_else(|| object.remove("result")) {
+        Some(serde_json::Value::String(nested)) => {
+          serde_json::from_str(&nested).map_err(|e| e.to_string())?
+        }
+        Some(abi) => abi,
+        None => return Err("ABI 배열을 찾을 수 없습니다".to_string()),
+      }
+    }
+    abi => abi,
+  };
+
+  serde_json::from_value(abi).map_err(|e| format!("ABI 형식 오류: {}", e))
+}
+
+fn abi_to_json(abi: &Abi) -> String {
+  serde_json::to_string_pretty(abi).unwrap_or_default()
+}
+
+fn download_abi(url: &str) -> Promise<Result<String, String>> {
+  let request = ehttp::Request::get(url);
+  Promise::spawn_local(async move {
+    let response = ehttp::fetch_async(request).await?;
+    if !response.ok {
+      return Err(format!("{} {}", response.status, response.status_text));
+    }
+
+    let json = response.text().unwrap_or_default().to_string();
+    parse_abi(&json)?;
+    Ok(json)
+  })
+}
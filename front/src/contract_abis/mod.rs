@@ -1,2 +0,0 @@
-pub mod ierc20;
-pub mod ierc721;
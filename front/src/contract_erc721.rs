@@ -1,13 +1,19 @@
-use crate::contract_abis::ierc721::IERC721;
-use std::sync::Arc;
+use shared::contract_abis::ierc721::IERC721;
 
 use ethers::{
-  providers::{Http, Provider},
+  abi::Detokenize,
+  contract::ContractCall,
   types::{Address, U256},
 };
 use poll_promise::Promise;
 
+use crate::airdrop::Airdrop;
 use crate::chain_settings::ChainSettingsWindow;
+use crate::erc165::{self, parse_interface_id, InterfaceSupport};
+use crate::holder_snapshot::HolderSnapshot;
+use crate::rpc::{self, RpcProvider};
+use crate::signer::{SignerWindow, TxRequest};
+use crate::token_inspector::{TokenInspector, DEFAULT_IPFS_GATEWAY};
 
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
@@ -22,7 +28,9 @@ pub struct ERC721ContractWindow {
 
   pub contract_name_color: egui::Color32,
 
-  #[serde(skip)]
+  /// Where `ipfs://` token and image URIs are fetched from.
+  pub ipfs_gateway: String,
+
   contracts: Vec<ERC721Contract>,
 }
 
@@ -38,6 +46,8 @@ impl Default for ERC721ContractWindow {
 
       selected_chain: "".to_string(),
       selected: 0,
+
+      ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(),
     }
   }
 }
@@ -52,6 +62,7 @@ impl ERC721ContractWindow {
     ctx: &egui::Context,
     _frame: &mut eframe::Frame,
     chain_settings: &mut ChainSettingsWindow,
+    signer: &mut SignerWindow,
   ) {
     egui::Window::new("ERC721 컨트랙트")
       .open(&mut self.is_open)
@@ -90,7 +101,7 @@ impl ERC721ContractWindow {
           });
 
           if ui.button("컨트랙트 추가").clicked() {
-            if let Err(_) = self.contract_address.parse::<Address>() {
+            if self.contract_address.parse::<Address>().is_err() {
               self.contract_name_color = egui::Color32::from_rgb(255, 0, 0);
               return;
             } else {
@@ -116,51 +127,73 @@ impl ERC721ContractWindow {
           }
         });
 
+        ui.horizontal(|ui| {
+          ui.label("IPFS 게이트웨이");
+          ui.text_edit_singleline(&mut self.ipfs_gateway);
+        });
+
+        let mut removed = None;
         for i in 0..self.contracts.len() {
           ui.horizontal(|ui| {
             ui.collapsing(self.contracts[i].name.clone(), |ui| {
-              // BALANCE_OF
-              ui.horizontal(|ui| {
-                ui.label("balanceOf");
-                ui.text_edit_singleline(
-                  &mut self.contracts[i].balance_of_target_address,
+              ui.collapsing("읽기", |ui| {
+                self.contracts[i].reads_ui(ui);
+              });
+
+              ui.collapsing("쓰기", |ui| {
+                self.contracts[i].writes_ui(ui, signer);
+              });
+
+              ui.collapsing("ERC-165 인터페이스", |ui| {
+                self.contracts[i].interfaces_ui(ui);
+              });
+
+              ui.collapsing("토큰 조회", |ui| {
+                let contract = &mut self.contracts[i];
+                contract.inspector.ui(
+                  ui,
+                  &contract.address,
+                  &contract.rpc_url,
+                  &self.ipfs_gateway,
                 );
               });
-              if let Some(promise) = &self.contracts[i].balance_of {
-                if let Some(balance_of) = promise.ready() {
-                  ui.label(format!("{} 개", balance_of));
-                }
-              }
-              if ui.button("호출").clicked() {
-                self.contracts[i].balance_of();
-              };
-
-              // OWNER_OF
-              ui.horizontal(|ui| {
-                ui.label("ownerOf");
-                ui.text_edit_singleline(
-                  &mut self.contracts[i].owner_of_target_token_id,
+
+              ui.collapsing("홀더 스냅샷", |ui| {
+                let contract = &mut self.contracts[i];
+                contract.snapshot.ui(
+                  ui,
+                  &contract.name,
+                  &contract.address,
+                  &contract.rpc_url,
+                );
+              });
+
+              ui.collapsing("일괄 전송", |ui| {
+                let contract = &mut self.contracts[i];
+                contract.airdrop.ui(
+                  ui,
+                  &contract.name,
+                  &contract.address,
+                  &contract.rpc_url,
+                  signer,
                 );
               });
-              if let Some(promise) = &self.contracts[i].owner_of {
-                if let Some(owner_of) = promise.ready() {
-                  ui.label(format!("{}", owner_of));
-                }
-              }
-              if ui.button("호출").clicked() {
-                self.contracts[i].owner_of();
-              };
 
               if ui.button("컨트랙트 삭제").clicked() {
-                self.contracts.remove(i);
+                removed = Some(i);
               }
             });
           });
         }
+        if let Some(i) = removed {
+          self.contracts.remove(i);
+        }
       });
   }
 }
 
+type ViewResult = Option<Promise<Result<String, String>>>;
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct ERC721Contract {
@@ -169,15 +202,51 @@ pub struct ERC721Contract {
   pub chain_name: String,
   pub rpc_url: String,
 
+  /// Last ERC-165 probe result.
+  pub interfaces: Vec<InterfaceSupport>,
+  #[serde(skip)]
+  probe: Option<Promise<Result<Vec<InterfaceSupport>, String>>>,
+  #[serde(skip)]
+  probe_error: Option<String>,
+
   #[serde(skip)]
-  pub balance_of: Option<Promise<U256>>,
+  pub balance_of: ViewResult,
   #[serde(skip)]
   pub balance_of_target_address: String,
 
   #[serde(skip)]
-  pub owner_of: Option<Promise<Address>>,
+  pub owner_of: ViewResult,
   #[serde(skip)]
   pub owner_of_target_token_id: String,
+
+  #[serde(skip)]
+  pub get_approved: ViewResult,
+  #[serde(skip)]
+  pub get_approved_token_id: String,
+
+  #[serde(skip)]
+  pub is_approved_for_all: ViewResult,
+  #[serde(skip)]
+  pub is_approved_for_all_owner: String,
+  #[serde(skip)]
+  pub is_approved_for_all_operator: String,
+
+  #[serde(skip)]
+  pub supports_interface: ViewResult,
+  #[serde(skip)]
+  pub supports_interface_id: String,
+
+  #[serde(skip)]
+  writes: WriteInputs,
+
+  #[serde(skip)]
+  pub inspector: TokenInspector,
+
+  #[serde(skip)]
+  pub snapshot: HolderSnapshot,
+
+  /// Kept across sessions so an interrupted airdrop can be resumed.
+  pub airdrop: Airdrop,
 }
 
 impl Default for ERC721Contract {
@@ -188,50 +257,443 @@ impl Default for ERC721Contract {
       chain_name: "".to_string(),
       rpc_url: "".to_string(),
 
+      interfaces: vec![],
+      probe: None,
+      probe_error: None,
+
       balance_of: None,
       balance_of_target_address: "".to_string(),
 
       owner_of: None,
       owner_of_target_token_id: "".to_string(),
+
+      get_approved: None,
+      get_approved_token_id: "".to_string(),
+
+      is_approved_for_all: None,
+      is_approved_for_all_owner: "".to_string(),
+      is_approved_for_all_operator: "".to_string(),
+
+      supports_interface: None,
+      supports_interface_id: "".to_string(),
+
+      writes: WriteInputs::default(),
+
+      inspector: TokenInspector::default(),
+
+      snapshot: HolderSnapshot::default(),
+
+      airdrop: Airdrop::default(),
     }
   }
 }
 
 impl ERC721Contract {
-  pub fn balance_of(&mut self) {
-    let contract_address = self.address.parse::<Address>().unwrap();
-    let wallet_address =
-      self.balance_of_target_address.parse::<Address>().unwrap();
-    let provider = Provider::<Http>::try_from(self.rpc_url.clone()).unwrap();
-    let client = Arc::new(provider);
-    let contract = IERC721::new(contract_address, client);
-
-    let promise = Promise::spawn_local(async move {
-      if let Ok(balance_of) = contract.balance_of(wallet_address).call().await {
-        balance_of
+  fn reads_ui(&mut self, ui: &mut egui::Ui) {
+    // BALANCE_OF
+    ui.horizontal(|ui| {
+      input(
+        ui,
+        "balanceOf(owner)",
+        &mut self.balance_of_target_address,
+        is_address,
+      );
+      if ui.button("호출").clicked() {
+        self.balance_of();
+      }
+    });
+    result_ui(ui, &self.balance_of);
+
+    // OWNER_OF
+    ui.horizontal(|ui| {
+      input(
+        ui,
+        "ownerOf(tokenId)",
+        &mut self.owner_of_target_token_id,
+        is_token_id,
+      );
+      if ui.button("호출").clicked() {
+        self.owner_of();
+      }
+    });
+    result_ui(ui, &self.owner_of);
+
+    // GET_APPROVED
+    ui.horizontal(|ui| {
+      input(
+        ui,
+        "getApproved(tokenId)",
+        &mut self.get_approved_token_id,
+        is_token_id,
+      );
+      if ui.button("호출").clicked() {
+        self.get_approved();
+      }
+    });
+    result_ui(ui, &self.get_approved);
+
+    // IS_APPROVED_FOR_ALL
+    ui.horizontal(|ui| {
+      input(
+        ui,
+        "isApprovedForAll(owner",
+        &mut self.is_approved_for_all_owner,
+        is_address,
+      );
+      input(
+        ui,
+        "operator)",
+        &mut self.is_approved_for_all_operator,
+        is_address,
+      );
+      if ui.button("호출").clicked() {
+        self.is_approved_for_all();
+      }
+    });
+    result_ui(ui, &self.is_approved_for_all);
+
+    // SUPPORTS_INTERFACE
+    ui.horizontal(|ui| {
+      input(
+        ui,
+        "supportsInterface(bytes4)",
+        &mut self.supports_interface_id,
+        |id| parse_interface_id(id).is_ok(),
+      );
+      if ui.button("호출").clicked() {
+        self.supports_interface();
+      }
+    });
+    result_ui(ui, &self.supports_interface);
+  }
+
+  /// Write calls are handed to the signer, which asks for confirmation.
+  fn writes_ui(&mut self, ui: &mut egui::Ui, signer: &mut SignerWindow) {
+    let contract = match self.contract() {
+      Ok(contract) => contract,
+      Err(e) => {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+        return;
+      }
+    };
+    let w = &mut self.writes;
+    let mut queued = None;
+
+    ui.horizontal(|ui| {
+      input(ui, "approve(to", &mut w.approve_to, is_address);
+      input(ui, "tokenId)", &mut w.approve_token_id, is_token_id);
+      if ui.button("보내기").clicked() {
+        queued = Some(parse_address(&w.approve_to).and_then(|to| {
+          let token_id = parse_token_id(&w.approve_token_id)?;
+          Ok((
+            format!("approve({:#x}, {})", to, token_id),
+            contract.approve(to, token_id),
+          ))
+        }));
+      }
+    });
+
+    ui.horizontal(|ui| {
+      input(
+        ui,
+        "setApprovalForAll(operator",
+        &mut w.operator,
+        is_address,
+      );
+      ui.checkbox(&mut w.approved, "approved)");
+      if ui.button("보내기").clicked() {
+        queued = Some(parse_address(&w.operator).map(|operator| {
+          (
+            format!("setApprovalForAll({:#x}, {})", operator, w.approved),
+            contract.set_approval_for_all(operator, w.approved),
+          )
+        }));
+      }
+    });
+
+    ui.horizontal(|ui| {
+      let name = if w.safe {
+        "safeTransferFrom"
       } else {
-        U256::zero()
+        "transferFrom"
+      };
+      input(
+        ui,
+        &format!("{}(from", name),
+        &mut w.transfer_from,
+        is_address,
+      );
+      input(ui, "to", &mut w.transfer_to, is_address);
+      input(ui, "tokenId)", &mut w.transfer_token_id, is_token_id);
+      ui.checkbox(&mut w.safe, "safe");
+      if ui.button("보내기").clicked() {
+        queued = Some(parse_address(&w.transfer_from).and_then(|from| {
+          let to = parse_address(&w.transfer_to)?;
+          let token_id = parse_token_id(&w.transfer_token_id)?;
+          let call = if w.safe {
+            contract.safe_transfer_from(from, to, token_id)
+          } else {
+            contract.transfer_from(from, to, token_id)
+          };
+          Ok((
+            format!("{}({:#x}, {:#x}, {})", name, from, to, token_id),
+            call,
+          ))
+        }));
+      }
+    });
+
+    ui.horizontal(|ui| {
+      input(ui, "mint(to", &mut w.mint_to, is_address);
+      input(ui, "tokenId)", &mut w.mint_token_id, is_token_id);
+      if ui.button("보내기").clicked() {
+        queued = Some(parse_address(&w.mint_to).and_then(|to| {
+          let token_id = parse_token_id(&w.mint_token_id)?;
+          Ok((
+            format!("mint({:#x}, {})", to, token_id),
+            contract.mint(to, token_id),
+          ))
+        }));
+      }
+    });
+
+    match queued {
+      Some(Ok((label, call))) => {
+        w.error = None;
+        signer.request(TxRequest {
+          label,
+          rpc_url: self.rpc_url.clone(),
+          to: contract.address(),
+          data: call.calldata().unwrap_or_default(),
+          value: U256::zero(),
+          outcome: None,
+        });
+      }
+      Some(Err(e)) => w.error = Some(e),
+      None => {}
+    }
+
+    if let Some(e) = &w.error {
+      ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+  }
+
+  fn interfaces_ui(&mut self, ui: &mut egui::Ui) {
+    if let Some(probe) = &self.probe {
+      match probe.ready() {
+        None => {
+          ui.spinner();
+          return;
+        }
+        Some(Ok(interfaces)) => {
+          self.interfaces = interfaces.clone();
+          self.probe_error = None;
+          self.probe = None;
+        }
+        Some(Err(e)) => {
+          self.probe_error = Some(e.clone());
+          self.probe = None;
+        }
+      }
+    }
+
+    egui::Grid::new("erc165").striped(true).show(ui, |ui| {
+      for interface in &self.interfaces {
+        ui.label(&interface.name);
+        ui.monospace(&interface.interface_id);
+        ui.label(match interface.supported {
+          Some(true) => "✔ 지원",
+          Some(false) => "✖ 미지원",
+          None => "? 확인 불가",
+        });
+        ui.end_row();
       }
     });
 
-    self.balance_of = Some(promise);
+    if let Some(e) = &self.probe_error {
+      ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+
+    if ui.button("인터페이스 확인").clicked() {
+      self.probe = Some(match self.contract() {
+        Ok(contract) => Promise::spawn_local(erc165::probe(contract)),
+        Err(e) => Promise::from_ready(Err(e)),
+      });
+    }
+  }
+
+  pub fn balance_of(&mut self) {
+    self.balance_of =
+      Some(match parse_address(&self.balance_of_target_address) {
+        Ok(owner) => self
+          .view(|c| c.balance_of(owner), |balance| format!("{} 개", balance)),
+        Err(e) => Promise::from_ready(Err(e)),
+      });
   }
 
   pub fn owner_of(&mut self) {
-    let contract_address = self.address.parse::<Address>().unwrap();
-    let token_id = self.owner_of_target_token_id.parse::<U256>().unwrap();
-    let provider = Provider::<Http>::try_from(self.rpc_url.clone()).unwrap();
-    let client = Arc::new(provider);
-    let contract = IERC721::new(contract_address, client);
-
-    let promise = Promise::spawn_local(async move {
-      if let Ok(owner_of) = contract.owner_of(token_id).call().await {
-        owner_of
-      } else {
-        Address::zero()
-      }
+    self.owner_of =
+      Some(match parse_token_id(&self.owner_of_target_token_id) {
+        Ok(token_id) => {
+          self.view(|c| c.owner_of(token_id), |owner| format!("{:#x}", owner))
+        }
+        Err(e) => Promise::from_ready(Err(e)),
+      });
+  }
+
+  pub fn get_approved(&mut self) {
+    self.get_approved =
+      Some(match parse_token_id(&self.get_approved_token_id) {
+        Ok(token_id) => self.view(
+          |c| c.get_approved(token_id),
+          |approved| format!("{:#x}", approved),
+        ),
+        Err(e) => Promise::from_ready(Err(e)),
+      });
+  }
+
+  pub fn is_approved_for_all(&mut self) {
+    let args =
+      parse_address(&self.is_approved_for_all_owner).and_then(|owner| {
+        Ok((owner, parse_address(&self.is_approved_for_all_operator)?))
+      });
+    self.is_approved_for_all = Some(match args {
+      Ok((owner, operator)) => self.view(
+        |c| c.is_approved_for_all(owner, operator),
+        |approved| approved.to_string(),
+      ),
+      Err(e) => Promise::from_ready(Err(e)),
     });
+  }
 
-    self.owner_of = Some(promise);
+  pub fn supports_interface(&mut self) {
+    self.supports_interface =
+      Some(match parse_interface_id(&self.supports_interface_id) {
+        Ok(interface_id) => self.view(
+          |c| c.supports_interface(interface_id),
+          |supported| supported.to_string(),
+        ),
+        Err(e) => Promise::from_ready(Err(e)),
+      });
   }
+
+  fn contract(&self) -> Result<IERC721<RpcProvider>, String> {
+    let address = parse_address(&self.address)?;
+    let provider = rpc::provider(&self.rpc_url)?;
+
+    Ok(IERC721::new(address, provider))
+  }
+
+  /// Runs a view call built by `call` and renders its output with `format`.
+  fn view<D, F>(
+    &self,
+    call: impl FnOnce(&IERC721<RpcProvider>) -> ContractCall<RpcProvider, D>,
+    format: F,
+  ) -> Promise<Result<String, String>>
+  where
+    D: Detokenize + 'static,
+    F: FnOnce(D) -> String + 'static,
+  {
+    let call = match self.contract() {
+      Ok(contract) => call(&contract),
+      Err(e) => return Promise::from_ready(Err(e)),
+    };
+
+    Promise::spawn_local(async move {
+      call.call().await.map(format).map_err(|e| e.to_string())
+    })
+  }
+}
+
+/// Arguments typed into the write section.
+struct WriteInputs {
+  approve_to: String,
+  approve_token_id: String,
+
+  operator: String,
+  approved: bool,
+
+  transfer_from: String,
+  transfer_to: String,
+  transfer_token_id: String,
+  /// Use `safeTransferFrom` instead of `transferFrom`.
+  safe: bool,
+
+  mint_to: String,
+  mint_token_id: String,
+
+  error: Option<String>,
+}
+
+impl Default for WriteInputs {
+  fn default() -> Self {
+    Self {
+      approve_to: "".to_string(),
+      approve_token_id: "".to_string(),
+
+      operator: "".to_string(),
+      approved: true,
+
+      transfer_from: "".to_string(),
+      transfer_to: "".to_string(),
+      transfer_token_id: "".to_string(),
+      safe: true,
+
+      mint_to: "".to_string(),
+      mint_token_id: "".to_string(),
+
+      error: None,
+    }
+  }
+}
+
+/// Text field whose label turns red while `valid` rejects the input.
+fn input(
+  ui: &mut egui::Ui,
+  label: &str,
+  value: &mut String,
+  valid: impl Fn(&str) -> bool,
+) {
+  let color = if value.is_empty() || valid(value) {
+    egui::Color32::from_rgb(140, 140, 140)
+  } else {
+    egui::Color32::from_rgb(255, 0, 0)
+  };
+  ui.colored_label(color, label);
+  ui.text_edit_singleline(value);
+}
+
+fn result_ui(ui: &mut egui::Ui, result: &ViewResult) {
+  match result.as_ref().map(Promise::ready) {
+    None => {}
+    Some(None) => {
+      ui.spinner();
+    }
+    Some(Some(Ok(value))) => {
+      ui.label(value);
+    }
+    Some(Some(Err(e))) => {
+      ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+  }
+}
+
+fn is_address(value: &str) -> bool {
+  parse_address(value).is_ok()
+}
+
+fn is_token_id(value: &str) -> bool {
+  parse_token_id(value).is_ok()
+}
+
+fn parse_address(value: &str) -> Result<Address, String> {
+  value
+    .trim()
+    .parse::<Address>()
+    .map_err(|_| format!("잘못된 주소: {}", value))
+}
+
+fn parse_token_id(value: &str) -> Result<U256, String> {
+  U256::from_dec_str(value.trim())
+    .map_err(|_| format!("잘못된 토큰 ID: {}", value))
 }
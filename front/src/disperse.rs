@@ -0,0 +1,783 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use ethers::{
+  abi::{parse_abi, Address},
+  contract::BaseContract,
+  providers::Middleware,
+  types::{Bytes, TxHash, U256},
+  utils::{format_units, parse_units, ParseUnits},
+};
+use poll_promise::Promise;
+use shared::contract_abis::ierc20::IERC20;
+
+use crate::chain_settings::{ChainSettings, ChainSettingsWindow};
+use crate::rpc::{self, RpcProvider};
+use crate::signer::{Outcome, SignerWindow, TxRequest};
+use crate::tx_history::{self, MAX_UNSEEN_POLLS, POLL_INTERVAL};
+
+/// Disperse.app, deployed at the same address on most EVM chains.
+const DEFAULT_DISPERSE_ADDRESS: &str =
+  "0xD152f549545093347A162Dce210e7293f1452150";
+
+const DISPERSE_ABI: [&str; 2] = [
+  "function disperseEther(address[] recipients, uint256[] values) payable",
+  "function disperseToken(address token, address[] recipients, uint256[] values)",
+];
+
+/// Receipt polls running at once; the rest wait for the next frame.
+const MAX_RECEIPT_POLLS: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Asset {
+  Native,
+  Erc20,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum SendMode {
+  /// One transfer per row; the signer keeps the nonces in order.
+  Sequential,
+  /// A single `disperseEther` / `disperseToken` call.
+  Contract,
+}
+
+/// Sends native coin or an ERC-20 to every `address,amount` row of a CSV.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DisperseWindow {
+  pub is_open: bool,
+
+  pub selected: usize,
+  pub asset: Asset,
+  pub token_address: String,
+  pub mode: SendMode,
+  pub disperse_address: String,
+  pub csv: String,
+
+  #[serde(skip)]
+  funds: Option<Promise<Result<Funds, String>>>,
+  #[serde(skip)]
+  rows: Option<Vec<Row>>,
+  /// Broadcast transactions by hash; in contract mode every row shares one.
+  #[serde(skip)]
+  receipts: HashMap<TxHash, Receipt>,
+  #[serde(skip)]
+  error: Option<String>,
+}
+
+impl Default for DisperseWindow {
+  fn default() -> Self {
+    Self {
+      is_open: false,
+
+      selected: 0,
+      asset: Asset::Native,
+      token_address: "".to_string(),
+      mode: SendMode::Sequential,
+      disperse_address: DEFAULT_DISPERSE_ADDRESS.to_string(),
+      csv: "".to_string(),
+
+      funds: None,
+      rows: None,
+      receipts: HashMap::new(),
+      error: None,
+    }
+  }
+}
+
+/// What the sender holds, loaded before the rows are checked.
+#[derive(Clone)]
+struct Funds {
+  from: Address,
+  decimals: u8,
+  symbol: String,
+  balance: U256,
+  /// ERC-20 allowance granted to the disperse contract.
+  allowance: U256,
+}
+
+/// One CSV line; `line` is 1-based.
+pub struct Row {
+  pub line: usize,
+  pub recipient: String,
+  pub amount: String,
+  pub parsed: Result<(Address, U256), String>,
+  status: RowStatus,
+  /// The signer request paying this row, until it is answered.
+  payment: Option<Payment>,
+}
+
+struct Payment {
+  outcome: Outcome,
+  from: Address,
+  rpc_url: String,
+}
+
+enum RowStatus {
+  Unsent,
+  /// Broadcast, waiting for the receipt.
+  Pending(TxHash),
+  /// Mined with status 1.
+  Sent(TxHash),
+  Failed(String),
+}
+
+/// Receipt polling for one broadcast transaction.
+struct Receipt {
+  rpc_url: String,
+  from: Address,
+  poll: Option<Promise<Result<tx_history::Outcome, String>>>,
+  next_poll: f64,
+  nonce: Option<U256>,
+  unseen_polls: u32,
+  /// `Ok` once mined with status 1, `Err` once reverted or dropped.
+  result: Option<Result<(), String>>,
+}
+
+impl Row {
+  /// Queued with the signer or broadcast, and not settled yet.
+  fn in_flight(&self) -> bool {
+    self.payment.is_some() || matches!(self.status, RowStatus::Pending(_))
+  }
+
+  /// Neither paid nor on its way, so a send (or retry) includes it.
+  fn unsent(&self) -> bool {
+    !self.in_flight()
+      && matches!(self.status, RowStatus::Unsent | RowStatus::Failed(_))
+  }
+}
+
+impl DisperseWindow {
+  pub fn show(&mut self, ui: &mut egui::Ui) {
+    ui.checkbox(&mut self.is_open, "대량 전송");
+  }
+
+  pub fn update(
+    &mut self,
+    ctx: &egui::Context,
+    _frame: &mut eframe::Frame,
+    chain_settings: &ChainSettingsWindow,
+    signer: &mut SignerWindow,
+  ) {
+    let mut is_open = self.is_open;
+    egui::Window::new("대량 전송")
+      .open(&mut is_open)
+      .vscroll(true)
+      .show(ctx, |ui| {
+        let chain = chain_settings.chain_settings.get(self.selected);
+        egui::ComboBox::from_label("체인 선택")
+          .selected_text(
+            chain
+              .map(|chain| chain.chain_name.clone())
+              .unwrap_or_default(),
+          )
+          .show_ui(ui, |ui| {
+            for (i, chain) in chain_settings.chain_settings.iter().enumerate() {
+              ui.selectable_value(&mut self.selected, i, &chain.chain_name);
+            }
+          });
+
+        ui.horizontal(|ui| {
+          ui.radio_value(&mut self.asset, Asset::Native, "네이티브 코인");
+          ui.radio_value(&mut self.asset, Asset::Erc20, "ERC-20");
+        });
+        if self.asset == Asset::Erc20 {
+          ui.horizontal(|ui| {
+            ui.label("토큰 주소");
+            let valid = self.token_address.trim().parse::<Address>().is_ok();
+            ui.text_edit_singleline(&mut self.token_address);
+            if !valid {
+              ui.colored_label(
+                egui::Color32::from_rgb(255, 0, 0),
+                "잘못된 주소",
+              );
+            }
+          });
+        }
+
+        ui.horizontal(|ui| {
+          ui.radio_value(&mut self.mode, SendMode::Sequential, "개별 전송");
+          ui.radio_value(
+            &mut self.mode,
+            SendMode::Contract,
+            "Disperse 컨트랙트",
+          );
+        });
+        if self.mode == SendMode::Contract {
+          ui.horizontal(|ui| {
+            ui.label("Disperse 주소");
+            ui.text_edit_singleline(&mut self.disperse_address);
+          });
+        }
+
+        ui.label("CSV (address,amount) — 금액은 토큰 단위, 예: 1.5");
+        ui.add(
+          egui::TextEdit::multiline(&mut self.csv)
+            .code_editor()
+            .desired_rows(6)
+            .desired_width(f32::INFINITY),
+        );
+
+        if ui.button("검증").clicked() {
+          self.rows = None;
+          self.receipts.clear();
+          self.error = None;
+          match (chain, signer.address()) {
+            (None, _) => self.error = Some("체인을 선택하세요".to_string()),
+            (_, None) => {
+              self.error = Some("서명 지갑을 먼저 불러오세요".to_string())
+            }
+            (Some(chain), Some(from)) => {
+              self.funds = Some(self.load_funds(chain, from))
+            }
+          }
+        }
+
+        self.sync_outcomes();
+        self.poll_receipts(ui.ctx());
+        self.check_ui(ui, chain, signer);
+
+        if let Some(e) = &self.error {
+          ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+      });
+    self.is_open = is_open;
+  }
+
+  fn load_funds(
+    &self,
+    chain: &ChainSettings,
+    from: Address,
+  ) -> Promise<Result<Funds, String>> {
+    let rpc_url = chain.rpc_url.clone();
+    let native = (chain.native_symbol.clone(), chain.decimals);
+    let asset = self.asset;
+    let token = self.token_address.trim().parse::<Address>();
+    let disperse = self.disperse_address.trim().parse::<Address>();
+
+    Promise::spawn_local(async move {
+      let provider = rpc::provider(&rpc_url)?;
+
+      if asset == Asset::Native {
+        let balance = provider
+          .get_balance(from, None)
+          .await
+          .map_err(|e| format!("RPC 오류: {}", e))?;
+        return Ok(Funds {
+          from,
+          decimals: native.1,
+          symbol: native.0,
+          balance,
+          allowance: U256::zero(),
+        });
+      }
+
+      let token = token.map_err(|_| "잘못된 토큰 주소입니다".to_string())?;
+      let contract = IERC20::new(token, provider);
+      let rpc_error = |e: ethers::contract::ContractError<RpcProvider>| {
+        format!("RPC 오류: {}", e)
+      };
+      let allowance = match disperse {
+        Ok(disperse) => contract
+          .allowance(from, disperse)
+          .call()
+          .await
+          .map_err(rpc_error)?,
+        Err(_) => U256::zero(),
+      };
+
+      Ok(Funds {
+        from,
+        decimals: contract.decimals().call().await.map_err(rpc_error)?,
+        symbol: contract.symbol().call().await.unwrap_or_default(),
+        balance: contract.balance_of(from).call().await.map_err(rpc_error)?,
+        allowance,
+      })
+    })
+  }
+
+  /// Row table, totals and the send buttons, once the funds have loaded.
+  fn check_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    chain: Option<&ChainSettings>,
+    signer: &mut SignerWindow,
+  ) {
+    let funds = match self.funds.as_ref().map(Promise::ready) {
+      None => return,
+      Some(None) => {
+        ui.spinner();
+        return;
+      }
+      Some(Some(Err(e))) => {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+        return;
+      }
+      Some(Some(Ok(funds))) => funds.clone(),
+    };
+    let rows = self
+      .rows
+      .get_or_insert_with(|| parse_rows(&self.csv, funds.decimals));
+
+    let valid = rows.iter().filter(|row| row.parsed.is_ok()).count();
+    let total = sum(rows.iter()).unwrap_or(U256::MAX);
+    // What a retry still has to pay.
+    let unsent = rows.iter().filter(|row| row.unsent()).collect::<Vec<_>>();
+    let remaining = sum(unsent.iter().copied()).unwrap_or(U256::MAX);
+    let in_flight = rows.iter().any(Row::in_flight);
+    let amount = |value: U256| {
+      format!(
+        "{} {}",
+        format_units(value, u32::from(funds.decimals)).unwrap_or_default(),
+        funds.symbol
+      )
+    };
+
+    ui.separator();
+    egui::Grid::new("disperse_summary").show(ui, |ui| {
+      ui.label("보내는 주소");
+      ui.monospace(format!("{:#x}", funds.from));
+      ui.end_row();
+      ui.label("수신자");
+      ui.label(format!("{} / {}", valid, rows.len()));
+      ui.end_row();
+      ui.label("합계");
+      ui.label(amount(total));
+      ui.end_row();
+      if unsent.len() < rows.len() {
+        ui.label("남은 금액");
+        ui.label(amount(remaining));
+        ui.end_row();
+      }
+      ui.label("잔액");
+      let color = if remaining <= funds.balance {
+        egui::Color32::from_rgb(140, 140, 140)
+      } else {
+        egui::Color32::from_rgb(255, 0, 0)
+      };
+      ui.colored_label(color, amount(funds.balance));
+      ui.end_row();
+      if self.asset == Asset::Erc20 && self.mode == SendMode::Contract {
+        ui.label("승인액");
+        ui.label(amount(funds.allowance));
+        ui.end_row();
+      }
+    });
+    if self.asset == Asset::Native {
+      ui.label("가스비는 합계에 포함되지 않습니다");
+    }
+
+    egui::Grid::new("disperse_rows")
+      .striped(true)
+      .show(ui, |ui| {
+        ui.label("줄");
+        ui.label("주소");
+        ui.label("금액");
+        ui.label("상태");
+        ui.end_row();
+        for row in rows.iter() {
+          ui.label(row.line.to_string());
+          ui.monospace(&row.recipient);
+          ui.label(&row.amount);
+          match (&row.parsed, &row.status, &row.payment) {
+            (Err(e), _, _) => {
+              ui.colored_label(egui::Color32::from_rgb(255, 0, 0), e);
+            }
+            (Ok(_), _, Some(_)) => {
+              ui.label("대기 중");
+            }
+            (Ok(_), RowStatus::Unsent, None) => {
+              ui.label("");
+            }
+            (Ok(_), RowStatus::Pending(hash), None) => {
+              ui.monospace(format!("⏳ {:#x}", hash));
+            }
+            (Ok(_), RowStatus::Sent(hash), None) => {
+              ui.monospace(format!("{:#x}", hash));
+            }
+            (Ok(_), RowStatus::Failed(e), None) => {
+              ui.colored_label(ui.visuals().error_fg_color, e);
+            }
+          }
+          ui.end_row();
+        }
+      });
+
+    let Some(chain) = chain else {
+      return;
+    };
+    let ready = valid == rows.len()
+      && !unsent.is_empty()
+      && !in_flight
+      && remaining <= funds.balance
+      && signer.address() == Some(funds.from);
+    if signer.address() != Some(funds.from) {
+      ui.label("서명 지갑이 바뀌었습니다. 다시 검증하세요");
+    }
+
+    ui.horizontal(|ui| {
+      let needs_approval = self.asset == Asset::Erc20
+        && self.mode == SendMode::Contract
+        && funds.allowance < remaining;
+      if needs_approval
+        && ui
+          .add_enabled(ready, egui::Button::new("Disperse 컨트랙트 승인"))
+          .clicked()
+      {
+        if let Err(e) = self.approve(chain, remaining, signer) {
+          self.error = Some(e);
+        }
+      }
+
+      if ui
+        .add_enabled(ready && !needs_approval, egui::Button::new("보내기"))
+        .clicked()
+      {
+        if let Err(e) = self.send(chain, funds.from, signer) {
+          self.error = Some(e);
+        }
+      }
+    });
+  }
+
+  fn approve(
+    &self,
+    chain: &ChainSettings,
+    total: U256,
+    signer: &mut SignerWindow,
+  ) -> Result<(), String> {
+    let token = self.token()?;
+    let disperse = self.disperse()?;
+    let data = IERC20::new(token, provider(chain)?)
+      .approve(disperse, total)
+      .calldata()
+      .unwrap_or_default();
+
+    signer.request(TxRequest {
+      label: format!("approve({:#x}, {})", disperse, total),
+      rpc_url: chain.rpc_url.clone(),
+      to: token,
+      data,
+      value: U256::zero(),
+      outcome: None,
+    });
+    Ok(())
+  }
+
+  /// Queues the transfers with the signer; each row's status follows the
+  /// request that pays it, then that transaction's receipt. Rows paid or on
+  /// their way are skipped, so a retry only pays the ones that failed.
+  fn send(
+    &mut self,
+    chain: &ChainSettings,
+    from: Address,
+    signer: &mut SignerWindow,
+  ) -> Result<(), String> {
+    let token = self.token();
+    let disperse = self.disperse();
+    let Some(rows) = &mut self.rows else {
+      return Ok(());
+    };
+    let mut rows = rows
+      .iter_mut()
+      .filter(|row| row.parsed.is_ok() && row.unsent())
+      .collect::<Vec<_>>();
+    let payments = rows
+      .iter()
+      .filter_map(|row| row.parsed.clone().ok())
+      .collect::<Vec<_>>();
+
+    match self.mode {
+      SendMode::Sequential => {
+        let token = match self.asset {
+          Asset::Native => None,
+          Asset::Erc20 => Some(IERC20::new(token?, provider(chain)?)),
+        };
+
+        for (row, (recipient, amount)) in rows.iter_mut().zip(payments) {
+          let outcome: Outcome = Arc::new(Mutex::new(None));
+          row.payment = Some(Payment {
+            outcome: outcome.clone(),
+            from,
+            rpc_url: chain.rpc_url.clone(),
+          });
+
+          let (to, data, value) = match &token {
+            None => (recipient, Bytes::default(), amount),
+            Some(token) => (
+              token.address(),
+              token
+                .transfer(recipient, amount)
+                .calldata()
+                .unwrap_or_default(),
+              U256::zero(),
+            ),
+          };
+          signer.request(TxRequest {
+            label: format!("transfer({:#x}, {})", recipient, amount),
+            rpc_url: chain.rpc_url.clone(),
+            to,
+            data,
+            value,
+            outcome: Some(outcome),
+          });
+        }
+      }
+      SendMode::Contract => {
+        let contract = BaseContract::from(
+          parse_abi(&DISPERSE_ABI).map_err(|e| e.to_string())?,
+        );
+        let (recipients, amounts): (Vec<Address>, Vec<U256>) =
+          payments.into_iter().unzip();
+        let total = amounts
+          .iter()
+          .try_fold(U256::zero(), |total, amount| total.checked_add(*amount))
+          .ok_or_else(|| "합계가 uint256 범위를 넘습니다".to_string())?;
+
+        let (label, data, value) = match self.asset {
+          Asset::Native => (
+            format!("disperseEther({} 명)", recipients.len()),
+            contract.encode("disperseEther", (recipients, amounts)),
+            total,
+          ),
+          Asset::Erc20 => (
+            format!("disperseToken({} 명)", recipients.len()),
+            contract.encode("disperseToken", (token?, recipients, amounts)),
+            U256::zero(),
+          ),
+        };
+
+        let outcome: Outcome = Arc::new(Mutex::new(None));
+        for row in rows.iter_mut() {
+          row.payment = Some(Payment {
+            outcome: outcome.clone(),
+            from,
+            rpc_url: chain.rpc_url.clone(),
+          });
+        }
+        signer.request(TxRequest {
+          label,
+          rpc_url: chain.rpc_url.clone(),
+          to: disperse?,
+          data: data.map_err(|e| e.to_string())?,
+          value,
+          outcome: Some(outcome),
+        });
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Moves rows whose signer request finished to `Pending` (starting a
+  /// receipt poll for the transaction) or `Failed`, and settles pending rows
+  /// whose receipt came back.
+  fn sync_outcomes(&mut self) {
+    let Some(rows) = &mut self.rows else {
+      return;
+    };
+
+    for row in rows.iter_mut() {
+      if let Some(payment) = &row.payment {
+        let result = payment.outcome.lock().unwrap().clone();
+        if let Some(result) = result {
+          row.status = match result {
+            Ok(hash) => {
+              self.receipts.entry(hash).or_insert_with(|| Receipt {
+                rpc_url: payment.rpc_url.clone(),
+                from: payment.from,
+                poll: None,
+                next_poll: 0.0,
+                nonce: None,
+                unseen_polls: 0,
+                result: None,
+              });
+              RowStatus::Pending(hash)
+            }
+            Err(e) => RowStatus::Failed(e),
+          };
+          row.payment = None;
+        }
+      }
+
+      if let RowStatus::Pending(hash) = row.status {
+        match self.receipts.get(&hash).and_then(|r| r.result.clone()) {
+          Some(Ok(())) => row.status = RowStatus::Sent(hash),
+          Some(Err(e)) => row.status = RowStatus::Failed(e),
+          None => {}
+        }
+      }
+    }
+  }
+
+  /// Polls the receipts of broadcast transactions the way the transaction
+  /// history does; only a mined status 1 marks their rows sent.
+  fn poll_receipts(&mut self, ctx: &egui::Context) {
+    let now = ctx.input(|i| i.time);
+    let mut running = self
+      .receipts
+      .values()
+      .filter(|receipt| receipt.poll.is_some())
+      .count();
+    let mut waiting = false;
+
+    for (hash, receipt) in self.receipts.iter_mut() {
+      if receipt.result.is_some() {
+        continue;
+      }
+      waiting = true;
+
+      match receipt.poll.as_ref().map(Promise::ready) {
+        None if now >= receipt.next_poll && running < MAX_RECEIPT_POLLS => {
+          let (rpc_url, hash, from, nonce) =
+            (receipt.rpc_url.clone(), *hash, receipt.from, receipt.nonce);
+          receipt.poll = Some(Promise::spawn_local(async move {
+            tx_history::fetch_outcome(&rpc_url, hash, from, nonce).await
+          }));
+          running += 1;
+        }
+        Some(Some(_)) => {
+          match receipt.poll.take().and_then(|p| p.try_take().ok()) {
+            Some(Ok(tx_history::Outcome::Mined {
+              receipt: mined,
+              revert,
+            })) => {
+              receipt.result =
+                Some(if mined.status.map(|s| s.as_u64()) == Some(1) {
+                  Ok(())
+                } else {
+                  Err(match revert {
+                    Some(data) => tx_history::decode_revert(&data, &[]),
+                    None => "트랜잭션 실패".to_string(),
+                  })
+                });
+            }
+            Some(Ok(tx_history::Outcome::Waiting { nonce: Some(nonce) })) => {
+              receipt.nonce = Some(nonce);
+              receipt.unseen_polls = 0;
+            }
+            Some(Ok(tx_history::Outcome::Waiting { nonce: None })) => {
+              receipt.unseen_polls += 1;
+              if receipt.unseen_polls >= MAX_UNSEEN_POLLS {
+                receipt.result = Some(Err(
+                  "노드가 트랜잭션을 찾지 못합니다 (멤풀에서 제외됨)"
+                    .to_string(),
+                ));
+              }
+            }
+            Some(Ok(tx_history::Outcome::Replaced)) => {
+              receipt.result = Some(Err(
+                "같은 nonce의 다른 트랜잭션이 먼저 채굴되었습니다".to_string(),
+              ));
+            }
+            // An RPC error; the next poll tries again.
+            Some(Err(_)) | None => {}
+          }
+          receipt.next_poll = now + POLL_INTERVAL;
+          running -= 1;
+        }
+        _ => {}
+      }
+    }
+
+    if waiting {
+      ctx.request_repaint_after(std::time::Duration::from_secs_f64(
+        POLL_INTERVAL,
+      ));
+    }
+  }
+
+  fn token(&self) -> Result<Address, String> {
+    self
+      .token_address
+      .trim()
+      .parse::<Address>()
+      .map_err(|_| "잘못된 토큰 주소입니다".to_string())
+  }
+
+  fn disperse(&self) -> Result<Address, String> {
+    self
+      .disperse_address
+      .trim()
+      .parse::<Address>()
+      .map_err(|_| "잘못된 Disperse 주소입니다".to_string())
+  }
+}
+
+fn provider(chain: &ChainSettings) -> Result<Arc<RpcProvider>, String> {
+  rpc::provider(&chain.rpc_url)
+}
+
+/// Whether a CSV's first line is column names: none of its fields looks like
+/// data, i.e. starts with `0x` or is a number. A mistyped first row such as
+/// `0x12,1` stays a row, so it is reported instead of silently skipped.
+pub fn is_header(line: &str) -> bool {
+  line.split(',').map(str::trim).all(|field| {
+    !field
+      .get(..2)
+      .is_some_and(|prefix| prefix.eq_ignore_ascii_case("0x"))
+      && field.parse::<f64>().is_err()
+  })
+}
+
+/// Sum of the valid rows' amounts, `None` on overflow.
+fn sum<'a>(rows: impl Iterator<Item = &'a Row>) -> Option<U256> {
+  rows
+    .filter_map(|row| row.parsed.as_ref().ok())
+    .try_fold(U256::zero(), |total, (_, amount)| {
+      total.checked_add(*amount)
+    })
+}
+
+/// One row per non-empty line; the first one is skipped if [`is_header`].
+/// The row that would push the total past `uint256` is an error, so the
+/// valid rows always add up.
+pub fn parse_rows(csv: &str, decimals: u8) -> Vec<Row> {
+  let mut rows = csv
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.trim().is_empty())
+    .enumerate()
+    .filter(|(nth, (_, line))| *nth > 0 || !is_header(line))
+    .map(|(_, (i, line))| {
+      let mut fields = line.split(',').map(str::trim);
+      let recipient = fields.next().unwrap_or_default().to_string();
+      let amount = fields.next().unwrap_or_default().to_string();
+
+      let parsed = match (
+        recipient.parse::<Address>(),
+        parse_units(&amount, u32::from(decimals)),
+      ) {
+        (Err(_), _) => Err("잘못된 주소".to_string()),
+        (_, Err(_)) => Err("잘못된 금액".to_string()),
+        (_, Ok(ParseUnits::I256(_))) => Err("음수 금액".to_string()),
+        (Ok(_), Ok(ParseUnits::U256(amount))) if amount.is_zero() => {
+          Err("금액이 0입니다".to_string())
+        }
+        (Ok(address), Ok(ParseUnits::U256(amount))) => Ok((address, amount)),
+      };
+
+      Row {
+        line: i + 1,
+        recipient,
+        amount,
+        parsed,
+        status: RowStatus::Unsent,
+        payment: None,
+      }
+    })
+    .collect::<Vec<_>>();
+
+  let mut total = U256::zero();
+  for row in &mut rows {
+    if let Ok((_, amount)) = &row.parsed {
+      match total.checked_add(*amount) {
+        Some(sum) => total = sum,
+        None => row.parsed = Err("합계가 uint256 범위를 넘습니다".to_string()),
+      }
+    }
+  }
+
+  rows
+}
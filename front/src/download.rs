@@ -0,0 +1,50 @@
+#[cfg(target_arch = "wasm32")]
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+#[cfg(target_arch = "wasm32")]
+use web_sys::Blob;
+
+/// Hands `bytes` to the browser as a file download named `file_name`.
+#[cfg(target_arch = "wasm32")]
+pub fn save(file_name: &str, mime: &str, bytes: &[u8]) -> Result<(), String> {
+  let js = |e: JsValue| format!("다운로드 실패: {:?}", e);
+  let doc = web_sys::window()
+    .and_then(|window| window.document())
+    .ok_or("다운로드 실패: document가 없습니다")?;
+
+  let uint8arr = web_sys::js_sys::Uint8Array::new(
+    &unsafe { web_sys::js_sys::Uint8Array::view(bytes) }.into(),
+  );
+  let array = web_sys::js_sys::Array::new();
+  array.push(&uint8arr.buffer());
+
+  let blob = Blob::new_with_u8_array_sequence_and_options(
+    &array,
+    web_sys::BlobPropertyBag::new().type_(mime),
+  )
+  .map_err(js)?;
+
+  let blob_url =
+    web_sys::Url::create_object_url_with_blob(&blob).map_err(js)?;
+  let download_link: web_sys::HtmlAnchorElement =
+    doc.create_element("a").map_err(js)?.unchecked_into();
+  download_link.set_href(&blob_url);
+  download_link.set_download(file_name);
+  doc
+    .body()
+    .ok_or("다운로드 실패: body가 없습니다")?
+    .append_child(&download_link)
+    .map_err(js)?;
+  download_link.click();
+
+  Ok(())
+}
+
+/// Native builds have no browser to hand the file to.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(
+  _file_name: &str,
+  _mime: &str,
+  _bytes: &[u8],
+) -> Result<(), String> {
+  Err("파일 다운로드는 웹에서만 지원됩니다".to_string())
+}
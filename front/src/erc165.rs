@@ -0,0 +1,67 @@
+use shared::contract_abis::ierc721::IERC721;
+
+use crate::rpc::RpcProvider;
+
+/// Interfaces the ERC-165 probe asks about, in display order.
+pub const INTERFACES: [(&str, [u8; 4]); 6] = [
+  ("ERC-165", [0x01, 0xff, 0xc9, 0xa7]),
+  ("ERC-721", [0x80, 0xac, 0x58, 0xcd]),
+  ("ERC-721 Metadata", [0x5b, 0x5e, 0x13, 0x9f]),
+  ("ERC-721 Enumerable", [0x78, 0x0e, 0x9d, 0x63]),
+  ("ERC-2981 Royalties", [0x2a, 0x55, 0x20, 0x5a]),
+  ("ERC-4906 Metadata Update", [0x49, 0x06, 0x49, 0x06]),
+];
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct InterfaceSupport {
+  pub name: String,
+  pub interface_id: String,
+  /// `None` when `supportsInterface` reverted, i.e. the contract does not
+  /// implement ERC-165 at all.
+  pub supported: Option<bool>,
+}
+
+/// Asks `contract` about every entry in [`INTERFACES`]. Fails on the first
+/// call that errors for a reason other than a revert.
+pub async fn probe(
+  contract: IERC721<RpcProvider>,
+) -> Result<Vec<InterfaceSupport>, String> {
+  let mut results = Vec::with_capacity(INTERFACES.len());
+  for (name, interface_id) in INTERFACES {
+    let supported = match contract.supports_interface(interface_id).call().await
+    {
+      Ok(supported) => Some(supported),
+      Err(e) if e.is_revert() => None,
+      Err(e) => {
+        return Err(format!(
+          "supportsInterface({}): {}",
+          format_interface_id(interface_id),
+          e
+        ))
+      }
+    };
+    results.push(InterfaceSupport {
+      name: name.to_string(),
+      interface_id: format_interface_id(interface_id),
+      supported,
+    });
+  }
+  Ok(results)
+}
+
+pub fn format_interface_id(interface_id: [u8; 4]) -> String {
+  format!("0x{:08x}", u32::from_be_bytes(interface_id))
+}
+
+/// Accepts `0x80ac58cd` or `80ac58cd`.
+pub fn parse_interface_id(value: &str) -> Result<[u8; 4], String> {
+  let hex = value.trim();
+  let hex = hex.strip_prefix("0x").unwrap_or(hex);
+  if hex.len() != 8 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+    return Err(format!("잘못된 인터페이스 ID: {}", value));
+  }
+
+  u32::from_str_radix(hex, 16)
+    .map(u32::to_be_bytes)
+    .map_err(|_| format!("잘못된 인터페이스 ID: {}", value))
+}
@@ -0,0 +1,401 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::{Arc, Mutex};
+
+use ethers::{
+  contract::LogMeta,
+  providers::Middleware,
+  types::{Address, BlockId, H256, U256},
+};
+use poll_promise::Promise;
+use shared::contract_abis::ierc721::{TransferFilter, IERC721};
+
+use crate::rpc::{self, RpcProvider};
+
+/// Largest block range asked for in one `eth_getLogs`.
+const LOG_RANGE: u64 = 2000;
+
+/// Most token IDs one `ownerOf` snapshot calls; each is an RPC round trip.
+/// Larger collections are better replayed from `Transfer` logs.
+pub const MAX_OWNER_OF_TOKENS: u64 = 100_000;
+
+/// Holder address → token IDs it owns.
+pub type Holders = BTreeMap<Address, BTreeSet<U256>>;
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum SnapshotMode {
+  /// `ownerOf` for every token ID in a range.
+  OwnerOf,
+  /// Replay `Transfer` logs from the deployment block.
+  Transfers,
+}
+
+pub struct Snapshot {
+  pub block: u64,
+  pub holders: Holders,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Progress {
+  done: u64,
+  total: u64,
+}
+
+/// "Who holds what at block N" for one ERC-721 contract, exported as CSV.
+pub struct HolderSnapshot {
+  pub mode: SnapshotMode,
+  /// Empty means the latest block at the time the snapshot starts.
+  pub block: String,
+  pub first_token_id: String,
+  pub last_token_id: String,
+  pub from_block: String,
+
+  progress: Arc<Mutex<Progress>>,
+  result: Option<Promise<Result<Snapshot, String>>>,
+  download_error: Option<String>,
+}
+
+impl Default for HolderSnapshot {
+  fn default() -> Self {
+    Self {
+      mode: SnapshotMode::OwnerOf,
+      block: "".to_string(),
+      first_token_id: "0".to_string(),
+      last_token_id: "".to_string(),
+      from_block: "0".to_string(),
+
+      progress: Arc::new(Mutex::new(Progress::default())),
+      result: None,
+      download_error: None,
+    }
+  }
+}
+
+impl HolderSnapshot {
+  pub fn ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    contract_name: &str,
+    contract_address: &str,
+    rpc_url: &str,
+  ) {
+    ui.horizontal(|ui| {
+      ui.radio_value(&mut self.mode, SnapshotMode::OwnerOf, "ownerOf 조회");
+      ui.radio_value(
+        &mut self.mode,
+        SnapshotMode::Transfers,
+        "Transfer 이벤트",
+      );
+    });
+
+    ui.horizontal(|ui| {
+      ui.label("기준 블록 (비우면 최신)");
+      ui.text_edit_singleline(&mut self.block);
+    });
+
+    match self.mode {
+      SnapshotMode::OwnerOf => {
+        ui.horizontal(|ui| {
+          ui.label("토큰 ID 범위");
+          ui.text_edit_singleline(&mut self.first_token_id);
+          ui.label("~");
+          ui.text_edit_singleline(&mut self.last_token_id);
+        });
+      }
+      SnapshotMode::Transfers => {
+        ui.horizontal(|ui| {
+          ui.label("시작 블록 (배포 블록)");
+          ui.text_edit_singleline(&mut self.from_block);
+        });
+      }
+    }
+
+    let running = self
+      .result
+      .as_ref()
+      .is_some_and(|result| result.ready().is_none());
+
+    if ui
+      .add_enabled(!running, egui::Button::new("스냅샷 만들기"))
+      .clicked()
+    {
+      self.start(contract_address, rpc_url);
+    }
+
+    let Some(result) = &self.result else {
+      return;
+    };
+
+    match result.ready() {
+      None => {
+        let progress = *self.progress.lock().unwrap();
+        ui.horizontal(|ui| {
+          ui.spinner();
+          ui.label(format!("{} / {}", progress.done, progress.total));
+        });
+        if progress.total > 0 {
+          ui.add(egui::ProgressBar::new(
+            progress.done as f32 / progress.total as f32,
+          ));
+        }
+      }
+      Some(Ok(snapshot)) => {
+        let tokens: usize = snapshot.holders.values().map(BTreeSet::len).sum();
+        ui.label(format!(
+          "블록 {}: 홀더 {}명, 토큰 {}개",
+          snapshot.block,
+          snapshot.holders.len(),
+          tokens
+        ));
+        if ui.button("CSV 다운로드").clicked() {
+          self.download_error = crate::download::save(
+            &format!("{}-holders-{}.csv", contract_name, snapshot.block),
+            "text/csv",
+            to_csv(&snapshot.holders).as_bytes(),
+          )
+          .err();
+        }
+        if let Some(e) = &self.download_error {
+          ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+      }
+      Some(Err(e)) => {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+      }
+    }
+  }
+
+  fn start(&mut self, contract_address: &str, rpc_url: &str) {
+    self.progress = Arc::new(Mutex::new(Progress::default()));
+
+    let request = match self.request(contract_address, rpc_url) {
+      Ok(request) => request,
+      Err(e) => {
+        self.result = Some(Promise::from_ready(Err(e)));
+        return;
+      }
+    };
+
+    let progress = self.progress.clone();
+    self.result = Some(Promise::spawn_local(request.run(progress)));
+  }
+
+  fn request(
+    &self,
+    contract_address: &str,
+    rpc_url: &str,
+  ) -> Result<Request, String> {
+    let address = contract_address
+      .parse::<Address>()
+      .map_err(|_| format!("잘못된 컨트랙트 주소: {}", contract_address))?;
+    let provider = rpc::provider(rpc_url)?;
+
+    let block = match self.block.trim() {
+      "" => None,
+      block => Some(parse_block(block)?),
+    };
+
+    let range = match self.mode {
+      SnapshotMode::OwnerOf => {
+        let (first, last) =
+          token_range(&self.first_token_id, &self.last_token_id)?;
+        Range::TokenIds(first, last)
+      }
+      SnapshotMode::Transfers => Range::Blocks(parse_block(&self.from_block)?),
+    };
+
+    Ok(Request {
+      contract: IERC721::new(address, provider),
+      block,
+      range,
+    })
+  }
+}
+
+enum Range {
+  TokenIds(U256, U256),
+  /// Replay from this block up to the snapshot block.
+  Blocks(u64),
+}
+
+struct Request {
+  contract: IERC721<RpcProvider>,
+  block: Option<u64>,
+  range: Range,
+}
+
+impl Request {
+  async fn run(
+    self,
+    progress: Arc<Mutex<Progress>>,
+  ) -> Result<Snapshot, String> {
+    // Pin "latest" to a number so every call below sees the same state.
+    let block = match self.block {
+      Some(block) => block,
+      None => self
+        .contract
+        .client_ref()
+        .get_block_number()
+        .await
+        .map_err(|e| e.to_string())?
+        .as_u64(),
+    };
+
+    let holders = match self.range {
+      Range::TokenIds(first, last) => {
+        owners_of(&self.contract, block, first, last, &progress).await?
+      }
+      Range::Blocks(from) => {
+        if from > block {
+          return Err(format!(
+            "시작 블록 {}이 기준 블록 {}보다 큽니다",
+            from, block
+          ));
+        }
+        replay_transfers(&self.contract, from, block, &progress).await?
+      }
+    };
+
+    Ok(Snapshot { block, holders })
+  }
+}
+
+/// Calls `ownerOf` for each token in `first..=last` at `block`. Tokens whose
+/// call reverts (never minted or burned) are left out.
+async fn owners_of(
+  contract: &IERC721<RpcProvider>,
+  block: u64,
+  first: U256,
+  last: U256,
+  progress: &Mutex<Progress>,
+) -> Result<Holders, String> {
+  // `token_range` keeps the span within `MAX_OWNER_OF_TOKENS`.
+  progress.lock().unwrap().total = (last - first).as_u64() + 1;
+
+  let mut holders = Holders::new();
+  let mut token_id = first;
+  loop {
+    let call = contract.owner_of(token_id).block(BlockId::from(block));
+    // A revert means the token does not exist (yet).
+    let owner = match call.call().await {
+      Ok(owner) => Some(owner),
+      Err(e) if e.is_revert() => None,
+      Err(e) => return Err(format!("ownerOf({}): {}", token_id, e)),
+    };
+
+    if let Some(owner) = owner.filter(|owner| !owner.is_zero()) {
+      holders.entry(owner).or_default().insert(token_id);
+    }
+    progress.lock().unwrap().done += 1;
+
+    if token_id == last {
+      return Ok(holders);
+    }
+    token_id += U256::one();
+  }
+}
+
+/// Follows every `Transfer` in `from..=to`; each token ends up with the
+/// recipient of its last transfer. Burned tokens (sent to the zero address)
+/// are dropped.
+async fn replay_transfers(
+  contract: &IERC721<RpcProvider>,
+  from: u64,
+  to: u64,
+  progress: &Mutex<Progress>,
+) -> Result<Holders, String> {
+  progress.lock().unwrap().total = to - from + 1;
+
+  let mut replay = Replay::default();
+  let mut start = from;
+  while start <= to {
+    let end = to.min(start + LOG_RANGE - 1);
+    let event = contract.transfer_filter().from_block(start).to_block(end);
+    let logs = event
+      .query_with_meta()
+      .await
+      .map_err(|e| format!("Transfer 로그 {}~{}: {}", start, end, e))?;
+
+    replay.push(logs);
+
+    progress.lock().unwrap().done = end - from + 1;
+    start = end + 1;
+  }
+
+  Ok(replay.holders())
+}
+
+/// Latest owner of each token over `Transfer` logs fed in block order.
+#[derive(Default)]
+pub struct Replay {
+  seen: HashSet<(H256, U256)>,
+  owners: BTreeMap<U256, Address>,
+}
+
+impl Replay {
+  /// Applies one `eth_getLogs` range.
+  pub fn push(&mut self, mut logs: Vec<(TransferFilter, LogMeta)>) {
+    logs.sort_by_key(|(_, meta)| (meta.block_number, meta.log_index));
+    for (transfer, meta) in logs {
+      // Some nodes repeat logs across adjacent ranges.
+      if self.seen.insert((meta.transaction_hash, meta.log_index)) {
+        self.owners.insert(transfer.token_id, transfer.to);
+      }
+    }
+  }
+
+  pub fn holders(self) -> Holders {
+    let mut holders = Holders::new();
+    for (token_id, owner) in self.owners {
+      if !owner.is_zero() {
+        holders.entry(owner).or_default().insert(token_id);
+      }
+    }
+    holders
+  }
+}
+
+/// `holder,count,token_ids` with the token IDs space-separated.
+pub fn to_csv(holders: &Holders) -> String {
+  let mut csv = "holder,count,token_ids\n".to_string();
+  for (holder, token_ids) in holders {
+    let ids = token_ids
+      .iter()
+      .map(U256::to_string)
+      .collect::<Vec<_>>()
+      .join(" ");
+    csv.push_str(&format!("{:#x},{},{}\n", holder, token_ids.len(), ids));
+  }
+  csv
+}
+
+fn parse_block(block: &str) -> Result<u64, String> {
+  block
+    .trim()
+    .parse()
+    .map_err(|_| format!("잘못된 블록 번호: {}", block))
+}
+
+/// Parses the `first..=last` token IDs of an `ownerOf` snapshot, at most
+/// `MAX_OWNER_OF_TOKENS` of them.
+pub fn token_range(first: &str, last: &str) -> Result<(U256, U256), String> {
+  let first = parse_token_id(first)?;
+  let last = parse_token_id(last)?;
+  if first > last {
+    return Err(format!(
+      "토큰 ID 범위가 잘못되었습니다: {} ~ {}",
+      first, last
+    ));
+  }
+  if last - first >= U256::from(MAX_OWNER_OF_TOKENS) {
+    return Err(format!(
+      "ownerOf 조회는 한 번에 최대 {}개 토큰까지 할 수 있습니다. Transfer 로그 방식을 사용하세요",
+      MAX_OWNER_OF_TOKENS
+    ));
+  }
+  Ok((first, last))
+}
+
+fn parse_token_id(token_id: &str) -> Result<U256, String> {
+  U256::from_dec_str(token_id.trim())
+    .map_err(|_| format!("잘못된 토큰 ID: {}", token_id))
+}
@@ -1,9 +1,20 @@
+pub mod airdrop;
+mod api;
 mod app;
-mod chain_settings;
+pub mod chain_settings;
+pub mod disperse;
+mod download;
+pub mod erc165;
+pub mod holder_snapshot;
 mod metadata;
+pub mod rpc;
+mod server_settings;
+pub mod signer;
+pub mod token_inspector;
+pub mod tx_history;
 mod wallet_balance;
 pub use app::ToyApp;
-mod contract_abis;
+pub mod contract_abi;
 mod contract_erc20;
 mod contract_erc721;
 mod wallet_settings;
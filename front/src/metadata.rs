@@ -1,10 +1,15 @@
 use std::io::Write;
 
-use web_sys::wasm_bindgen::JsCast;
-use web_sys::Blob;
+use poll_promise::Promise;
+use shared::collection::{CollectionFields, NewCollection};
+use shared::job::JobRequest;
+use shared::metadata::{numbered, Attribute, Metadata};
 use zip::write::FileOptions;
 use zip::ZipWriter;
 
+use crate::api::{ApiClient, ApiError, JobWatcher};
+use crate::server_settings::ServerSettingsWindow;
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct SingleMetadataWindow {
@@ -15,6 +20,15 @@ pub struct SingleMetadataWindow {
   pub metadata_count: usize,
 
   pub show_metadata: bool,
+
+  /// Collection the metadata is uploaded under on the server.
+  pub slug: String,
+  #[serde(skip)]
+  upload: Option<Promise<Result<usize, ApiError>>>,
+  #[serde(skip)]
+  job: Option<JobWatcher>,
+  #[serde(skip)]
+  download_error: Option<String>,
 }
 
 impl Default for SingleMetadataWindow {
@@ -25,6 +39,10 @@ impl Default for SingleMetadataWindow {
       metadata: Metadata::default(),
       metadata_count: 0,
       show_metadata: false,
+      slug: "".to_string(),
+      upload: None,
+      job: None,
+      download_error: None,
     }
   }
 }
@@ -34,7 +52,16 @@ impl SingleMetadataWindow {
     ui.checkbox(&mut self.is_open, "단일 메타데이터");
   }
 
-  pub fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+  pub fn update(
+    &mut self,
+    ctx: &egui::Context,
+    _frame: &mut eframe::Frame,
+    server_settings: &ServerSettingsWindow,
+  ) {
+    if let Some(job) = &mut self.job {
+      job.update(ctx);
+    }
+
     egui::Window::new("단일 메타데이터 만들기")
       .open(&mut self.is_open)
       .vscroll(true)
@@ -104,21 +131,21 @@ impl SingleMetadataWindow {
                   .as_str(),
               );
             }
-            fields.push_str("\n");
+            fields.push('\n');
 
             csv_data.extend_from_slice(fields.as_bytes());
 
-            for i in 0..self.metadata_count {
-              let mut metadata = self.metadata.clone();
-              metadata.name = format!("{} #{}", metadata.name, i);
-
+            for (i, metadata) in tokens(&self.metadata, self.metadata_count)
+              .iter()
+              .enumerate()
+            {
               let metadata_json = serde_json::to_string(&metadata).unwrap();
               let bytes = metadata_json.as_bytes();
 
               zip
                 .start_file(format!("{}.json", i), FileOptions::default())
                 .unwrap();
-              zip.write_all(&bytes).unwrap();
+              zip.write_all(bytes).unwrap();
             }
 
             let mut csv_line = format!(
@@ -137,7 +164,7 @@ impl SingleMetadataWindow {
                 .as_str(),
               );
             }
-            csv_line.push_str("\n");
+            csv_line.push('\n');
 
             csv_data.extend_from_slice(csv_line.as_bytes());
 
@@ -151,77 +178,132 @@ impl SingleMetadataWindow {
 
             let metadata_files = zip.finish().unwrap().into_inner();
 
-            // #[cfg(target_arch = "wasm32")]
-            // {
-            let window = web_sys::window().unwrap();
-            let doc = window.document().unwrap();
+            self.download_error = crate::download::save(
+              &format!("{}.zip", self.project_name),
+              "application/octet-stream",
+              metadata_files,
+            )
+            .err();
+          }
+        });
+        if let Some(e) = &self.download_error {
+          ui.colored_label(ui.visuals().error_fg_color, e);
+        }
 
-            let uint8arr = web_sys::js_sys::Uint8Array::new(
-              &unsafe {
-                web_sys::js_sys::Uint8Array::view(metadata_files.by_ref())
-              }
-              .into(),
-            );
-            let array = web_sys::js_sys::Array::new();
-            array.push(&uint8arr.buffer());
+        ui.separator();
 
-            let blob = Blob::new_with_u8_array_sequence_and_options(
-              &array,
-              web_sys::BlobPropertyBag::new().type_("application/octet-stream"),
-            )
-            .unwrap();
-
-            let blob_url =
-              web_sys::Url::create_object_url_with_blob(&blob).unwrap();
-            let download_link = doc.create_element("a").unwrap();
-            let download_link: web_sys::HtmlAnchorElement =
-              download_link.unchecked_into();
-            download_link.set_href(&blob_url);
-            download_link
-              .set_download(format!("{}.zip", self.project_name).as_str());
-            doc.body().unwrap().append_child(&download_link).unwrap();
-            download_link.click();
-            // }
+        ui.horizontal(|ui| {
+          ui.label("collection slug:");
+          ui.text_edit_singleline(&mut self.slug);
+        });
+
+        let uploading = self
+          .upload
+          .as_ref()
+          .is_some_and(|upload| upload.ready().is_none());
+
+        ui.vertical_centered(|ui| {
+          if ui
+            .add_enabled(!uploading, egui::Button::new("Upload to server"))
+            .clicked()
+          {
+            self.upload = Some(upload(
+              server_settings.client(),
+              self.slug.trim().to_string(),
+              self.project_name.clone(),
+              tokens(&self.metadata, self.metadata_count),
+            ));
+          }
+        });
+
+        if let Some(upload) = &self.upload {
+          match upload.ready() {
+            None => {
+              ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("업로드 중...");
+              });
+            }
+            Some(Ok(count)) => {
+              ui.label(format!("{}개 업로드 완료", count));
+            }
+            Some(Err(e)) => {
+              ui.colored_label(ui.visuals().error_fg_color, e.to_string());
+            }
+          }
+        }
+
+        ui.separator();
+
+        ui.label(
+          "많은 수의 메타데이터는 서버에서 만들어 zip으로 받을 수 있습니다",
+        );
+        ui.label("collection slug가 있으면 서버에도 저장됩니다");
+
+        let generating = self.job.as_ref().is_some_and(JobWatcher::is_active);
+
+        ui.vertical_centered(|ui| {
+          if ui
+            .add_enabled(!generating, egui::Button::new("Generate on server"))
+            .clicked()
+          {
+            let slug = self.slug.trim();
+            let request = JobRequest::Generate {
+              project_name: self.project_name.clone(),
+              template: self.metadata.clone(),
+              count: self.metadata_count as u32,
+              slug: (!slug.is_empty()).then(|| slug.to_string()),
+            };
+            self.job =
+              Some(JobWatcher::submit(server_settings.client(), request));
           }
         });
+
+        if let Some(job) = &self.job {
+          job.ui(ui);
+        }
       });
   }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
-#[serde(default)]
-struct Attribute {
-  trait_type: String,
-  value: String,
-}
+/// Creates the collection if the server does not know `slug` yet, then
+/// stores each token's metadata under it. Large collections run into the
+/// server's rate limit; [`ApiClient`] waits those out.
+fn upload(
+  client: ApiClient,
+  slug: String,
+  project_name: String,
+  metadata: Vec<Metadata>,
+) -> Promise<Result<usize, ApiError>> {
+  Promise::spawn_local(async move {
+    match client.get_collection(&slug).await {
+      Ok(_) => {}
+      Err(ApiError::Server { status: 404, .. }) => {
+        let new = NewCollection {
+          slug: slug.clone(),
+          fields: CollectionFields {
+            name: project_name,
+            ..CollectionFields::default()
+          },
+        };
+        client.create_collection(&new).await?;
+      }
+      Err(e) => return Err(e),
+    }
 
-impl Default for Attribute {
-  fn default() -> Self {
-    Self {
-      trait_type: "".to_string(),
-      value: "".to_string(),
+    for (token_id, metadata) in metadata.iter().enumerate() {
+      client
+        .put_token_metadata(&slug, token_id as u64, metadata)
+        .await?;
     }
-  }
-}
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
-#[serde(default)]
-struct Metadata {
-  pub name: String,
-  pub description: String,
-  pub image: String,
-  pub attributes: Vec<Attribute>,
+    Ok(metadata.len())
+  })
 }
 
-impl Default for Metadata {
-  fn default() -> Self {
-    Self {
-      name: "".to_string(),
-      description: "".to_string(),
-      image: "".to_string(),
-      attributes: Vec::new(),
-    }
-  }
+/// One copy of `template` per token, numbered from 0.
+fn tokens(template: &Metadata, count: usize) -> Vec<Metadata> {
+  (0..count as u32).map(|i| numbered(template, i)).collect()
 }
 
 // impl serde::Serialize for Metadata {
@@ -240,33 +322,3 @@ impl Default for Metadata {
 //     metadata.serialize(serializer)
 //   }
 // }
-
-// let mut headers = BTreeMap::new();
-//   headers.insert(
-//     "Content-Type".to_string(),
-//     "application/json".to_string(),
-// );
-
-// let request = ehttp::Request {
-//   method: "POST".to_string(),
-//   url: "http://localhost:3001/json".to_string(),
-//   headers,
-//   body: metadata_json.as_bytes().to_vec(),
-// };
-
-// let bytes = metadata_json.as_bytes();
-
-// let uint8arr = web_sys::js_sys::Uint8Array::new(
-//   &unsafe { web_sys::js_sys::Uint8Array::view(&bytes) }.into(),
-// );
-// let array = web_sys::js_sys::Array::new();
-// array.push(&uint8arr.buffer());
-
-// let blob = Blob::new_with_str_sequence(&array).unwrap();
-
-// let blob_url = Url::create_object_url_with_blob(&blob).unwrap();
-// ui.ctx().open_url(egui::OpenUrl {
-//   url: blob_url,
-//   new_tab: true,
-// });
-// ehttp::fetch(request, move |_| {});
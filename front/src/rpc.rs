@@ -0,0 +1,562 @@
+//! Shared RPC endpoints. A chain may list several RPC URLs; the provider
+//! handed out for it tries them in order of health, fails over on transport
+//! errors and retries with backoff per [`RpcConfig`]. Signed transactions
+//! are the exception: they go to one endpoint, once. One provider per chain
+//! is cached and shared by every window.
+
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ethers::providers::{
+  Http, HttpClientError, JsonRpcClient, JsonRpcError, Provider, ProviderError,
+  RpcError,
+};
+use ethers::types::{Bytes, H256, U64};
+use ethers::utils::keccak256;
+use futures_timer::Delay;
+use futures_util::future::{select, Either};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::chain_settings::ChainSettings;
+
+pub type RpcProvider = Provider<Failover>;
+
+/// Blocks an endpoint may trail the best one before it counts as lagging.
+pub const MAX_LAG: u64 = 10;
+
+/// Reads of the state at a block, with the position of their block
+/// parameter.
+const BLOCK_PARAMS: [(&str, usize); 6] = [
+  ("eth_call", 1),
+  ("eth_getBalance", 1),
+  ("eth_getCode", 1),
+  ("eth_getStorageAt", 2),
+  ("eth_getTransactionCount", 1),
+  ("eth_getBlockByNumber", 0),
+];
+
+/// Timeout and retry policy shared by every provider.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct RpcConfig {
+  /// How long one endpoint gets to answer one request, in seconds.
+  pub timeout_secs: u64,
+  /// Extra rounds over the endpoints after every one of them failed.
+  pub retries: u32,
+  /// Pause before the first retry, in milliseconds; doubles each round.
+  pub backoff_ms: u64,
+}
+
+impl Default for RpcConfig {
+  fn default() -> Self {
+    Self {
+      timeout_secs: 15,
+      retries: 2,
+      backoff_ms: 500,
+    }
+  }
+}
+
+impl RpcConfig {
+  fn timeout(&self) -> Duration {
+    Duration::from_secs(self.timeout_secs.max(1))
+  }
+
+  fn backoff(&self, retry: u32) -> Duration {
+    Duration::from_millis(self.backoff_ms.saturating_mul(1 << retry.min(16)))
+  }
+
+  pub fn ui(&mut self, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+      ui.label("타임아웃(초)");
+      ui.add(egui::DragValue::new(&mut self.timeout_secs).clamp_range(1..=120));
+      ui.label("재시도");
+      ui.add(egui::DragValue::new(&mut self.retries).clamp_range(0..=10));
+      ui.label("백오프(ms)");
+      ui.add(
+        egui::DragValue::new(&mut self.backoff_ms)
+          .clamp_range(0..=10_000)
+          .speed(10),
+      );
+    });
+  }
+}
+
+/// What the last probe (or request) learned about an endpoint.
+#[derive(Clone, Debug, Default)]
+pub struct Health {
+  pub chain_id: Option<u64>,
+  pub block_number: Option<u64>,
+  pub latency_ms: Option<u64>,
+  pub error: Option<String>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+  url: String,
+  http: Http,
+  health: Mutex<Health>,
+}
+
+/// A chain's endpoints and the provider everything on that chain shares.
+struct Route {
+  urls: Vec<String>,
+  chain_id: Option<u64>,
+  quorum: usize,
+  /// Whether the route comes from chain settings rather than from a URL
+  /// nothing lists.
+  listed: bool,
+  provider: Result<Arc<RpcProvider>, String>,
+}
+
+impl Route {
+  fn new(
+    urls: Vec<String>,
+    chain_id: Option<u64>,
+    quorum: usize,
+    listed: bool,
+    config: &RpcConfig,
+  ) -> Self {
+    let endpoints: Vec<_> =
+      urls.iter().filter_map(|url| endpoint(url)).collect();
+    let provider = if endpoints.is_empty() {
+      Err(format!("잘못된 RPC URL: {}", urls[0]))
+    } else {
+      Ok(Arc::new(Provider::new(Failover {
+        endpoints,
+        chain_id,
+        quorum,
+        config: config.clone(),
+      })))
+    };
+
+    Self {
+      urls,
+      chain_id,
+      quorum,
+      listed,
+      provider,
+    }
+  }
+
+  fn serves(&self, chain: &ChainSettings) -> bool {
+    self.listed
+      && self.urls == chain.rpc_urls()
+      && self.chain_id == chain.chain_id
+      && self.quorum == chain.quorum.max(1)
+  }
+}
+
+static ENDPOINTS: Mutex<Vec<Arc<Endpoint>>> = Mutex::new(Vec::new());
+// Lock order: CONFIG before ROUTES.
+static CONFIG: Mutex<Option<RpcConfig>> = Mutex::new(None);
+static ROUTES: Mutex<Vec<Route>> = Mutex::new(Vec::new());
+
+/// Makes `chains` and `config` current. Cached providers survive as long as
+/// their chain's endpoints and the config stay the same.
+pub fn register(chains: &[ChainSettings], config: &RpcConfig) {
+  let mut current = CONFIG.lock().unwrap();
+  let mut routes = ROUTES.lock().unwrap();
+  if current.as_ref() != Some(config) {
+    *current = Some(config.clone());
+    routes.clear();
+  }
+
+  let listed = routes.iter().filter(|route| route.listed).count();
+  if listed == chains.len()
+    && routes
+      .iter()
+      .zip(chains)
+      .all(|(route, chain)| route.serves(chain))
+  {
+    return;
+  }
+
+  let mut old = std::mem::take(&mut *routes);
+  for chain in chains {
+    let route = match old.iter().position(|route| route.serves(chain)) {
+      Some(i) => old.swap_remove(i),
+      None => Route::new(
+        chain.rpc_urls(),
+        chain.chain_id,
+        chain.quorum.max(1),
+        true,
+        config,
+      ),
+    };
+    routes.push(route);
+  }
+  routes.extend(old.into_iter().filter(|route| !route.listed));
+}
+
+/// Shared provider for the chain that lists `rpc_url`, failing over to that
+/// chain's other endpoints. A URL no chain lists gets a provider of its own.
+pub fn provider(rpc_url: &str) -> Result<Arc<RpcProvider>, String> {
+  let config = CONFIG.lock().unwrap().clone().unwrap_or_default();
+  let mut routes = ROUTES.lock().unwrap();
+  if let Some(route) = routes
+    .iter()
+    .find(|route| route.urls.iter().any(|url| url == rpc_url))
+  {
+    return route.provider.clone();
+  }
+
+  let route = Route::new(vec![rpc_url.to_string()], None, 1, false, &config);
+  let provider = route.provider.clone();
+  routes.push(route);
+  provider
+}
+
+/// Last known health of `url`, if it has been used or probed.
+pub fn health(url: &str) -> Option<Health> {
+  ENDPOINTS
+    .lock()
+    .unwrap()
+    .iter()
+    .find(|endpoint| endpoint.url == url)
+    .map(|endpoint| endpoint.health.lock().unwrap().clone())
+}
+
+/// Asks `url` for its chain ID and latest block, timing the block query.
+pub async fn probe(url: String) {
+  let Some(endpoint) = endpoint(&url) else {
+    return;
+  };
+
+  let result = async {
+    let chain_id: U64 = endpoint.http.request("eth_chainId", ()).await?;
+    let started = now_ms();
+    let block_number: U64 =
+      endpoint.http.request("eth_blockNumber", ()).await?;
+    Ok::<_, HttpClientError>((chain_id, block_number, now_ms() - started))
+  }
+  .await;
+
+  let mut health = endpoint.health.lock().unwrap();
+  match result {
+    Ok((chain_id, block_number, latency)) => {
+      *health = Health {
+        chain_id: Some(chain_id.as_u64()),
+        block_number: Some(block_number.as_u64()),
+        latency_ms: Some(latency as u64),
+        error: None,
+      };
+    }
+    Err(e) => health.error = Some(e.to_string()),
+  }
+}
+
+fn endpoint(url: &str) -> Option<Arc<Endpoint>> {
+  let mut endpoints = ENDPOINTS.lock().unwrap();
+  if let Some(endpoint) = endpoints.iter().find(|endpoint| endpoint.url == url)
+  {
+    return Some(endpoint.clone());
+  }
+
+  let endpoint = Arc::new(Endpoint {
+    url: url.to_string(),
+    http: Http::from_str(url).ok()?,
+    health: Mutex::default(),
+  });
+  endpoints.push(endpoint.clone());
+  Some(endpoint)
+}
+
+/// `None` when `future` takes longer than `timeout`.
+async fn timeout<F: Future>(future: F, timeout: Duration) -> Option<F::Output> {
+  let future = std::pin::pin!(future);
+  match select(future, Delay::new(timeout)).await {
+    Either::Left((output, _)) => Some(output),
+    Either::Right(_) => None,
+  }
+}
+
+/// Whether a read is pinned to a block number or hash, the only reads put to
+/// a quorum. At "latest" or "pending" healthy nodes may be a block apart, and
+/// a receipt may not have reached every node yet.
+pub fn is_pinned(method: &str, params: &Value) -> bool {
+  if method == "eth_getLogs" {
+    let filter = &params[0];
+    return filter.get("blockHash").is_some()
+      || (is_block(&filter["fromBlock"]) && is_block(&filter["toBlock"]));
+  }
+  BLOCK_PARAMS
+    .iter()
+    .find(|(name, _)| *name == method)
+    .is_some_and(|(_, i)| is_block(&params[*i]))
+}
+
+/// A block number or hash rather than a tag (or nothing, meaning "latest").
+fn is_block(param: &Value) -> bool {
+  match param {
+    Value::String(block) => block.starts_with("0x"),
+    // EIP-1898 `{ "blockHash": .. }` or `{ "blockNumber": .. }`.
+    Value::Object(block) => {
+      block.contains_key("blockHash")
+        || block.get("blockNumber").is_some_and(is_block)
+    }
+    _ => false,
+  }
+}
+
+/// Order to try endpoints with these healths in, as indices, best first:
+/// healthy and caught up before failing or lagging, then by latency.
+/// Endpoints that answered with another chain's ID than `chain_id` are left
+/// out altogether.
+pub fn rank(healths: &[Health], chain_id: Option<u64>) -> Vec<usize> {
+  let tip = healths
+    .iter()
+    .filter_map(|health| health.block_number)
+    .max()
+    .unwrap_or_default();
+
+  let mut ranked: Vec<_> = healths
+    .iter()
+    .enumerate()
+    .filter(|(_, health)| match (chain_id, health.chain_id) {
+      (Some(expected), Some(actual)) => expected == actual,
+      _ => true,
+    })
+    .map(|(i, health)| {
+      let degraded = health.error.is_some()
+        || health
+          .block_number
+          .is_some_and(|block_number| block_number + MAX_LAG < tip);
+      let latency = health.latency_ms.unwrap_or(u64::MAX);
+      ((degraded, latency), i)
+    })
+    .collect();
+  ranked.sort_by_key(|(key, _)| *key);
+  ranked.into_iter().map(|(_, i)| i).collect()
+}
+
+/// The node already has this very tx in its pool, say gossiped from another
+/// node; it is on its way all the same.
+fn is_already_known(e: &JsonRpcError) -> bool {
+  let message = e.message.to_lowercase();
+  message.contains("already known") || message.contains("known transaction")
+}
+
+/// Rate limiting is reported as a JSON-RPC error by some providers; unlike
+/// other JSON-RPC errors it is worth trying elsewhere or later.
+fn is_rate_limited(e: &JsonRpcError) -> bool {
+  e.code == 429
+    || e.code == -32005
+    || e.message.to_lowercase().contains("rate limit")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+  web_sys::js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+  std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+    .unwrap_or_default()
+}
+
+/// JSON-RPC transport over one chain's endpoints.
+#[derive(Debug)]
+pub struct Failover {
+  endpoints: Vec<Arc<Endpoint>>,
+  chain_id: Option<u64>,
+  quorum: usize,
+  config: RpcConfig,
+}
+
+impl Failover {
+  /// Endpoints worth trying, best first; see [`rank`].
+  fn ranked(&self) -> Vec<Arc<Endpoint>> {
+    let healths: Vec<Health> = self
+      .endpoints
+      .iter()
+      .map(|endpoint| endpoint.health.lock().unwrap().clone())
+      .collect();
+    rank(&healths, self.chain_id)
+      .into_iter()
+      .map(|i| self.endpoints[i].clone())
+      .collect()
+  }
+
+  /// Sends a signed tx to the best endpoint only, once. A send that timed out
+  /// may still have gone through, and sending it again elsewhere would
+  /// report "nonce too low" for a tx that is on its way.
+  async fn send_raw<R: DeserializeOwned>(
+    &self,
+    params: &Value,
+  ) -> Result<R, FailoverError> {
+    let endpoint = self
+      .ranked()
+      .into_iter()
+      .next()
+      .ok_or(FailoverError::NoEndpoint)?;
+    let request = endpoint
+      .http
+      .request::<_, Value>("eth_sendRawTransaction", params);
+    let error = match timeout(request, self.config.timeout()).await {
+      Some(Ok(value)) => {
+        endpoint.health.lock().unwrap().error = None;
+        return serde_json::from_value(value).map_err(FailoverError::Serde);
+      }
+      Some(Err(HttpClientError::JsonRpcError(e))) if is_already_known(&e) => {
+        let raw: Bytes = serde_json::from_value(params[0].clone())
+          .map_err(FailoverError::Serde)?;
+        let hash = H256::from(keccak256(raw));
+        return serde_json::from_value(serde_json::json!(hash))
+          .map_err(FailoverError::Serde);
+      }
+      Some(Err(e @ HttpClientError::JsonRpcError(_))) => {
+        return Err(FailoverError::Client(e))
+      }
+      Some(Err(e)) => FailoverError::Client(e),
+      None => FailoverError::Timeout(self.config.timeout_secs.max(1)),
+    };
+    endpoint.health.lock().unwrap().error = Some(error.to_string());
+    Err(error)
+  }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl JsonRpcClient for Failover {
+  type Error = FailoverError;
+
+  async fn request<T, R>(
+    &self,
+    method: &str,
+    params: T,
+  ) -> Result<R, FailoverError>
+  where
+    T: fmt::Debug + Serialize + Send + Sync,
+    R: DeserializeOwned + Send,
+  {
+    let params = serde_json::to_value(params).map_err(FailoverError::Serde)?;
+    if method == "eth_sendRawTransaction" {
+      return self.send_raw(&params).await;
+    }
+    let quorum = if is_pinned(method, &params) {
+      self.quorum
+    } else {
+      1
+    };
+
+    let mut last_error = FailoverError::NoEndpoint;
+    for retry in 0..=self.config.retries {
+      if retry > 0 {
+        Delay::new(self.config.backoff(retry - 1)).await;
+      }
+
+      // Distinct answers this round, with how many endpoints gave each.
+      let mut answers: Vec<(Value, usize)> = vec![];
+      for endpoint in self.ranked() {
+        let request = endpoint.http.request::<_, Value>(method, &params);
+        let error = match timeout(request, self.config.timeout()).await {
+          Some(Ok(value)) => {
+            endpoint.health.lock().unwrap().error = None;
+            let agreed = match answers.iter_mut().find(|(v, _)| *v == value) {
+              Some((_, count)) => {
+                *count += 1;
+                *count
+              }
+              None => {
+                answers.push((value.clone(), 1));
+                1
+              }
+            };
+            if agreed >= quorum {
+              return serde_json::from_value(value)
+                .map_err(FailoverError::Serde);
+            }
+            continue;
+          }
+          // The node understood the request and said no; another node will
+          // say the same.
+          Some(Err(HttpClientError::JsonRpcError(e)))
+            if !is_rate_limited(&e) =>
+          {
+            return Err(FailoverError::Client(HttpClientError::JsonRpcError(e)))
+          }
+          Some(Err(e)) => FailoverError::Client(e),
+          None => FailoverError::Timeout(self.config.timeout_secs.max(1)),
+        };
+        endpoint.health.lock().unwrap().error = Some(error.to_string());
+        last_error = error;
+      }
+
+      if let Some(agreed) = answers.iter().map(|(_, count)| *count).max() {
+        last_error = FailoverError::NoQuorum { agreed, quorum };
+      }
+    }
+    Err(last_error)
+  }
+}
+
+#[derive(Debug)]
+pub enum FailoverError {
+  /// The last endpoint's error, or a JSON-RPC error from any endpoint.
+  Client(HttpClientError),
+  Serde(serde_json::Error),
+  /// The last endpoint tried did not answer within this many seconds.
+  Timeout(u64),
+  /// Every endpoint was dropped for answering with the wrong chain ID.
+  NoEndpoint,
+  NoQuorum {
+    agreed: usize,
+    quorum: usize,
+  },
+}
+
+impl fmt::Display for FailoverError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FailoverError::Client(e) => write!(f, "{}", e),
+      FailoverError::Serde(e) => write!(f, "{}", e),
+      FailoverError::Timeout(secs) => {
+        write!(f, "RPC 응답 시간 초과 ({}초)", secs)
+      }
+      FailoverError::NoEndpoint => {
+        write!(f, "체인 ID가 맞는 RPC가 없습니다")
+      }
+      FailoverError::NoQuorum { agreed, quorum } => {
+        write!(f, "RPC 응답 불일치 ({}/{} 일치)", agreed, quorum)
+      }
+    }
+  }
+}
+
+impl std::error::Error for FailoverError {}
+
+impl RpcError for FailoverError {
+  fn as_error_response(&self) -> Option<&JsonRpcError> {
+    match self {
+      FailoverError::Client(e) => e.as_error_response(),
+      _ => None,
+    }
+  }
+
+  fn as_serde_error(&self) -> Option<&serde_json::Error> {
+    match self {
+      FailoverError::Client(e) => e.as_serde_error(),
+      FailoverError::Serde(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<FailoverError> for ProviderError {
+  fn from(e: FailoverError) -> Self {
+    match e {
+      FailoverError::Client(HttpClientError::ReqwestError(e)) => {
+        ProviderError::HTTPError(e)
+      }
+      e => ProviderError::JsonRpcClientError(Box::new(e)),
+    }
+  }
+}
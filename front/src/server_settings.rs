@@ -0,0 +1,46 @@
+use crate::api::ApiClient;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ServerSettingsWindow {
+  pub is_open: bool,
+
+  pub base_url: String,
+  pub api_key: String,
+}
+
+impl Default for ServerSettingsWindow {
+  fn default() -> Self {
+    Self {
+      is_open: false,
+      base_url: "http://localhost:3001".to_string(),
+      api_key: "".to_string(),
+    }
+  }
+}
+
+impl ServerSettingsWindow {
+  pub fn show(&mut self, ui: &mut egui::Ui) {
+    ui.checkbox(&mut self.is_open, "서버 설정");
+  }
+
+  pub fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    egui::Window::new("서버 설정")
+      .open(&mut self.is_open)
+      .vscroll(true)
+      .show(ctx, |ui| {
+        ui.horizontal(|ui| {
+          ui.label("서버 URL");
+          ui.text_edit_singleline(&mut self.base_url);
+        });
+        ui.horizontal(|ui| {
+          ui.label("API 키");
+          ui.add(egui::TextEdit::singleline(&mut self.api_key).password(true));
+        });
+      });
+  }
+
+  pub fn client(&self) -> ApiClient {
+    ApiClient::new(&self.base_url, &self.api_key)
+  }
+}
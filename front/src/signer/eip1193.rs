@@ -0,0 +1,400 @@
+//! EIP-1193 bridge to the browser wallet injected as `window.ethereum`
+//! (MetaMask and friends). Only the web build has one; natively every call
+//! fails with [`NOT_AVAILABLE`].
+
+use std::sync::{Arc, Mutex};
+
+use ethers::{
+  providers::Middleware,
+  types::{Address, TxHash, U64},
+};
+use poll_promise::Promise;
+use serde_json::{json, Value};
+
+use super::{rpc_error, Prepared, TxRequest};
+use crate::chain_settings::ChainSettings;
+use crate::rpc;
+
+const NOT_AVAILABLE: &str =
+  "브라우저 지갑(window.ethereum)이 없습니다. 웹 빌드에서 MetaMask 등을 설치하세요";
+
+/// EIP-1193 error code for a rejected request.
+const USER_REJECTED: i64 = 4001;
+/// EIP-3326 error code when the wallet does not know the chain yet.
+const UNRECOGNIZED_CHAIN: i64 = 4902;
+
+struct ProviderError {
+  code: Option<i64>,
+  message: String,
+}
+
+impl From<ProviderError> for String {
+  fn from(e: ProviderError) -> Self {
+    match e.code {
+      Some(USER_REJECTED) => "지갑에서 요청을 거절했습니다".to_string(),
+      Some(code) => format!("지갑 오류 {}: {}", code, e.message),
+      None => format!("지갑 오류: {}", e.message),
+    }
+  }
+}
+
+/// Connected accounts and chain as last reported by the wallet; kept fresh
+/// by the `accountsChanged` / `chainChanged` listeners.
+#[derive(Default)]
+struct State {
+  accounts: Vec<Address>,
+  chain_id: Option<u64>,
+}
+
+pub struct BrowserWallet {
+  state: Arc<Mutex<State>>,
+  listening: bool,
+  /// Connect or chain switch in flight.
+  task: Option<Promise<Result<(), String>>>,
+  error: Option<String>,
+  chain_index: usize,
+}
+
+impl Default for BrowserWallet {
+  fn default() -> Self {
+    Self {
+      state: Arc::new(Mutex::new(State::default())),
+      listening: false,
+      task: None,
+      error: None,
+      chain_index: 0,
+    }
+  }
+}
+
+impl BrowserWallet {
+  pub fn is_available() -> bool {
+    js::ethereum().is_some()
+  }
+
+  /// The selected account, once connected.
+  pub fn account(&self) -> Option<Address> {
+    self.state.lock().unwrap().accounts.first().copied()
+  }
+
+  pub fn chain_id(&self) -> Option<u64> {
+    self.state.lock().unwrap().chain_id
+  }
+
+  /// `eth_requestAccounts`, which pops up the wallet's connect prompt.
+  pub fn connect(&mut self, ctx: &egui::Context) {
+    if !self.listening {
+      self.listen(ctx);
+    }
+
+    let state = self.state.clone();
+    self.task = Some(Promise::spawn_local(async move {
+      let accounts: Vec<Address> =
+        request("eth_requestAccounts", json!([])).await?;
+      let chain_id = chain_id().await?;
+
+      let mut state = state.lock().unwrap();
+      state.accounts = accounts;
+      state.chain_id = Some(chain_id);
+      Ok(())
+    }));
+  }
+
+  /// Switches the wallet to `chain`, adding it first if the wallet has never
+  /// seen it.
+  fn switch(&mut self, chain: ChainSettings) {
+    self.task = Some(Promise::spawn_local(async move {
+      if let Some(chain_id) = chain.chain_id {
+        return switch_chain(chain_id, Some(&chain)).await;
+      }
+      let chain_id = rpc::provider(&chain.rpc_url)?
+        .get_chainid()
+        .await
+        .map_err(rpc_error)?
+        .as_u64();
+      switch_chain(chain_id, Some(&chain)).await
+    }));
+  }
+
+  fn listen(&mut self, ctx: &egui::Context) {
+    let state = self.state.clone();
+    let repaint = ctx.clone();
+    js::on("accountsChanged", move |accounts| {
+      state.lock().unwrap().accounts =
+        serde_json::from_value(accounts).unwrap_or_default();
+      repaint.request_repaint();
+    });
+
+    let state = self.state.clone();
+    let repaint = ctx.clone();
+    js::on("chainChanged", move |chain_id| {
+      state.lock().unwrap().chain_id = serde_json::from_value::<U64>(chain_id)
+        .ok()
+        .map(|chain_id| chain_id.as_u64());
+      repaint.request_repaint();
+    });
+
+    self.listening = true;
+  }
+
+  pub fn ui(&mut self, ui: &mut egui::Ui, chains: &[ChainSettings]) {
+    if !Self::is_available() {
+      ui.label(NOT_AVAILABLE);
+      return;
+    }
+
+    match self.account() {
+      Some(account) => {
+        egui::Grid::new("browser_wallet").show(ui, |ui| {
+          ui.label("계정");
+          ui.monospace(format!("{:#x}", account));
+          ui.end_row();
+          ui.label("chain id");
+          ui.label(
+            self
+              .chain_id()
+              .map(|chain_id| chain_id.to_string())
+              .unwrap_or_default(),
+          );
+          ui.end_row();
+        });
+      }
+      None => {
+        if ui.button("브라우저 지갑 연결").clicked() {
+          self.connect(ui.ctx());
+        }
+      }
+    }
+
+    ui.horizontal(|ui| {
+      egui::ComboBox::from_id_source("browser_wallet_chain")
+        .selected_text(
+          chains
+            .get(self.chain_index)
+            .map(|chain| chain.chain_name.clone())
+            .unwrap_or_default(),
+        )
+        .show_ui(ui, |ui| {
+          for (i, chain) in chains.iter().enumerate() {
+            ui.selectable_value(&mut self.chain_index, i, &chain.chain_name);
+          }
+        });
+      if ui
+        .add_enabled(
+          self.account().is_some() && self.chain_index < chains.len(),
+          egui::Button::new("체인 전환"),
+        )
+        .clicked()
+      {
+        self.switch(chains[self.chain_index].clone());
+      }
+    });
+
+    let done = self.task.as_ref().and_then(Promise::ready).cloned();
+    match done {
+      Some(result) => {
+        self.error = result.err();
+        self.task = None;
+      }
+      None if self.task.is_some() => {
+        ui.spinner();
+      }
+      None => {}
+    }
+
+    if let Some(e) = &self.error {
+      ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+  }
+}
+
+pub async fn chain_id() -> Result<u64, String> {
+  let chain_id: U64 = request("eth_chainId", json!([])).await?;
+  Ok(chain_id.as_u64())
+}
+
+/// `wallet_switchEthereumChain`, falling back to `wallet_addEthereumChain`
+/// with `chain`'s RPC URL when the wallet does not know `chain_id`.
+pub async fn switch_chain(
+  chain_id: u64,
+  chain: Option<&ChainSettings>,
+) -> Result<(), String> {
+  let hex_id = format!("{:#x}", chain_id);
+  let switched =
+    js::request("wallet_switchEthereumChain", json!([{ "chainId": hex_id }]))
+      .await;
+
+  match (switched, chain) {
+    (Ok(_), _) => Ok(()),
+    (Err(e), Some(chain)) if e.code == Some(UNRECOGNIZED_CHAIN) => {
+      let mut params = json!({
+        "chainId": hex_id,
+        "chainName": chain.chain_name,
+        "rpcUrls": [chain.rpc_url],
+        "nativeCurrency": {
+          "name": chain.native_symbol,
+          "symbol": chain.native_symbol,
+          "decimals": chain.decimals,
+        },
+      });
+      if !chain.explorer_url.is_empty() {
+        params["blockExplorerUrls"] = json!([chain.explorer_url]);
+      }
+      js::request("wallet_addEthereumChain", json!([params])).await?;
+      Ok(())
+    }
+    (Err(e), _) => Err(e.into()),
+  }
+}
+
+/// `eth_sendTransaction` from `from`; the wallet shows its own prompt and
+/// signs. Switches the wallet to `prepared.chain_id` first if needed.
+pub async fn send_transaction(
+  from: Address,
+  request: &TxRequest,
+  prepared: &Prepared,
+) -> Result<TxHash, String> {
+  if chain_id().await? != prepared.chain_id {
+    switch_chain(prepared.chain_id, None).await?;
+  }
+
+  self::request(
+    "eth_sendTransaction",
+    json!([{
+      "from": from,
+      "to": request.to,
+      "data": request.data,
+      "value": request.value,
+      "gas": prepared.gas,
+      "maxFeePerGas": prepared.max_fee_per_gas,
+      "maxPriorityFeePerGas": prepared.max_priority_fee_per_gas,
+    }]),
+  )
+  .await
+}
+
+async fn request<T: serde::de::DeserializeOwned>(
+  method: &str,
+  params: Value,
+) -> Result<T, String> {
+  let result = js::request(method, params).await?;
+  serde_json::from_value(result)
+    .map_err(|e| format!("{} 응답 해석 실패: {}", method, e))
+}
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+  use serde_json::{json, Value};
+  use web_sys::js_sys::{Function, Promise, Reflect, JSON};
+  use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+  use super::{ProviderError, NOT_AVAILABLE};
+
+  pub fn ethereum() -> Option<JsValue> {
+    let window = web_sys::window()?;
+    Reflect::get(&window, &"ethereum".into())
+      .ok()
+      .filter(|ethereum| ethereum.is_object())
+  }
+
+  /// `ethereum.request({ method, params })`.
+  pub async fn request(
+    method: &str,
+    params: Value,
+  ) -> Result<Value, ProviderError> {
+    let ethereum = ethereum().ok_or_else(|| error(NOT_AVAILABLE))?;
+    let args = json!({ "method": method, "params": params });
+
+    let args = JSON::parse(&args.to_string()).map_err(from_js)?;
+    let promise = method_of(&ethereum, "request")?
+      .call1(&ethereum, &args)
+      .map_err(from_js)?
+      .dyn_into::<Promise>()
+      .map_err(|_| error("request()가 Promise를 반환하지 않았습니다"))?;
+    let result = wasm_bindgen_futures::JsFuture::from(promise)
+      .await
+      .map_err(from_js)?;
+
+    to_json(&result).map_err(|e| error(&e))
+  }
+
+  /// `ethereum.on(event, handler)`; the listener lives as long as the page.
+  pub fn on(event: &str, mut handler: impl FnMut(Value) + 'static) {
+    let Some(ethereum) = ethereum() else {
+      return;
+    };
+    let Ok(on) = method_of(&ethereum, "on") else {
+      return;
+    };
+
+    let closure = Closure::<dyn FnMut(JsValue)>::new(move |value: JsValue| {
+      handler(to_json(&value).unwrap_or(Value::Null))
+    });
+    if on.call2(&ethereum, &event.into(), closure.as_ref()).is_ok() {
+      closure.forget();
+    }
+  }
+
+  fn method_of(
+    object: &JsValue,
+    name: &str,
+  ) -> Result<Function, ProviderError> {
+    Reflect::get(object, &name.into())
+      .ok()
+      .and_then(|method| method.dyn_into::<Function>().ok())
+      .ok_or_else(|| error(&format!("ethereum.{}이 없습니다", name)))
+  }
+
+  fn to_json(value: &JsValue) -> Result<Value, String> {
+    if value.is_undefined() || value.is_null() {
+      return Ok(Value::Null);
+    }
+    let text: String = JSON::stringify(value)
+      .map_err(|e| format!("{:?}", e))?
+      .into();
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+  }
+
+  /// EIP-1193 errors are `{ code, message }` objects.
+  fn from_js(value: JsValue) -> ProviderError {
+    let field = |name: &str| Reflect::get(&value, &name.into()).ok();
+    ProviderError {
+      code: field("code")
+        .and_then(|code| code.as_f64())
+        .map(|code| code as i64),
+      message: field("message")
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| format!("{:?}", value)),
+    }
+  }
+
+  fn error(message: &str) -> ProviderError {
+    ProviderError {
+      code: None,
+      message: message.to_string(),
+    }
+  }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod js {
+  use serde_json::Value;
+
+  use super::{ProviderError, NOT_AVAILABLE};
+
+  pub fn ethereum() -> Option<()> {
+    None
+  }
+
+  pub async fn request(
+    _method: &str,
+    _params: Value,
+  ) -> Result<Value, ProviderError> {
+    Err(ProviderError {
+      code: None,
+      message: NOT_AVAILABLE.to_string(),
+    })
+  }
+
+  pub fn on(_event: &str, _handler: impl FnMut(Value) + 'static) {}
+}
@@ -0,0 +1,308 @@
+//! Web3 Secret Storage (V3 keystore JSON), entirely in memory so it works in
+//! the browser where there is no keystore directory.
+
+use std::fmt;
+
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ctr::Ctr128BE;
+use ethers::{
+  core::rand::{CryptoRng, Rng},
+  signers::{LocalWallet, Signer as _},
+  utils::keccak256,
+};
+use hmac::Hmac;
+use sha2::Sha256;
+
+/// scrypt cost for keystores created here: `n = 2^13, r = 8, p = 1`, light
+/// enough to unlock in a browser tab.
+const SCRYPT_N: u32 = 1 << 13;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const DKLEN: u32 = 32;
+
+/// Largest scrypt `n * r` accepted, i.e. 256 MiB of memory: geth's standard
+/// `n = 2^18, r = 8`.
+const MAX_SCRYPT_NR: u64 = 1 << 21;
+/// Largest scrypt `n * r * p`, which bounds the time spent.
+const MAX_SCRYPT_NRP: u64 = 1 << 22;
+/// Largest PBKDF2 iteration count; geth writes `c = 262144`.
+const MAX_PBKDF2_C: u32 = 10_000_000;
+/// The MAC uses bytes 16..32 of the derived key; anything past 64 is waste.
+const MAX_DKLEN: u32 = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeystoreError {
+  /// Not a V3 keystore, or a field has the wrong shape.
+  Format(String),
+  /// Cipher, KDF or PRF this implementation does not know.
+  Unsupported(String),
+  /// The MAC did not match, which almost always means a wrong password.
+  WrongPassword,
+}
+
+impl fmt::Display for KeystoreError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      KeystoreError::Format(e) => write!(f, "키스토어 형식 오류: {}", e),
+      KeystoreError::Unsupported(what) => {
+        write!(f, "지원하지 않는 키스토어: {}", what)
+      }
+      KeystoreError::WrongPassword => write!(f, "비밀번호가 틀렸습니다"),
+    }
+  }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Keystore {
+  id: String,
+  version: u8,
+  /// Lowercase hex without `0x`, as geth writes it. Optional in the spec.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  address: Option<String>,
+  #[serde(alias = "Crypto")]
+  crypto: Crypto,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Crypto {
+  cipher: String,
+  cipherparams: CipherParams,
+  #[serde(with = "hex_bytes")]
+  ciphertext: Vec<u8>,
+  kdf: String,
+  kdfparams: KdfParams,
+  #[serde(with = "hex_bytes")]
+  mac: Vec<u8>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct CipherParams {
+  #[serde(with = "hex_bytes")]
+  iv: Vec<u8>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+enum KdfParams {
+  Scrypt {
+    dklen: u32,
+    n: u32,
+    p: u32,
+    r: u32,
+    #[serde(with = "hex_bytes")]
+    salt: Vec<u8>,
+  },
+  Pbkdf2 {
+    c: u32,
+    dklen: u32,
+    prf: String,
+    #[serde(with = "hex_bytes")]
+    salt: Vec<u8>,
+  },
+}
+
+/// The `address` field of a keystore, if it has one, as `0x..`.
+pub fn address(json: &str) -> Result<Option<String>, KeystoreError> {
+  let keystore = parse(json)?;
+  Ok(keystore.address.map(|address| {
+    format!("0x{}", address.trim_start_matches("0x").to_lowercase())
+  }))
+}
+
+/// The 32-byte private key inside `json`.
+pub fn decrypt(json: &str, password: &str) -> Result<Vec<u8>, KeystoreError> {
+  let keystore = parse(json)?;
+  let crypto = keystore.crypto;
+
+  if crypto.cipher != "aes-128-ctr" {
+    return Err(KeystoreError::Unsupported(crypto.cipher));
+  }
+
+  let dklen = match &crypto.kdfparams {
+    KdfParams::Scrypt { dklen, .. } | KdfParams::Pbkdf2 { dklen, .. } => *dklen,
+  };
+  if !(32..=MAX_DKLEN).contains(&dklen) {
+    return Err(KeystoreError::Unsupported(format!("dklen = {}", dklen)));
+  }
+
+  let key = match (crypto.kdf.as_str(), &crypto.kdfparams) {
+    (
+      "scrypt",
+      KdfParams::Scrypt {
+        dklen,
+        n,
+        p,
+        r,
+        salt,
+      },
+    ) => {
+      if *n < 2 || !n.is_power_of_two() || *r == 0 || *p == 0 {
+        return Err(KeystoreError::Format(format!(
+          "scrypt n = {}, r = {}, p = {}",
+          n, r, p
+        )));
+      }
+      let nr = *n as u64 * *r as u64;
+      if nr > MAX_SCRYPT_NR || nr * *p as u64 > MAX_SCRYPT_NRP {
+        return Err(KeystoreError::Unsupported(format!(
+          "scrypt n = {}, r = {}, p = {}",
+          n, r, p
+        )));
+      }
+      derive_scrypt(password, salt, *n, *r, *p, *dklen)?
+    }
+    (
+      "pbkdf2",
+      KdfParams::Pbkdf2 {
+        c,
+        dklen,
+        prf,
+        salt,
+      },
+    ) => {
+      if prf != "hmac-sha256" {
+        return Err(KeystoreError::Unsupported(prf.clone()));
+      }
+      if *c == 0 || *c > MAX_PBKDF2_C {
+        return Err(KeystoreError::Unsupported(format!("pbkdf2 c = {}", c)));
+      }
+      let mut key = vec![0u8; *dklen as usize];
+      pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, *c, &mut key);
+      key
+    }
+    (kdf, _) => return Err(KeystoreError::Unsupported(kdf.to_string())),
+  };
+
+  if mac(&key, &crypto.ciphertext)[..] != crypto.mac[..] {
+    return Err(KeystoreError::WrongPassword);
+  }
+
+  let mut private_key = crypto.ciphertext;
+  aes_ctr(&key[..16], &crypto.cipherparams.iv)?
+    .apply_keystream(&mut private_key);
+
+  Ok(private_key)
+}
+
+/// A new scrypt keystore for `private_key`, serialized as JSON.
+pub fn encrypt<R: Rng + CryptoRng>(
+  rng: &mut R,
+  private_key: &[u8],
+  password: &str,
+) -> Result<String, KeystoreError> {
+  let address = LocalWallet::from_bytes(private_key)
+    .map_err(|e| KeystoreError::Format(e.to_string()))?
+    .address();
+
+  let salt: [u8; 32] = rng.gen();
+  let iv: [u8; 16] = rng.gen();
+  let key =
+    derive_scrypt(password, &salt, SCRYPT_N, SCRYPT_R, SCRYPT_P, DKLEN)?;
+
+  let mut ciphertext = private_key.to_vec();
+  aes_ctr(&key[..16], &iv)?.apply_keystream(&mut ciphertext);
+
+  let keystore = Keystore {
+    id: uuid_v4(rng.gen()),
+    version: 3,
+    address: Some(format!("{:x}", address)),
+    crypto: Crypto {
+      cipher: "aes-128-ctr".to_string(),
+      cipherparams: CipherParams { iv: iv.to_vec() },
+      mac: mac(&key, &ciphertext).to_vec(),
+      ciphertext,
+      kdf: "scrypt".to_string(),
+      kdfparams: KdfParams::Scrypt {
+        dklen: DKLEN,
+        n: SCRYPT_N,
+        p: SCRYPT_P,
+        r: SCRYPT_R,
+        salt: salt.to_vec(),
+      },
+    },
+  };
+
+  serde_json::to_string_pretty(&keystore)
+    .map_err(|e| KeystoreError::Format(e.to_string()))
+}
+
+fn parse(json: &str) -> Result<Keystore, KeystoreError> {
+  let keystore: Keystore = serde_json::from_str(json.trim())
+    .map_err(|e| KeystoreError::Format(e.to_string()))?;
+  if keystore.version != 3 {
+    return Err(KeystoreError::Unsupported(format!(
+      "version {}",
+      keystore.version
+    )));
+  }
+  Ok(keystore)
+}
+
+/// `n` must be a power of two. Params the `scrypt` crate rejects, such as
+/// the spec vector's `n = 2^18, r = 1` (RFC 7914 wants `n < 2^(16 * r)`),
+/// are unsupported.
+fn derive_scrypt(
+  password: &str,
+  salt: &[u8],
+  n: u32,
+  r: u32,
+  p: u32,
+  dklen: u32,
+) -> Result<Vec<u8>, KeystoreError> {
+  let unsupported = || {
+    KeystoreError::Unsupported(format!(
+      "scrypt n = {}, r = {}, p = {}",
+      n, r, p
+    ))
+  };
+  let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p)
+    .map_err(|_| unsupported())?;
+  let mut key = vec![0u8; dklen as usize];
+  scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+    .map_err(|_| unsupported())?;
+  Ok(key)
+}
+
+fn mac(key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+  keccak256([&key[16..32], ciphertext].concat())
+}
+
+fn aes_ctr(key: &[u8], iv: &[u8]) -> Result<Ctr128BE<Aes128>, KeystoreError> {
+  Ctr128BE::<Aes128>::new_from_slices(key, iv)
+    .map_err(|_| KeystoreError::Format(format!("iv 길이 {}", iv.len())))
+}
+
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+  bytes[6] = (bytes[6] & 0x0f) | 0x40;
+  bytes[8] = (bytes[8] & 0x3f) | 0x80;
+  let hex = ethers::utils::hex::encode(bytes);
+  format!(
+    "{}-{}-{}-{}-{}",
+    &hex[..8],
+    &hex[8..12],
+    &hex[12..16],
+    &hex[16..20],
+    &hex[20..]
+  )
+}
+
+/// Keystores store byte fields as bare lowercase hex.
+mod hex_bytes {
+  use ethers::utils::hex;
+  use serde::{Deserialize, Deserializer, Serializer};
+
+  pub fn serialize<S: Serializer>(
+    bytes: &[u8],
+    serializer: S,
+  ) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(bytes))
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Vec<u8>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    hex::decode(s.trim_start_matches("0x")).map_err(serde::de::Error::custom)
+  }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use ethers::{
+  core::rand::thread_rng,
+  providers::Middleware,
+  signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Signer as _},
+  types::{
+    transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes,
+    Eip1559TransactionRequest, TxHash, U256,
+  },
+};
+
+use crate::rpc::{self, RpcProvider};
+
+mod eip1193;
+pub mod keystore;
+mod window;
+
+pub use window::SignerWindow;
+
+/// Headroom added on top of `eth_estimateGas`, in percent.
+const GAS_LIMIT_BUFFER: u64 = 20;
+
+/// Filled in once a [`TxRequest`] is sent, fails or is cancelled, for
+/// windows that track many requests at a time.
+pub type Outcome = Arc<Mutex<Option<Result<TxHash, String>>>>;
+
+/// A write call a window wants sent; shown in the confirmation dialog
+/// before anything is signed.
+#[derive(Clone)]
+pub struct TxRequest {
+  /// What the user clicked, e.g. `approve(0x.., 1)`.
+  pub label: String,
+  pub rpc_url: String,
+  pub to: Address,
+  pub data: Bytes,
+  pub value: U256,
+  pub outcome: Option<Outcome>,
+}
+
+/// Fees and gas for a [`TxRequest`], estimated before the user confirms.
+#[derive(Clone)]
+pub struct Prepared {
+  pub chain_id: u64,
+  pub from: Address,
+  pub gas: U256,
+  pub max_fee_per_gas: U256,
+  pub max_priority_fee_per_gas: U256,
+}
+
+impl Prepared {
+  /// Most the transaction can cost: value plus gas at the max fee.
+  pub fn max_cost(&self, value: U256) -> U256 {
+    value + self.gas * self.max_fee_per_gas
+  }
+}
+
+/// Who signs a [`TxRequest`]: the in-memory key or the browser wallet.
+#[derive(Clone)]
+pub enum Account {
+  Local(Signer),
+  Browser(Address),
+}
+
+impl Account {
+  pub fn address(&self) -> Address {
+    match self {
+      Account::Local(signer) => signer.address(),
+      Account::Browser(address) => *address,
+    }
+  }
+
+  /// Chain ID, gas limit and EIP-1559 fees for `request`.
+  pub async fn prepare(&self, request: &TxRequest) -> Result<Prepared, String> {
+    let from = self.address();
+    let provider = rpc::provider(&request.rpc_url)?;
+    let chain_id = provider.get_chainid().await.map_err(rpc_error)?.as_u64();
+
+    let tx = transaction(from, request, chain_id);
+    let gas = provider.estimate_gas(&tx, None).await.map_err(rpc_error)?;
+    let (max_fee_per_gas, max_priority_fee_per_gas) = provider
+      .estimate_eip1559_fees(None)
+      .await
+      .map_err(rpc_error)?;
+
+    Ok(Prepared {
+      chain_id,
+      from,
+      gas: gas * (100 + GAS_LIMIT_BUFFER) / 100,
+      max_fee_per_gas,
+      max_priority_fee_per_gas,
+    })
+  }
+
+  pub async fn send(
+    &self,
+    request: &TxRequest,
+    prepared: &Prepared,
+  ) -> Result<TxHash, String> {
+    match self {
+      Account::Local(signer) => signer.send(request, prepared).await,
+      Account::Browser(from) => {
+        eip1193::send_transaction(*from, request, prepared).await
+      }
+    }
+  }
+}
+
+/// A private key held in memory only; it is never persisted.
+#[derive(Clone)]
+pub struct Signer {
+  wallet: LocalWallet,
+  /// Next nonce per `(chain_id, address)`, so back-to-back sends do not
+  /// reuse a nonce the node has not seen in its pending pool yet.
+  nonces: Arc<Mutex<HashMap<(u64, Address), U256>>>,
+}
+
+impl Signer {
+  pub fn from_private_key(key: &str) -> Result<Self, String> {
+    let key = key.trim();
+    let wallet = key
+      .strip_prefix("0x")
+      .unwrap_or(key)
+      .parse::<LocalWallet>()
+      .map_err(|_| "잘못된 개인 키입니다".to_string())?;
+
+    Ok(Self::new(wallet))
+  }
+
+  /// BIP-39 phrase derived along BIP-44 `m/44'/60'/0'/0/{index}`.
+  pub fn from_mnemonic(
+    phrase: &str,
+    password: &str,
+    index: u32,
+  ) -> Result<Self, String> {
+    let wallet = MnemonicBuilder::<English>::default()
+      .phrase(phrase.trim())
+      .password(password)
+      .index(index)
+      .and_then(|builder| builder.build())
+      .map_err(|e| format!("니모닉 오류: {}", e))?;
+
+    Ok(Self::new(wallet))
+  }
+
+  /// Unlocks a V3 keystore; see [`keystore::decrypt`].
+  pub fn from_keystore(json: &str, password: &str) -> Result<Self, String> {
+    let private_key =
+      keystore::decrypt(json, password).map_err(|e| e.to_string())?;
+    let wallet =
+      LocalWallet::from_bytes(&private_key).map_err(|e| e.to_string())?;
+
+    Ok(Self::new(wallet))
+  }
+
+  /// A fresh random key.
+  pub fn random() -> Self {
+    Self::new(LocalWallet::new(&mut thread_rng()))
+  }
+
+  /// Encrypts the key as a V3 keystore under `password`.
+  pub fn to_keystore(&self, password: &str) -> Result<String, String> {
+    let private_key = self.wallet.signer().to_bytes();
+    keystore::encrypt(&mut thread_rng(), &private_key, password)
+      .map_err(|e| e.to_string())
+  }
+
+  fn new(wallet: LocalWallet) -> Self {
+    Self {
+      wallet,
+      nonces: Arc::new(Mutex::new(HashMap::new())),
+    }
+  }
+
+  pub fn address(&self) -> Address {
+    self.wallet.address()
+  }
+
+  /// Signs `request` with the fees from `prepared` and broadcasts it.
+  pub async fn send(
+    &self,
+    request: &TxRequest,
+    prepared: &Prepared,
+  ) -> Result<TxHash, String> {
+    let provider = rpc::provider(&request.rpc_url)?;
+    let key = (prepared.chain_id, self.address());
+
+    let pending = provider
+      .get_transaction_count(self.address(), Some(BlockNumber::Pending.into()))
+      .await
+      .map_err(rpc_error)?;
+    // Reserved under the lock before signing, so concurrent sends from this key
+    // each get their own nonce.
+    let nonce = {
+      let mut nonces = self.nonces.lock().unwrap();
+      let nonce = nonces.get(&key).copied().unwrap_or_default().max(pending);
+      nonces.insert(key, nonce + 1);
+      nonce
+    };
+
+    let sent = self
+      .sign_and_send(&provider, request, prepared, nonce)
+      .await;
+    if sent.is_err() {
+      let mut nonces = self.nonces.lock().unwrap();
+      if nonces.get(&key) == Some(&(nonce + 1)) {
+        // Nothing was reserved after it: hand the nonce back.
+        nonces.insert(key, nonce);
+      } else {
+        // Later sends hold higher nonces; resync from the node next time so
+        // the gap this one leaves gets filled.
+        nonces.remove(&key);
+      }
+    }
+    sent
+  }
+
+  async fn sign_and_send(
+    &self,
+    provider: &RpcProvider,
+    request: &TxRequest,
+    prepared: &Prepared,
+    nonce: U256,
+  ) -> Result<TxHash, String> {
+    let mut tx = transaction(self.address(), request, prepared.chain_id);
+    tx.set_nonce(nonce);
+    tx.set_gas(prepared.gas);
+    if let TypedTransaction::Eip1559(inner) = &mut tx {
+      inner.max_fee_per_gas = Some(prepared.max_fee_per_gas);
+      inner.max_priority_fee_per_gas = Some(prepared.max_priority_fee_per_gas);
+    }
+
+    let wallet = self.wallet.clone().with_chain_id(prepared.chain_id);
+    let signature = wallet
+      .sign_transaction(&tx)
+      .await
+      .map_err(|e| format!("서명 실패: {}", e))?;
+
+    provider
+      .send_raw_transaction(tx.rlp_signed(&signature))
+      .await
+      .map(|pending| pending.tx_hash())
+      .map_err(rpc_error)
+  }
+}
+
+fn transaction(
+  from: Address,
+  request: &TxRequest,
+  chain_id: u64,
+) -> TypedTransaction {
+  Eip1559TransactionRequest::new()
+    .from(from)
+    .to(request.to)
+    .data(request.data.clone())
+    .value(request.value)
+    .chain_id(chain_id)
+    .into()
+}
+
+fn rpc_error(e: impl std::fmt::Display) -> String {
+  format!("RPC 오류: {}", e)
+}
@@ -0,0 +1,741 @@
+use std::time::Duration;
+
+use ethers::{
+  types::TxHash,
+  utils::{format_ether, format_units},
+};
+use poll_promise::Promise;
+
+use super::eip1193::BrowserWallet;
+use super::{keystore, Account, Prepared, Signer, TxRequest};
+use crate::chain_settings::{ChainSettings, ChainSettingsWindow};
+use crate::tx_history::TxHistoryWindow;
+
+/// Which account confirmed transactions are sent from.
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Backend {
+  /// The in-memory [`Signer`].
+  Local,
+  /// The EIP-1193 browser wallet; only on the web build.
+  Browser,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum KeySource {
+  Keystore,
+  PrivateKey,
+  Mnemonic,
+  /// Generate a fresh key and save it as a keystore.
+  New,
+}
+
+/// An encrypted V3 keystore kept in app storage; safe to persist since the
+/// key inside is only readable with its password.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct StoredKeystore {
+  pub name: String,
+  pub address: String,
+  pub json: String,
+}
+
+impl StoredKeystore {
+  fn title(&self) -> String {
+    format!("{} ({})", self.name, self.address)
+  }
+
+  /// Hands the JSON to the browser as `<name>.json`.
+  #[cfg(target_arch = "wasm32")]
+  fn export(&self, _ctx: &egui::Context) -> Result<(), String> {
+    let file_name = if self.name.is_empty() {
+      &self.address
+    } else {
+      &self.name
+    };
+    crate::download::save(
+      &format!("{}.json", file_name),
+      "application/json",
+      self.json.as_bytes(),
+    )
+  }
+
+  /// Native builds cannot download files; the JSON goes to the clipboard.
+  #[cfg(not(target_arch = "wasm32"))]
+  fn export(&self, ctx: &egui::Context) -> Result<(), String> {
+    ctx.output_mut(|output| output.copied_text = self.json.clone());
+    Ok(())
+  }
+}
+
+/// Holds the in-memory [`Signer`] and shows a confirmation dialog for every
+/// [`TxRequest`] other windows queue with [`SignerWindow::request`].
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SignerWindow {
+  pub is_open: bool,
+
+  pub backend: Backend,
+  pub source: KeySource,
+  pub account_index: u32,
+
+  pub keystores: Vec<StoredKeystore>,
+  pub selected_keystore: usize,
+
+  #[serde(skip)]
+  secret: String,
+  #[serde(skip)]
+  password: String,
+  #[serde(skip)]
+  keystore_name: String,
+  #[serde(skip)]
+  new_password: String,
+  #[serde(skip)]
+  new_password_confirm: String,
+  #[serde(skip)]
+  import_json: String,
+  #[serde(skip)]
+  signer: Option<Signer>,
+  /// Keystore decryption in progress; scrypt takes a moment.
+  #[serde(skip)]
+  unlocking: Option<Promise<Result<Signer, String>>>,
+  #[serde(skip)]
+  error: Option<String>,
+  #[serde(skip)]
+  browser: BrowserWallet,
+
+  #[serde(skip)]
+  pending: Vec<PendingTx>,
+  #[serde(skip)]
+  next_id: u64,
+}
+
+impl Default for SignerWindow {
+  fn default() -> Self {
+    Self {
+      is_open: false,
+
+      backend: Backend::Local,
+      source: KeySource::Keystore,
+      account_index: 0,
+
+      keystores: vec![],
+      selected_keystore: 0,
+
+      secret: "".to_string(),
+      password: "".to_string(),
+      keystore_name: "".to_string(),
+      new_password: "".to_string(),
+      new_password_confirm: "".to_string(),
+      import_json: "".to_string(),
+      signer: None,
+      unlocking: None,
+      error: None,
+      browser: BrowserWallet::default(),
+
+      pending: vec![],
+      next_id: 0,
+    }
+  }
+}
+
+struct PendingTx {
+  id: u64,
+  request: TxRequest,
+  prepared: Option<Promise<Result<Prepared, String>>>,
+  sent: Option<Promise<Result<TxHash, String>>>,
+  /// Whether the sent hash has been handed to the history panel yet.
+  recorded: bool,
+}
+
+impl SignerWindow {
+  pub fn show(&mut self, ui: &mut egui::Ui) {
+    ui.checkbox(&mut self.is_open, "서명 지갑");
+  }
+
+  /// Queues `request` for confirmation; nothing is signed until the user
+  /// accepts it in the dialog.
+  pub fn request(&mut self, request: TxRequest) {
+    self.pending.push(PendingTx {
+      id: self.next_id,
+      request,
+      prepared: None,
+      sent: None,
+      recorded: false,
+    });
+    self.next_id += 1;
+  }
+
+  /// Address of the account confirmed transactions are sent from.
+  pub fn address(&self) -> Option<ethers::types::Address> {
+    self.account().map(|account| account.address())
+  }
+
+  /// The account transactions are sent from, if one is ready.
+  fn account(&self) -> Option<Account> {
+    match self.backend {
+      Backend::Local => self.signer.clone().map(Account::Local),
+      Backend::Browser => self.browser.account().map(Account::Browser),
+    }
+  }
+
+  /// Connected-account indicator for the top panel.
+  pub fn status_ui(&mut self, ui: &mut egui::Ui) {
+    match (self.backend, self.account()) {
+      (Backend::Browser, Some(account)) => {
+        ui.label(format!(
+          "🦊 {} (chain {})",
+          short_address(account.address()),
+          self
+            .browser
+            .chain_id()
+            .map(|chain_id| chain_id.to_string())
+            .unwrap_or_default()
+        ));
+      }
+      (Backend::Local, Some(account)) => {
+        ui.label(format!("🔑 {}", short_address(account.address())));
+      }
+      (_, None) => {
+        if BrowserWallet::is_available() {
+          if ui.button("브라우저 지갑 연결").clicked() {
+            self.backend = Backend::Browser;
+            self.browser.connect(ui.ctx());
+          }
+        } else if ui.button("서명 지갑 없음").clicked() {
+          self.is_open = true;
+        }
+      }
+    }
+  }
+
+  pub fn update(
+    &mut self,
+    ctx: &egui::Context,
+    _frame: &mut eframe::Frame,
+    chain_settings: &ChainSettingsWindow,
+    tx_history: &mut TxHistoryWindow,
+  ) {
+    if let Some(unlocking) = self.unlocking.take() {
+      match unlocking.try_take() {
+        Ok(signer) => self.unlock(signer),
+        Err(unlocking) => self.unlocking = Some(unlocking),
+      }
+    }
+
+    let mut is_open = self.is_open;
+    egui::Window::new("서명 지갑")
+      .open(&mut is_open)
+      .vscroll(true)
+      .show(ctx, |ui| {
+        ui.horizontal(|ui| {
+          ui.radio_value(&mut self.backend, Backend::Local, "메모리 키");
+          ui.radio_value(&mut self.backend, Backend::Browser, "브라우저 지갑");
+        });
+        ui.separator();
+
+        if self.backend == Backend::Browser {
+          self.browser.ui(ui, &chain_settings.chain_settings);
+          return;
+        }
+
+        ui.label("⚠️키는 메모리에만 보관되며 새로고침하면 사라집니다");
+        ui.label("저장되는 것은 비밀번호로 암호화된 키스토어뿐입니다");
+
+        if self.signer.is_some() {
+          self.unlocked_ui(ui);
+        } else {
+          self.locked_ui(ui);
+        }
+
+        ui.separator();
+        ui.collapsing("키스토어 관리", |ui| self.keystores_ui(ui));
+
+        if let Some(e) = &self.error {
+          ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+      });
+    self.is_open = is_open;
+
+    let account = self.account();
+    let mut closed = vec![];
+    for pending in &mut self.pending {
+      let chain = chain_settings.chain_by_rpc_url(&pending.request.rpc_url);
+      if !pending.ui(ctx, account.as_ref(), chain) {
+        closed.push(pending.id);
+      }
+      if let Some((hash, prepared)) = pending.take_sent() {
+        tx_history.record(hash, &pending.request, &prepared);
+      }
+    }
+    self.pending.retain(|pending| !closed.contains(&pending.id));
+  }
+
+  fn unlocked_ui(&mut self, ui: &mut egui::Ui) {
+    let Some(signer) = &self.signer else {
+      return;
+    };
+
+    ui.horizontal(|ui| {
+      ui.label("주소");
+      ui.monospace(format!("{:#x}", signer.address()));
+    });
+
+    let signer = signer.clone();
+    ui.collapsing("키스토어로 저장", |ui| {
+      self.new_keystore_ui(ui, "저장", |password| {
+        signer
+          .to_keystore(password)
+          .map(|json| (signer.clone(), json))
+      });
+    });
+
+    if ui.button("지갑 잠그기").clicked() {
+      self.signer = None;
+    }
+  }
+
+  fn locked_ui(&mut self, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+      ui.radio_value(&mut self.source, KeySource::Keystore, "키스토어");
+      ui.radio_value(&mut self.source, KeySource::PrivateKey, "개인 키");
+      ui.radio_value(&mut self.source, KeySource::Mnemonic, "니모닉");
+      ui.radio_value(&mut self.source, KeySource::New, "새 지갑");
+    });
+
+    match self.source {
+      KeySource::Keystore => {
+        let selected = self
+          .keystores
+          .get(self.selected_keystore)
+          .map(StoredKeystore::title)
+          .unwrap_or_default();
+        egui::ComboBox::from_label("키스토어")
+          .selected_text(selected)
+          .show_ui(ui, |ui| {
+            for (i, keystore) in self.keystores.iter().enumerate() {
+              ui.selectable_value(
+                &mut self.selected_keystore,
+                i,
+                keystore.title(),
+              );
+            }
+          });
+        ui.horizontal(|ui| {
+          ui.label("비밀번호");
+          ui.add(egui::TextEdit::singleline(&mut self.password).password(true));
+        });
+
+        if self.unlocking.is_some() {
+          ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("잠금 해제 중...");
+          });
+        } else if ui.button("잠금 해제").clicked() {
+          match self.keystores.get(self.selected_keystore) {
+            Some(keystore) => {
+              let json = keystore.json.clone();
+              let password = self.password.clone();
+              self.unlocking = Some(Promise::spawn_local(async move {
+                // A timer, not a microtask, so a frame with the spinner
+                // paints before scrypt holds the thread.
+                futures_timer::Delay::new(Duration::from_millis(50)).await;
+                Signer::from_keystore(&json, &password)
+              }));
+            }
+            None => {
+              self.error =
+                Some("키스토어를 먼저 가져오거나 만드세요".to_string())
+            }
+          }
+        }
+      }
+      KeySource::PrivateKey | KeySource::Mnemonic => {
+        ui.horizontal(|ui| {
+          ui.label(if self.source == KeySource::PrivateKey {
+            "개인 키"
+          } else {
+            "니모닉"
+          });
+          ui.add(egui::TextEdit::singleline(&mut self.secret).password(true));
+        });
+
+        if self.source == KeySource::Mnemonic {
+          ui.horizontal(|ui| {
+            ui.label("BIP-39 비밀번호 (선택)");
+            ui.add(
+              egui::TextEdit::singleline(&mut self.password).password(true),
+            );
+          });
+          ui.horizontal(|ui| {
+            ui.label("계정 인덱스 (m/44'/60'/0'/0/n)");
+            ui.add(egui::DragValue::new(&mut self.account_index));
+          });
+        }
+
+        if ui.button("불러오기").clicked() {
+          let signer = if self.source == KeySource::PrivateKey {
+            Signer::from_private_key(&self.secret)
+          } else {
+            Signer::from_mnemonic(
+              &self.secret,
+              &self.password,
+              self.account_index,
+            )
+          };
+          self.unlock(signer);
+        }
+      }
+      KeySource::New => {
+        ui.label("새 키를 만들어 키스토어로 저장하고 바로 잠금 해제합니다");
+        self.new_keystore_ui(ui, "만들기", |password| {
+          let signer = Signer::random();
+          signer.to_keystore(password).map(|json| (signer, json))
+        });
+      }
+    }
+  }
+
+  /// Name + password (twice) form; `create` returns the signer to unlock and
+  /// its keystore JSON, which is added to the saved keystores.
+  fn new_keystore_ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    button: &str,
+    create: impl FnOnce(&str) -> Result<(Signer, String), String>,
+  ) {
+    ui.horizontal(|ui| {
+      ui.label("이름");
+      ui.text_edit_singleline(&mut self.keystore_name);
+    });
+    ui.horizontal(|ui| {
+      ui.label("비밀번호");
+      ui.add(egui::TextEdit::singleline(&mut self.new_password).password(true));
+    });
+    ui.horizontal(|ui| {
+      ui.label("비밀번호 확인");
+      ui.add(
+        egui::TextEdit::singleline(&mut self.new_password_confirm)
+          .password(true),
+      );
+    });
+
+    if !ui.button(button).clicked() {
+      return;
+    }
+    if self.new_password.is_empty() {
+      self.error = Some("비밀번호를 입력하세요".to_string());
+      return;
+    }
+    if self.new_password != self.new_password_confirm {
+      self.error = Some("비밀번호가 일치하지 않습니다".to_string());
+      return;
+    }
+
+    match create(&self.new_password) {
+      Ok((signer, json)) => {
+        self.keystores.push(StoredKeystore {
+          name: self.keystore_name.trim().to_string(),
+          address: format!("{:#x}", signer.address()),
+          json,
+        });
+        self.selected_keystore = self.keystores.len() - 1;
+        self.keystore_name = "".to_string();
+        self.new_password = "".to_string();
+        self.new_password_confirm = "".to_string();
+        self.unlock(Ok(signer));
+      }
+      Err(e) => self.error = Some(e),
+    }
+  }
+
+  fn keystores_ui(&mut self, ui: &mut egui::Ui) {
+    let export_label = if cfg!(target_arch = "wasm32") {
+      "내보내기"
+    } else {
+      "JSON 복사"
+    };
+    let mut removed = None;
+    let mut error = None;
+    for (i, keystore) in self.keystores.iter().enumerate() {
+      ui.horizontal(|ui| {
+        ui.label(keystore.title());
+        if ui.button(export_label).clicked() {
+          error = keystore.export(ui.ctx()).err();
+        }
+        if ui.button("삭제").clicked() {
+          removed = Some(i);
+        }
+      });
+    }
+    if error.is_some() {
+      self.error = error;
+    }
+    if let Some(i) = removed {
+      self.keystores.remove(i);
+      self.selected_keystore = 0;
+    }
+
+    ui.separator();
+    ui.label("V3 키스토어 JSON 가져오기");
+    ui.horizontal(|ui| {
+      ui.label("이름");
+      ui.text_edit_singleline(&mut self.keystore_name);
+    });
+    ui.add(
+      egui::TextEdit::multiline(&mut self.import_json)
+        .code_editor()
+        .desired_rows(4)
+        .desired_width(f32::INFINITY),
+    );
+    if ui.button("가져오기").clicked() {
+      match keystore::address(&self.import_json) {
+        Ok(address) => {
+          self.keystores.push(StoredKeystore {
+            name: self.keystore_name.trim().to_string(),
+            address: address.unwrap_or_default(),
+            json: self.import_json.trim().to_string(),
+          });
+          self.keystore_name = "".to_string();
+          self.import_json = "".to_string();
+          self.error = None;
+        }
+        Err(e) => self.error = Some(e.to_string()),
+      }
+    }
+  }
+
+  fn unlock(&mut self, signer: Result<Signer, String>) {
+    match signer {
+      Ok(signer) => {
+        self.signer = Some(signer);
+        self.secret = "".to_string();
+        self.password = "".to_string();
+        self.error = None;
+      }
+      Err(e) => self.error = Some(e),
+    }
+  }
+}
+
+impl PendingTx {
+  /// The hash and fees of a successful send, returned only once.
+  fn take_sent(&mut self) -> Option<(TxHash, Prepared)> {
+    if self.recorded {
+      return None;
+    }
+    let Some(Some(Ok(hash))) = self.sent.as_ref().map(Promise::ready) else {
+      return None;
+    };
+    let Some(Some(Ok(prepared))) = self.prepared.as_ref().map(Promise::ready)
+    else {
+      return None;
+    };
+
+    self.recorded = true;
+    Some((*hash, prepared.clone()))
+  }
+
+  /// Draws the confirmation dialog, amounts in the native symbol of the
+  /// request's `chain`; `false` once the user closes it.
+  fn ui(
+    &mut self,
+    ctx: &egui::Context,
+    account: Option<&Account>,
+    chain: Option<&ChainSettings>,
+  ) -> bool {
+    let mut open = true;
+    let symbol = chain
+      .map(|chain| chain.native_symbol.as_str())
+      .filter(|symbol| !symbol.is_empty())
+      .unwrap_or("ETH");
+
+    // Estimate again if the user switched accounts before sending.
+    if let (Some(account), Some(Some(Ok(prepared)))) =
+      (account, self.prepared.as_ref().map(Promise::ready))
+    {
+      if prepared.from != account.address() && self.sent.is_none() {
+        self.prepared = None;
+      }
+    }
+    if let (Some(account), None) = (account, &self.prepared) {
+      let account = account.clone();
+      let request = self.request.clone();
+      self.prepared = Some(Promise::spawn_local(async move {
+        account.prepare(&request).await
+      }));
+    }
+
+    egui::Window::new("트랜잭션 확인")
+      .id(egui::Id::new(("pending_tx", self.id)))
+      .collapsible(false)
+      .resizable(false)
+      .show(ctx, |ui| {
+        let request = &self.request;
+        ui.heading(&request.label);
+
+        egui::Grid::new("pending_tx_fields").show(ui, |ui| {
+          ui.label("to");
+          ui.monospace(format!("{:#x}", request.to));
+          ui.end_row();
+          ui.label("value");
+          ui.label(format!("{} {}", format_ether(request.value), symbol));
+          ui.end_row();
+          ui.label("data");
+          ui.monospace(truncate(&request.data.to_string(), 74));
+          ui.end_row();
+
+          if let Some(Some(Ok(prepared))) =
+            self.prepared.as_ref().map(Promise::ready)
+          {
+            ui.label("from");
+            ui.monospace(format!("{:#x}", prepared.from));
+            ui.end_row();
+            ui.label("chain id");
+            ui.label(prepared.chain_id.to_string());
+            ui.end_row();
+            ui.label("gas limit");
+            ui.label(prepared.gas.to_string());
+            ui.end_row();
+            ui.label("max fee");
+            ui.label(format!("{} gwei", gwei(prepared.max_fee_per_gas)));
+            ui.end_row();
+            ui.label("priority fee");
+            ui.label(format!(
+              "{} gwei",
+              gwei(prepared.max_priority_fee_per_gas)
+            ));
+            ui.end_row();
+            ui.label("최대 비용");
+            ui.label(format!(
+              "{} {}",
+              format_ether(prepared.max_cost(request.value)),
+              symbol
+            ));
+            ui.end_row();
+          }
+        });
+
+        let prepared =
+          match (account, self.prepared.as_ref().map(Promise::ready)) {
+            (None, _) => {
+              ui.label("서명 지갑을 불러오거나 브라우저 지갑을 연결하세요");
+              None
+            }
+            (_, None) | (_, Some(None)) => {
+              ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("가스 추정 중...");
+              });
+              None
+            }
+            (_, Some(Some(Err(e)))) => {
+              ui.colored_label(ui.visuals().error_fg_color, e);
+              None
+            }
+            (Some(account), Some(Some(Ok(prepared)))) => {
+              Some((account.clone(), prepared.clone()))
+            }
+          };
+
+        let mismatch =
+          mismatched_chain_id(chain, prepared.as_ref().map(|(_, p)| p));
+        if let Some((expected, actual)) = mismatch {
+          ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!(
+              "⚠ 체인 ID 불일치: 설정 {}, RPC {}. 체인 설정을 확인하세요",
+              expected, actual
+            ),
+          );
+        }
+
+        match self.sent.as_ref().map(Promise::ready) {
+          None => {
+            ui.horizontal(|ui| {
+              if ui
+                .add_enabled(
+                  prepared.is_some() && mismatch.is_none(),
+                  egui::Button::new("보내기"),
+                )
+                .clicked()
+              {
+                let (account, prepared) = prepared.unwrap();
+                let request = self.request.clone();
+                self.sent = Some(Promise::spawn_local(async move {
+                  account.send(&request, &prepared).await
+                }));
+              }
+              if ui.button("취소").clicked() {
+                report(&self.request, Err("취소됨".to_string()));
+                open = false;
+              }
+            });
+          }
+          Some(None) => {
+            ui.horizontal(|ui| {
+              ui.spinner();
+              ui.label("전송 중...");
+            });
+          }
+          Some(Some(result)) => {
+            report(&self.request, result.clone());
+            match result {
+              Ok(hash) => {
+                ui.label("전송 완료");
+                ui.monospace(format!("{:#x}", hash));
+              }
+              Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+              }
+            }
+            if ui.button("닫기").clicked() {
+              open = false;
+            }
+          }
+        }
+      });
+
+    open
+  }
+}
+
+/// `(expected, actual)` when the chain settings expect another chain than
+/// the RPC reports, either to the settings' probe or to the estimate.
+/// Signing then would target the wrong chain.
+fn mismatched_chain_id(
+  chain: Option<&ChainSettings>,
+  prepared: Option<&Prepared>,
+) -> Option<(u64, u64)> {
+  let chain = chain?;
+  let expected = chain.chain_id?;
+  match prepared {
+    Some(prepared) if prepared.chain_id != expected => {
+      Some((expected, prepared.chain_id))
+    }
+    _ if chain.is_mismatch() => Some((expected, chain.rpc_chain_id?)),
+    _ => None,
+  }
+}
+
+/// Hands the send result to whoever queued `request`, once.
+fn report(request: &TxRequest, result: Result<TxHash, String>) {
+  if let Some(outcome) = &request.outcome {
+    outcome.lock().unwrap().get_or_insert(result);
+  }
+}
+
+fn gwei(wei: ethers::types::U256) -> String {
+  format_units(wei, "gwei").unwrap_or_default()
+}
+
+fn short_address(address: ethers::types::Address) -> String {
+  let address = format!("{:#x}", address);
+  format!("{}…{}", &address[..6], &address[address.len() - 4..])
+}
+
+fn truncate(s: &str, max: usize) -> String {
+  if s.len() > max {
+    format!("{}...", &s[..max])
+  } else {
+    s.to_string()
+  }
+}
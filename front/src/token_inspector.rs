@@ -0,0 +1,373 @@
+use base64::Engine;
+use ethers::types::{Address, U256};
+use poll_promise::Promise;
+use shared::contract_abis::ierc721::IERC721;
+
+use crate::rpc::{self, RpcProvider};
+
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// `name`/`symbol`/`totalSupply` of the contract. `totalSupply` is only
+/// there for contracts with the enumerable extension.
+pub struct CollectionInfo {
+  pub name: String,
+  pub symbol: String,
+  pub total_supply: Option<U256>,
+}
+
+pub struct Token {
+  pub token_id: U256,
+  pub token_uri: String,
+  pub metadata: serde_json::Value,
+  /// Decoded `image`, or why it could not be shown.
+  pub image: Option<Result<egui::ColorImage, String>>,
+}
+
+/// Resolves `tokenURI` for one token and previews its metadata and image.
+pub struct TokenInspector {
+  pub token_id: String,
+  /// Treat `token_id` as an index for `tokenByIndex`.
+  pub by_index: bool,
+
+  collection: Option<Promise<Result<CollectionInfo, String>>>,
+  token: Option<Promise<Result<Token, String>>>,
+  texture: Option<egui::TextureHandle>,
+}
+
+impl Default for TokenInspector {
+  fn default() -> Self {
+    Self {
+      token_id: "".to_string(),
+      by_index: false,
+
+      collection: None,
+      token: None,
+      texture: None,
+    }
+  }
+}
+
+impl TokenInspector {
+  pub fn ui(
+    &mut self,
+    ui: &mut egui::Ui,
+    contract_address: &str,
+    rpc_url: &str,
+    ipfs_gateway: &str,
+  ) {
+    if ui.button("컬렉션 정보").clicked() {
+      self.collection = Some(match contract(contract_address, rpc_url) {
+        Ok(contract) => Promise::spawn_local(collection_info(contract)),
+        Err(e) => Promise::from_ready(Err(e)),
+      });
+    }
+    if let Some(collection) = &self.collection {
+      match collection.ready() {
+        None => {
+          ui.spinner();
+        }
+        Some(Ok(info)) => {
+          ui.label(format!("name: {}", info.name));
+          ui.label(format!("symbol: {}", info.symbol));
+          match info.total_supply {
+            Some(total_supply) => {
+              ui.label(format!("totalSupply: {}", total_supply))
+            }
+            None => ui.label("totalSupply: 지원하지 않음"),
+          };
+        }
+        Some(Err(e)) => {
+          ui.colored_label(ui.visuals().error_fg_color, e);
+        }
+      }
+    }
+
+    ui.separator();
+
+    ui.horizontal(|ui| {
+      ui.label(if self.by_index { "index" } else { "tokenId" });
+      ui.text_edit_singleline(&mut self.token_id);
+      ui.checkbox(&mut self.by_index, "tokenByIndex");
+    });
+
+    let loading = self
+      .token
+      .as_ref()
+      .is_some_and(|token| token.ready().is_none());
+
+    if ui
+      .add_enabled(!loading, egui::Button::new("조회"))
+      .clicked()
+    {
+      self.texture = None;
+      self.token = Some(
+        match (
+          contract(contract_address, rpc_url),
+          parse_u256(&self.token_id),
+        ) {
+          (Ok(contract), Ok(id)) => Promise::spawn_local(inspect(
+            contract,
+            id,
+            self.by_index,
+            ipfs_gateway.to_string(),
+          )),
+          (Err(e), _) | (_, Err(e)) => Promise::from_ready(Err(e)),
+        },
+      );
+    }
+
+    let Some(token) = &self.token else {
+      return;
+    };
+
+    match token.ready() {
+      None => {
+        ui.horizontal(|ui| {
+          ui.spinner();
+          ui.label("tokenURI 불러오는 중...");
+        });
+      }
+      Some(Err(e)) => {
+        ui.colored_label(ui.visuals().error_fg_color, e);
+      }
+      Some(Ok(token)) => {
+        ui.label(format!("tokenId: {}", token.token_id));
+        ui.horizontal_wrapped(|ui| {
+          ui.label("tokenURI:");
+          if token.token_uri.starts_with("data:") {
+            ui.label(format!("{}...", truncate(&token.token_uri, 48)));
+          } else {
+            ui.hyperlink(&token.token_uri);
+          }
+        });
+
+        match &token.image {
+          None => {}
+          Some(Ok(image)) => {
+            let texture = self.texture.get_or_insert_with(|| {
+              ui.ctx().load_texture(
+                format!("token-{}-{}", contract_address, token.token_id),
+                image.clone(),
+                Default::default(),
+              )
+            });
+            ui.add(egui::Image::new(&*texture).max_width(256.0));
+          }
+          Some(Err(e)) => {
+            ui.colored_label(ui.visuals().error_fg_color, e);
+          }
+        }
+
+        metadata_ui(ui, &token.metadata);
+      }
+    }
+  }
+}
+
+fn metadata_ui(ui: &mut egui::Ui, metadata: &serde_json::Value) {
+  for key in ["name", "description"] {
+    if let Some(value) = metadata.get(key).and_then(|v| v.as_str()) {
+      ui.label(format!("{}: {}", key, value));
+    }
+  }
+
+  if let Some(attributes) =
+    metadata.get("attributes").and_then(|a| a.as_array())
+  {
+    egui::Grid::new("token_attributes")
+      .striped(true)
+      .show(ui, |ui| {
+        for attribute in attributes {
+          ui.label(json_text(&attribute["trait_type"]));
+          ui.label(json_text(&attribute["value"]));
+          ui.end_row();
+        }
+      });
+  }
+
+  ui.collapsing("JSON", |ui| {
+    let mut json = serde_json::to_string_pretty(metadata).unwrap_or_default();
+    ui.add(
+      egui::TextEdit::multiline(&mut json)
+        .code_editor()
+        .desired_width(f32::INFINITY),
+    );
+  });
+}
+
+fn contract(
+  contract_address: &str,
+  rpc_url: &str,
+) -> Result<IERC721<RpcProvider>, String> {
+  let address = contract_address
+    .parse::<Address>()
+    .map_err(|_| format!("잘못된 컨트랙트 주소: {}", contract_address))?;
+  let provider = rpc::provider(rpc_url)?;
+
+  Ok(IERC721::new(address, provider))
+}
+
+async fn collection_info(
+  contract: IERC721<RpcProvider>,
+) -> Result<CollectionInfo, String> {
+  let name = contract
+    .name()
+    .call()
+    .await
+    .map_err(|e| format!("name: {}", e))?;
+  let symbol = contract
+    .symbol()
+    .call()
+    .await
+    .map_err(|e| format!("symbol: {}", e))?;
+  let total_supply = contract.total_supply().call().await.ok();
+
+  Ok(CollectionInfo {
+    name,
+    symbol,
+    total_supply,
+  })
+}
+
+async fn inspect(
+  contract: IERC721<RpcProvider>,
+  id: U256,
+  by_index: bool,
+  ipfs_gateway: String,
+) -> Result<Token, String> {
+  let token_id = if by_index {
+    contract
+      .token_by_index(id)
+      .call()
+      .await
+      .map_err(|e| format!("tokenByIndex({}): {}", id, e))?
+  } else {
+    id
+  };
+
+  let token_uri = contract
+    .token_uri(token_id)
+    .call()
+    .await
+    .map_err(|e| format!("tokenURI({}): {}", token_id, e))?;
+
+  let body = load(&token_uri, &ipfs_gateway).await?;
+  let metadata: serde_json::Value = serde_json::from_slice(&body)
+    .map_err(|e| format!("메타데이터 JSON 오류: {}", e))?;
+
+  let image = match metadata.get("image").and_then(|image| image.as_str()) {
+    Some(image) if !image.is_empty() => Some(
+      load(image, &ipfs_gateway)
+        .await
+        .and_then(|bytes| decode_image(&bytes)),
+    ),
+    _ => None,
+  };
+
+  Ok(Token {
+    token_id,
+    token_uri,
+    metadata,
+    image,
+  })
+}
+
+/// Bytes behind a token or image URI: inline `data:` URIs are decoded,
+/// `ipfs://` goes through `ipfs_gateway`, `http(s)://` is fetched as is.
+async fn load(uri: &str, ipfs_gateway: &str) -> Result<Vec<u8>, String> {
+  if let Some(data) = uri.strip_prefix("data:") {
+    return decode_data_uri(data);
+  }
+
+  let url = if let Some(path) = uri.strip_prefix("ipfs://") {
+    let path = path.strip_prefix("ipfs/").unwrap_or(path);
+    format!("{}/{}", ipfs_gateway.trim_end_matches('/'), path)
+  } else if uri.starts_with("http://") || uri.starts_with("https://") {
+    uri.to_string()
+  } else {
+    return Err(format!("지원하지 않는 URI: {}", truncate(uri, 64)));
+  };
+
+  let response = ehttp::fetch_async(ehttp::Request::get(&url))
+    .await
+    .map_err(|e| format!("{}: {}", url, e))?;
+  if !response.ok {
+    return Err(format!(
+      "{}: {} {}",
+      url, response.status, response.status_text
+    ));
+  }
+
+  Ok(response.bytes)
+}
+
+/// The part after `data:`, i.e. `[<mediatype>][;base64],<data>`.
+pub fn decode_data_uri(data: &str) -> Result<Vec<u8>, String> {
+  let (header, payload) = data
+    .split_once(',')
+    .ok_or_else(|| "잘못된 data URI".to_string())?;
+
+  if header.ends_with(";base64") {
+    base64::engine::general_purpose::STANDARD
+      .decode(payload.trim())
+      .map_err(|e| format!("data URI base64 오류: {}", e))
+  } else {
+    Ok(percent_decode(payload))
+  }
+}
+
+/// `%XY` escapes to bytes; a `%` not followed by two hex digits is kept.
+pub fn percent_decode(s: &str) -> Vec<u8> {
+  let bytes = s.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    let hex = bytes
+      .get(i + 1..i + 3)
+      .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match (bytes[i], hex) {
+      (b'%', Some(byte)) => {
+        decoded.push(byte);
+        i += 3;
+      }
+      (byte, _) => {
+        decoded.push(byte);
+        i += 1;
+      }
+    }
+  }
+  decoded
+}
+
+fn decode_image(bytes: &[u8]) -> Result<egui::ColorImage, String> {
+  let image = image::load_from_memory(bytes)
+    .map_err(|e| format!("이미지를 표시할 수 없습니다: {}", e))?
+    .to_rgba8();
+  let size = [image.width() as usize, image.height() as usize];
+
+  Ok(egui::ColorImage::from_rgba_unmultiplied(
+    size,
+    image.as_raw(),
+  ))
+}
+
+fn json_text(value: &serde_json::Value) -> String {
+  match value {
+    serde_json::Value::String(s) => s.clone(),
+    value => value.to_string(),
+  }
+}
+
+fn parse_u256(value: &str) -> Result<U256, String> {
+  U256::from_dec_str(value.trim())
+    .map_err(|_| format!("잘못된 숫자: {}", value))
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+  match s.char_indices().nth(max) {
+    Some((i, _)) => &s[..i],
+    None => s,
+  }
+}
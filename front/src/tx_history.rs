@@ -0,0 +1,530 @@
+use ethers::{
+  abi::{Abi, ParamType, RawLog, Token},
+  providers::{Middleware, RpcError},
+  types::{
+    transaction::eip2718::TypedTransaction, Address, BlockId, BlockNumber,
+    Bytes, Eip1559TransactionRequest, Log, TransactionReceipt, TxHash, U256,
+  },
+  utils::format_units,
+};
+use poll_promise::Promise;
+
+use crate::contract_abi::{format_token, AbiContractWindow};
+use crate::rpc::{self, RpcProvider};
+use crate::signer::{Prepared, TxRequest};
+
+/// Seconds between `eth_getTransactionReceipt` polls for a pending tx.
+pub const POLL_INTERVAL: f64 = 4.0;
+/// Oldest entries are dropped past this many.
+const MAX_ENTRIES: usize = 200;
+/// Polls in a row the node may not know the tx before it counts as dropped
+/// from the mempool, about ten minutes.
+pub const MAX_UNSEEN_POLLS: u32 = 150;
+
+/// `Error(string)` and `Panic(uint256)` selectors from Solidity.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Every transaction sent from the signer, persisted with the app state.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TxHistoryWindow {
+  pub is_open: bool,
+
+  entries: Vec<TxRecord>,
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum TxStatus {
+  Pending,
+  Confirmed,
+  Failed,
+  /// Never mined: replaced by another tx with the same nonce, or gone from
+  /// the mempool.
+  Dropped,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TxRecord {
+  pub hash: TxHash,
+  pub chain_id: u64,
+  pub rpc_url: String,
+  pub from: Address,
+  pub to: Address,
+  /// The label the sending window gave, e.g. `approve(0x.., 1)`.
+  pub method: String,
+  /// Learned from the node on the first poll that sees the tx.
+  pub nonce: Option<U256>,
+  pub status: TxStatus,
+  pub block_number: Option<u64>,
+  pub gas_used: Option<U256>,
+  pub effective_gas_price: Option<U256>,
+  pub revert_reason: Option<String>,
+  pub events: Vec<String>,
+
+  #[serde(skip)]
+  poll: Option<Promise<Result<Outcome, String>>>,
+  #[serde(skip)]
+  next_poll: f64,
+  /// Polls in a row the node did not know the tx.
+  #[serde(skip)]
+  unseen_polls: u32,
+  #[serde(skip)]
+  error: Option<String>,
+}
+
+impl Default for TxRecord {
+  fn default() -> Self {
+    Self {
+      hash: TxHash::zero(),
+      chain_id: 0,
+      rpc_url: "".to_string(),
+      from: Address::zero(),
+      to: Address::zero(),
+      method: "".to_string(),
+      nonce: None,
+      status: TxStatus::Pending,
+      block_number: None,
+      gas_used: None,
+      effective_gas_price: None,
+      revert_reason: None,
+      events: vec![],
+
+      poll: None,
+      next_poll: 0.0,
+      unseen_polls: 0,
+      error: None,
+    }
+  }
+}
+
+/// What one receipt poll found.
+pub enum Outcome {
+  /// The receipt, plus the revert data from replaying the tx if it failed.
+  Mined {
+    receipt: TransactionReceipt,
+    revert: Option<Bytes>,
+  },
+  /// Not mined yet. `nonce` is the tx's, `None` when the node does not know
+  /// the tx (any more).
+  Waiting { nonce: Option<U256> },
+  /// The sender's nonce moved past the tx without mining it.
+  Replaced,
+}
+
+impl TxHistoryWindow {
+  pub fn show(&mut self, ui: &mut egui::Ui) {
+    ui.checkbox(&mut self.is_open, "트랜잭션 기록");
+  }
+
+  /// Adds a just-broadcast transaction; its receipt is polled from here on.
+  pub fn record(
+    &mut self,
+    hash: TxHash,
+    request: &TxRequest,
+    prepared: &Prepared,
+  ) {
+    self.entries.insert(
+      0,
+      TxRecord {
+        hash,
+        chain_id: prepared.chain_id,
+        rpc_url: request.rpc_url.clone(),
+        from: prepared.from,
+        to: request.to,
+        method: request.label.clone(),
+        ..TxRecord::default()
+      },
+    );
+    self.entries.truncate(MAX_ENTRIES);
+    self.is_open = true;
+  }
+
+  pub fn update(
+    &mut self,
+    ctx: &egui::Context,
+    _frame: &mut eframe::Frame,
+    abi_contract: &AbiContractWindow,
+  ) {
+    let now = ctx.input(|i| i.time);
+    let mut abis = None;
+    for entry in &mut self.entries {
+      if entry.poll(now) {
+        let abis = abis.get_or_insert_with(|| abi_contract.known_abis());
+        entry.apply_outcome(abis);
+      }
+    }
+    if self
+      .entries
+      .iter()
+      .any(|entry| entry.status == TxStatus::Pending)
+    {
+      ctx.request_repaint_after(std::time::Duration::from_secs_f64(
+        POLL_INTERVAL,
+      ));
+    }
+
+    egui::Window::new("트랜잭션 기록")
+      .open(&mut self.is_open)
+      .vscroll(true)
+      .show(ctx, |ui| {
+        if self.entries.is_empty() {
+          ui.label("보낸 트랜잭션이 없습니다");
+          return;
+        }
+
+        if ui.button("기록 지우기").clicked() {
+          self.entries.clear();
+          return;
+        }
+
+        let mut removed = None;
+        for (i, entry) in self.entries.iter().enumerate() {
+          let title = format!("{} {}", entry.status.icon(), entry.method);
+          egui::CollapsingHeader::new(title)
+            .id_source(entry.hash)
+            .show(ui, |ui| {
+              entry.ui(ui);
+              if ui.button("삭제").clicked() {
+                removed = Some(i);
+              }
+            });
+        }
+        if let Some(i) = removed {
+          self.entries.remove(i);
+        }
+      });
+  }
+}
+
+impl TxStatus {
+  fn icon(&self) -> &'static str {
+    match self {
+      TxStatus::Pending => "⏳",
+      TxStatus::Confirmed => "✅",
+      TxStatus::Failed => "❌",
+      TxStatus::Dropped => "🚫",
+    }
+  }
+
+  fn label(&self) -> &'static str {
+    match self {
+      TxStatus::Pending => "대기 중",
+      TxStatus::Confirmed => "성공",
+      TxStatus::Failed => "실패",
+      TxStatus::Dropped => "채굴되지 않음",
+    }
+  }
+}
+
+impl TxRecord {
+  /// Starts or collects a receipt poll; `true` when a receipt has arrived
+  /// and is waiting for [`TxRecord::apply_outcome`].
+  fn poll(&mut self, now: f64) -> bool {
+    if self.status != TxStatus::Pending {
+      return false;
+    }
+
+    match self.poll.as_ref().map(Promise::ready) {
+      None if now >= self.next_poll => {
+        let rpc_url = self.rpc_url.clone();
+        let (hash, from, nonce) = (self.hash, self.from, self.nonce);
+        self.poll = Some(Promise::spawn_local(async move {
+          fetch_outcome(&rpc_url, hash, from, nonce).await
+        }));
+        false
+      }
+      Some(Some(Ok(Outcome::Mined { .. }))) => true,
+      Some(Some(_)) => {
+        match self.poll.take().and_then(|p| p.try_take().ok()) {
+          Some(Ok(Outcome::Waiting { nonce: Some(nonce) })) => {
+            self.nonce = Some(nonce);
+            self.unseen_polls = 0;
+            self.error = None;
+          }
+          Some(Ok(Outcome::Waiting { nonce: None })) => {
+            self.unseen_polls += 1;
+            if self.unseen_polls >= MAX_UNSEEN_POLLS {
+              self
+                .drop_with("노드가 트랜잭션을 찾지 못합니다 (멤풀에서 제외됨)");
+            }
+          }
+          Some(Ok(Outcome::Replaced)) => {
+            self.drop_with("같은 nonce의 다른 트랜잭션이 먼저 채굴되었습니다");
+          }
+          Some(Err(e)) => self.error = Some(e),
+          _ => {}
+        }
+        self.next_poll = now + POLL_INTERVAL;
+        false
+      }
+      _ => false,
+    }
+  }
+
+  fn drop_with(&mut self, reason: &str) {
+    self.status = TxStatus::Dropped;
+    self.error = Some(reason.to_string());
+  }
+
+  fn apply_outcome(&mut self, abis: &[Abi]) {
+    let Some(Ok(Outcome::Mined { receipt, revert })) =
+      self.poll.take().and_then(|p| p.try_take().ok())
+    else {
+      return;
+    };
+
+    self.block_number = receipt.block_number.map(|block| block.as_u64());
+    self.gas_used = receipt.gas_used;
+    self.effective_gas_price = receipt.effective_gas_price;
+    self.events = receipt
+      .logs
+      .iter()
+      .map(|log| decode_log(log, abis))
+      .collect();
+    self.error = None;
+
+    if receipt.status.map(|status| status.as_u64()) == Some(1) {
+      self.status = TxStatus::Confirmed;
+    } else {
+      self.status = TxStatus::Failed;
+      self.revert_reason = Some(match revert {
+        Some(data) => decode_revert(&data, abis),
+        None => "재현되지 않음 (가스 부족일 수 있습니다)".to_string(),
+      });
+    }
+  }
+
+  fn ui(&self, ui: &mut egui::Ui) {
+    egui::Grid::new(("tx_record", self.hash)).show(ui, |ui| {
+      ui.label("상태");
+      ui.label(self.status.label());
+      ui.end_row();
+      ui.label("해시");
+      ui.horizontal(|ui| {
+        ui.monospace(format!("{:#x}", self.hash));
+        if ui.small_button("📋").clicked() {
+          ui.output_mut(|o| o.copied_text = format!("{:#x}", self.hash));
+        }
+      });
+      ui.end_row();
+      ui.label("chain id");
+      ui.label(self.chain_id.to_string());
+      ui.end_row();
+      ui.label("from");
+      ui.monospace(format!("{:#x}", self.from));
+      ui.end_row();
+      ui.label("to");
+      ui.monospace(format!("{:#x}", self.to));
+      ui.end_row();
+      if let Some(block) = self.block_number {
+        ui.label("블록");
+        ui.label(block.to_string());
+        ui.end_row();
+      }
+      if let Some(gas_used) = self.gas_used {
+        ui.label("사용한 가스");
+        ui.label(gas_used.to_string());
+        ui.end_row();
+      }
+      if let (Some(gas_used), Some(price)) =
+        (self.gas_used, self.effective_gas_price)
+      {
+        ui.label("수수료");
+        ui.label(format!(
+          "{} ETH",
+          format_units(gas_used * price, "ether").unwrap_or_default()
+        ));
+        ui.end_row();
+      }
+      if let Some(reason) = &self.revert_reason {
+        ui.label("revert");
+        ui.colored_label(ui.visuals().error_fg_color, reason);
+        ui.end_row();
+      }
+    });
+
+    if self.status == TxStatus::Pending {
+      ui.horizontal(|ui| {
+        ui.spinner();
+        ui.label("영수증 기다리는 중...");
+      });
+    }
+    if let Some(e) = &self.error {
+      ui.colored_label(ui.visuals().error_fg_color, e);
+    }
+
+    if !self.events.is_empty() {
+      ui.label(format!("이벤트 {}개", self.events.len()));
+      for event in &self.events {
+        ui.monospace(event);
+      }
+    }
+  }
+}
+
+/// The receipt of `hash` if it is mined. Otherwise whether it can still be:
+/// once `from`'s mined nonce count passes the tx's nonce, another tx took
+/// its place.
+pub async fn fetch_outcome(
+  rpc_url: &str,
+  hash: TxHash,
+  from: Address,
+  nonce: Option<U256>,
+) -> Result<Outcome, String> {
+  let provider = rpc::provider(rpc_url)?;
+  let rpc_error =
+    |e: ethers::providers::ProviderError| format!("RPC 오류: {}", e);
+
+  let receipt = provider
+    .get_transaction_receipt(hash)
+    .await
+    .map_err(rpc_error)?;
+  if let Some(receipt) = receipt {
+    return mined(&provider, hash, receipt).await;
+  }
+
+  let seen = provider
+    .get_transaction(hash)
+    .await
+    .map_err(rpc_error)?
+    .map(|tx| tx.nonce);
+  let Some(nonce) = seen.or(nonce) else {
+    return Ok(Outcome::Waiting { nonce: None });
+  };
+
+  let mined_nonces = provider
+    .get_transaction_count(from, Some(BlockNumber::Latest.into()))
+    .await
+    .map_err(rpc_error)?;
+  if mined_nonces <= nonce {
+    return Ok(Outcome::Waiting { nonce: seen });
+  }
+
+  // The nonce is used; unless it was this tx, mined since the first look.
+  match provider
+    .get_transaction_receipt(hash)
+    .await
+    .map_err(rpc_error)?
+  {
+    Some(receipt) => mined(&provider, hash, receipt).await,
+    None => Ok(Outcome::Replaced),
+  }
+}
+
+async fn mined(
+  provider: &RpcProvider,
+  hash: TxHash,
+  receipt: TransactionReceipt,
+) -> Result<Outcome, String> {
+  let revert = match (receipt.status, receipt.block_number) {
+    (Some(status), Some(block)) if status.as_u64() == 0 => {
+      replay(provider, hash, block.as_u64()).await
+    }
+    _ => None,
+  };
+
+  Ok(Outcome::Mined { receipt, revert })
+}
+
+/// Re-runs a failed tx as `eth_call` on the state before its block to get
+/// the revert data the receipt does not carry.
+async fn replay(
+  provider: &RpcProvider,
+  hash: TxHash,
+  block: u64,
+) -> Option<Bytes> {
+  let tx = provider.get_transaction(hash).await.ok()??;
+  let call: TypedTransaction = Eip1559TransactionRequest::new()
+    .from(tx.from)
+    .to(tx.to?)
+    .data(tx.input)
+    .value(tx.value)
+    .gas(tx.gas)
+    .into();
+
+  let block = BlockId::from(block.saturating_sub(1));
+  match provider.call(&call, Some(block)).await {
+    Ok(_) => None,
+    Err(e) => e.as_error_response().and_then(|e| e.as_revert_data()),
+  }
+}
+
+/// `Error(string)`, `Panic(uint256)`, or a custom error from `abis`.
+pub fn decode_revert(data: &[u8], abis: &[Abi]) -> String {
+  if data.len() < 4 {
+    return "revert (사유 없음)".to_string();
+  }
+  let (selector, args) = data.split_at(4);
+
+  if selector == ERROR_SELECTOR {
+    if let Ok(tokens) = ethers::abi::decode(&[ParamType::String], args) {
+      if let Some(Token::String(reason)) = tokens.first() {
+        return reason.clone();
+      }
+    }
+  }
+  if selector == PANIC_SELECTOR {
+    if let Ok(tokens) = ethers::abi::decode(&[ParamType::Uint(256)], args) {
+      if let Some(Token::Uint(code)) = tokens.first() {
+        return format!("Panic(0x{:02x})", code);
+      }
+    }
+  }
+
+  for error in abis.iter().flat_map(|abi| abi.errors()) {
+    if error.signature()[..4] != *selector {
+      continue;
+    }
+    if let Ok(tokens) = error.decode(args) {
+      let kinds = error.inputs.iter().map(|input| &input.kind);
+      let args = tokens
+        .iter()
+        .zip(kinds)
+        .map(|(token, kind)| format_token(token, kind))
+        .collect::<Vec<_>>();
+      return format!("{}({})", error.name, args.join(", "));
+    }
+  }
+
+  format!("revert {}", Bytes::from(data.to_vec()))
+}
+
+/// `Name(arg=value, ..) @ 0xemitter`, or the raw topic when no ABI in
+/// `abis` knows the event.
+pub fn decode_log(log: &Log, abis: &[Abi]) -> String {
+  let raw = RawLog {
+    topics: log.topics.clone(),
+    data: log.data.to_vec(),
+  };
+
+  let Some(topic) = log.topics.first() else {
+    return format!("anonymous @ {:#x}", log.address);
+  };
+  for event in abis.iter().flat_map(|abi| abi.events()) {
+    if event.anonymous || event.signature() != *topic {
+      continue;
+    }
+    // ERC-20 and ERC-721 `Transfer` share a topic but differ in indexing,
+    // so a failed parse just means "try the next one".
+    if let Ok(parsed) = event.parse_log(raw.clone()) {
+      let args = parsed
+        .params
+        .iter()
+        .zip(&event.inputs)
+        .map(|(param, input)| {
+          format!("{}={}", param.name, format_token(&param.value, &input.kind))
+        })
+        .collect::<Vec<_>>();
+      return format!(
+        "{}({}) @ {:#x}",
+        event.name,
+        args.join(", "),
+        log.address
+      );
+    }
+  }
+
+  format!("{:#x} @ {:#x}", topic, log.address)
+}
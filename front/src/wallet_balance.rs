@@ -1,17 +1,16 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
-use ethers::{
-  providers::{Http, Middleware, Provider},
-  types::{Address, U256},
-};
+use ethers::{providers::Middleware, types::Address};
 use poll_promise::Promise;
 
 use crate::{
   chain_settings::{ChainSettings, ChainSettingsWindow},
+  rpc::{self, RpcProvider},
   wallet_settings::{WalletInfo, WalletSettingsWindow},
 };
 
-#[derive(serde::Deserialize, serde::Serialize)]
+#[derive(Default, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct WalletBalanceWindow {
   pub is_open: bool,
@@ -20,15 +19,6 @@ pub struct WalletBalanceWindow {
   pub balances: Option<Promise<BTreeMap<String, BTreeMap<String, String>>>>,
 }
 
-impl Default for WalletBalanceWindow {
-  fn default() -> Self {
-    Self {
-      is_open: false,
-      balances: None,
-    }
-  }
-}
-
 impl WalletBalanceWindow {
   pub fn show(&mut self, ui: &mut egui::Ui) {
     ui.checkbox(&mut self.is_open, "지갑 잔액");
@@ -58,12 +48,11 @@ impl WalletBalanceWindow {
             ui.label("지갑 이름");
             if let Some(s) = &self.balances {
               if let Some(r) = s.ready() {
-                for (_, balance) in r.iter() {
+                if let Some((_, balance)) = r.iter().next() {
                   for (chain_name, _) in balance.iter() {
                     ui.label(chain_name);
                   }
                   ui.end_row();
-                  break;
                 }
               } else {
                 ui.add(egui::widgets::ProgressBar::new(0.5));
@@ -74,7 +63,7 @@ impl WalletBalanceWindow {
               if let Some(r) = s.ready() {
                 for (address, balance) in r.iter() {
                   let address_name =
-                    address.split(":").collect::<Vec<&str>>()[0];
+                    address.split(':').collect::<Vec<&str>>()[0];
                   ui.label(address_name);
 
                   for (_, balance) in balance.iter() {
@@ -102,18 +91,17 @@ pub fn get_balance(
     BTreeMap<std::string::String, std::string::String>,
   >,
 > {
-  // TODO: 올바른 URL이 아닐 때 처리
   let wallet_infos = wallet_settings.clone();
   let chain_settings = chain_settings.clone();
-  let mut providers: Vec<(Provider<Http>, String)> = Vec::new();
+  let mut providers: Vec<(Result<Arc<RpcProvider>, String>, String)> =
+    Vec::new();
 
   for chain_info in chain_settings.iter() {
-    let provider =
-      Provider::<Http>::try_from(chain_info.rpc_url.clone()).unwrap();
+    let provider = rpc::provider(&chain_info.rpc_url);
     providers.push((provider, chain_info.chain_name.clone()));
   }
 
-  let promise = Promise::spawn_local(async move {
+  Promise::spawn_local(async move {
     let mut balances = BTreeMap::new();
 
     for wallet_info in wallet_infos.iter() {
@@ -137,15 +125,21 @@ pub fn get_balance(
           continue;
         };
 
-        let balance = provider.get_balance(address, None).await;
+        let balance = match provider {
+          Ok(provider) => provider
+            .get_balance(address, None)
+            .await
+            .map_err(|e| format!("RPC 오류: {}", e)),
+          Err(e) => Err(e.clone()),
+        };
 
         balances.get_mut(&wallet_key).unwrap().insert(
           chain_name.clone(),
           match balance {
             Ok(balance) => ethers::utils::format_ether(balance),
             Err(e) => {
-              log::info!("get_balance error: {:?}", e);
-              ethers::utils::format_ether(U256::zero())
+              log::info!("get_balance error: {}", e);
+              e
             }
           },
         );
@@ -153,7 +147,5 @@ pub fn get_balance(
     }
 
     balances
-  });
-
-  promise
+  })
 }
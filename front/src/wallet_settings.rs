@@ -1,8 +1,4 @@
-use ethers::{
-  prelude::{Http, Provider},
-  types::Address,
-};
-use poll_promise::Promise;
+use ethers::types::Address;
 
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
@@ -55,8 +51,11 @@ impl WalletSettingsWindow {
 
           for i in 0..self.wallet_addresses.len() {
             let res = ui.horizontal(|ui| {
-              let font_color = if let Ok(_) =
-                self.wallet_addresses[i].address.clone().parse::<Address>()
+              let font_color = if self.wallet_addresses[i]
+                .address
+                .clone()
+                .parse::<Address>()
+                .is_ok()
               {
                 egui::Color32::from_rgb(255, 255, 255)
               } else {
@@ -80,7 +79,7 @@ impl WalletSettingsWindow {
               }
             });
 
-            if res.inner == false {
+            if !res.inner {
               break;
             }
           }
@@ -89,25 +88,6 @@ impl WalletSettingsWindow {
   }
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
-#[serde(default)]
-pub struct ChainSettings {
-  pub chain_name: String,
-  pub rpc_url: String,
-  #[serde(skip)]
-  pub provider: Option<Promise<Provider<Http>>>,
-}
-
-impl Default for ChainSettings {
-  fn default() -> Self {
-    Self {
-      chain_name: "".to_string(),
-      rpc_url: "".to_string(),
-      provider: None,
-    }
-  }
-}
-
 #[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
 #[serde(default)]
 pub struct WalletInfo {
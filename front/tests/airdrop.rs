@@ -0,0 +1,54 @@
+use ethers::types::{Address, U256};
+use front::airdrop::{Airdrop, AirdropMode};
+
+const ALICE: &str = "0x00000000000000000000000000000000000000aa";
+const BOB: &str = "0x00000000000000000000000000000000000000bb";
+
+fn load(mode: AirdropMode, csv: &str) -> Airdrop {
+  let mut airdrop = Airdrop::default();
+  airdrop.mode = mode;
+  airdrop.csv = csv.to_string();
+  airdrop.load();
+  airdrop
+}
+
+#[test]
+fn skips_a_header_line() {
+  let airdrop = load(
+    AirdropMode::Transfer,
+    &format!("\nrecipient,token_id\n{ALICE},7\n"),
+  );
+  assert!(airdrop.parse_errors().is_empty());
+  assert_eq!(airdrop.items.len(), 1);
+  assert_eq!(airdrop.items[0].line, 3);
+  assert_eq!(
+    airdrop.items[0].recipient,
+    ALICE.parse::<Address>().unwrap()
+  );
+  assert_eq!(airdrop.items[0].token_id, U256::from(7));
+}
+
+#[test]
+fn reports_a_bad_first_row_instead_of_skipping_it() {
+  let airdrop = load(
+    AirdropMode::Transfer,
+    &format!("0x12,1\n{ALICE},2\n{BOB},3"),
+  );
+  assert_eq!(airdrop.parse_errors(), ["1번째 줄: 잘못된 주소 0x12"]);
+  assert_eq!(airdrop.items.len(), 2);
+
+  let airdrop = load(AirdropMode::Mint, &format!("alice,1\n{BOB},2"));
+  assert_eq!(airdrop.parse_errors(), ["1번째 줄: 잘못된 주소 alice"]);
+  assert_eq!(airdrop.items.len(), 2);
+}
+
+#[test]
+fn mints_consecutive_token_ids() {
+  let airdrop = load(AirdropMode::Mint, &format!("{ALICE},2\n{BOB},1"));
+  let ids = airdrop
+    .items
+    .iter()
+    .map(|item| item.token_id.as_u64())
+    .collect::<Vec<_>>();
+  assert_eq!(ids, [0, 1, 2]);
+}
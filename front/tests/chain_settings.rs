@@ -0,0 +1,61 @@
+use front::chain_settings::{ChainSettings, CHAIN_PRESETS};
+
+#[test]
+fn matches_until_the_rpc_answers_another_chain() {
+  let mut chain = ChainSettings {
+    chain_id: Some(1),
+    ..ChainSettings::default()
+  };
+  assert!(!chain.is_mismatch());
+
+  chain.set_rpc_chain_id(1);
+  assert!(!chain.is_mismatch());
+
+  chain.set_rpc_chain_id(137);
+  assert!(chain.is_mismatch());
+  assert_eq!(chain.chain_id, Some(1));
+  assert_eq!(chain.rpc_chain_id, Some(137));
+}
+
+#[test]
+fn never_mismatches_without_an_expected_id() {
+  let chain = ChainSettings {
+    rpc_chain_id: Some(5),
+    ..ChainSettings::default()
+  };
+  assert!(!chain.is_mismatch());
+}
+
+#[test]
+fn adopts_the_rpc_chain_and_its_preset() {
+  let mut chain = ChainSettings::default();
+  chain.set_rpc_chain_id(137);
+
+  assert_eq!(chain.chain_id, Some(137));
+  assert!(!chain.is_mismatch());
+  assert_eq!(chain.native_symbol, "POL");
+  assert_eq!(chain.explorer_url, "https://polygonscan.com");
+}
+
+#[test]
+fn keeps_a_symbol_and_explorer_set_by_hand() {
+  let mut chain = ChainSettings {
+    native_symbol: "MATIC".to_string(),
+    explorer_url: "https://example.com".to_string(),
+    ..ChainSettings::default()
+  };
+  chain.set_rpc_chain_id(137);
+
+  assert_eq!(chain.native_symbol, "MATIC");
+  assert_eq!(chain.explorer_url, "https://example.com");
+}
+
+#[test]
+fn keeps_an_expected_id_and_the_preset_fields() {
+  let mut chain = ChainSettings::from(&CHAIN_PRESETS[0]);
+  chain.set_rpc_chain_id(56);
+
+  assert_eq!(chain.chain_id, Some(1));
+  assert_eq!(chain.native_symbol, "ETH");
+  assert_eq!(chain.explorer_url, "https://etherscan.io");
+}
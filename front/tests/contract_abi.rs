@@ -0,0 +1,148 @@
+use ethers::abi::{ParamType, Token};
+use ethers::types::{Address, U256};
+use front::contract_abi::{format_token, parse_abi, Input};
+use serde_json::json;
+
+fn abi() -> serde_json::Value {
+  json!([{
+    "type": "function",
+    "name": "transfer",
+    "stateMutability": "nonpayable",
+    "inputs": [
+      {"name": "to", "type": "address"},
+      {"name": "value", "type": "uint256"}
+    ],
+    "outputs": [{"name": "", "type": "bool"}]
+  }])
+}
+
+fn text(value: &str) -> Input {
+  Input::Text(value.to_string())
+}
+
+#[test]
+fn parses_a_bare_abi_array() {
+  let abi = parse_abi(&abi().to_string()).unwrap();
+  assert_eq!(abi.function("transfer").unwrap().inputs.len(), 2);
+}
+
+#[test]
+fn parses_build_artifacts() {
+  let artifact =
+    json!({"contractName": "Token", "abi": abi(), "bytecode": "0x"});
+  let abi = parse_abi(&artifact.to_string()).unwrap();
+  assert!(abi.function("transfer").is_ok());
+}
+
+#[test]
+fn parses_etherscan_responses() {
+  let response =
+    json!({"status": "1", "message": "OK", "result": abi().to_string()});
+  let abi = parse_abi(&response.to_string()).unwrap();
+  assert!(abi.function("transfer").is_ok());
+}
+
+#[test]
+fn rejects_what_is_not_an_abi() {
+  assert!(parse_abi("").is_err());
+  assert!(parse_abi(r#"{"name": "Token"}"#).is_err());
+  // Etherscan's answer for unverified contracts.
+  assert!(parse_abi(
+    r#"{"status":"0","result":"Contract source code not verified"}"#
+  )
+  .is_err());
+  assert!(parse_abi(r#"[{"type": "function", "inputs": 1}]"#).is_err());
+}
+
+#[test]
+fn tokenizes_scalar_inputs() {
+  let to = Address::repeat_byte(0x11);
+  assert_eq!(
+    text(&format!(" {:#x} ", to)).to_token(&ParamType::Address),
+    Ok(Token::Address(to))
+  );
+  assert_eq!(
+    text("1000").to_token(&ParamType::Uint(256)),
+    Ok(Token::Uint(U256::from(1000)))
+  );
+  assert_eq!(
+    Input::Bool(true).to_token(&ParamType::Bool),
+    Ok(Token::Bool(true))
+  );
+  assert!(text("-1").to_token(&ParamType::Uint(256)).is_err());
+  assert!(text("0x12").to_token(&ParamType::Address).is_err());
+}
+
+#[test]
+fn tokenizes_nested_inputs() {
+  let kind = ParamType::Tuple(vec![
+    ParamType::Uint(8),
+    ParamType::Array(Box::new(ParamType::String)),
+  ]);
+  let mut input = Input::new(&kind);
+  let Input::Tuple(fields) = &mut input else {
+    panic!("expected a tuple input");
+  };
+  fields[0] = text("7");
+  fields[1] = Input::Array(vec![text("a"), text("b")]);
+
+  assert_eq!(
+    input.to_token(&kind),
+    Ok(Token::Tuple(vec![
+      Token::Uint(U256::from(7)),
+      Token::Array(vec![
+        Token::String("a".to_string()),
+        Token::String("b".to_string()),
+      ]),
+    ]))
+  );
+
+  let fixed = ParamType::FixedArray(Box::new(ParamType::Bool), 2);
+  assert_eq!(
+    Input::new(&fixed).to_token(&fixed),
+    Ok(Token::FixedArray(vec![
+      Token::Bool(false),
+      Token::Bool(false)
+    ]))
+  );
+
+  // A form built for another type.
+  assert!(Input::Tuple(vec![])
+    .to_token(&ParamType::Array(Box::new(ParamType::Bool)))
+    .is_err());
+}
+
+#[test]
+fn formats_output_tokens() {
+  let minus_one = Token::Int(U256::MAX);
+  assert_eq!(format_token(&minus_one, &ParamType::Int(256)), "-1");
+  assert_eq!(
+    format_token(&Token::Uint(U256::from(42)), &ParamType::Uint(256)),
+    "42"
+  );
+  assert_eq!(
+    format_token(
+      &Token::Address(Address::repeat_byte(0xab)),
+      &ParamType::Address
+    ),
+    format!("{:#x}", Address::repeat_byte(0xab))
+  );
+  assert_eq!(
+    format_token(&Token::String("hi".to_string()), &ParamType::String),
+    "\"hi\""
+  );
+  assert_eq!(
+    format_token(&Token::Bytes(vec![0xde, 0xad]), &ParamType::Bytes),
+    "0xdead"
+  );
+
+  let kind = ParamType::Tuple(vec![
+    ParamType::Bool,
+    ParamType::Array(Box::new(ParamType::Int(8))),
+  ]);
+  let token = Token::Tuple(vec![
+    Token::Bool(true),
+    Token::Array(vec![minus_one, Token::Int(U256::from(2))]),
+  ]);
+  assert_eq!(format_token(&token, &kind), "(true, [-1, 2])");
+}
@@ -0,0 +1,87 @@
+use ethers::types::{Address, U256};
+use front::disperse::{is_header, parse_rows};
+
+const ALICE: &str = "0x00000000000000000000000000000000000000aa";
+const BOB: &str = "0x00000000000000000000000000000000000000bb";
+
+fn address(s: &str) -> Address {
+  s.parse().unwrap()
+}
+
+#[test]
+fn skips_a_header_line() {
+  let rows = parse_rows(&format!("address,amount\n{ALICE},1.5\n"), 18);
+  assert_eq!(rows.len(), 1);
+  assert_eq!(rows[0].line, 2);
+  assert_eq!(
+    rows[0].parsed,
+    Ok((address(ALICE), U256::from(15) * U256::exp10(17)))
+  );
+}
+
+#[test]
+fn skips_a_header_after_blank_lines() {
+  let rows = parse_rows(&format!("\n  \naddress,amount\n{ALICE},2\n"), 0);
+  assert_eq!(rows.len(), 1);
+  assert_eq!(rows[0].line, 4);
+  assert_eq!(rows[0].parsed, Ok((address(ALICE), U256::from(2))));
+}
+
+#[test]
+fn keeps_a_first_line_that_is_an_address() {
+  let rows = parse_rows(&format!("{ALICE},1\n\n{BOB},2"), 0);
+  assert_eq!(rows.len(), 2);
+  assert_eq!(rows[1].line, 3);
+  assert_eq!(rows[1].parsed, Ok((address(BOB), U256::from(2))));
+}
+
+#[test]
+fn reports_a_bad_first_row_instead_of_skipping_it() {
+  let rows = parse_rows(&format!("0x12,1\n{ALICE},1"), 0);
+  assert_eq!(rows.len(), 2);
+  assert_eq!(rows[0].line, 1);
+  assert_eq!(rows[0].parsed, Err("잘못된 주소".to_string()));
+
+  let rows = parse_rows(&format!("alice,1\n{ALICE},1"), 0);
+  assert_eq!(rows.len(), 2);
+  assert_eq!(rows[0].parsed, Err("잘못된 주소".to_string()));
+}
+
+#[test]
+fn tells_headers_from_data() {
+  assert!(is_header("address,amount"));
+  assert!(is_header(" Recipient , Token ID "));
+  assert!(!is_header("0X12,amount"));
+  assert!(!is_header("alice,1.5"));
+  assert!(!is_header(ALICE));
+}
+
+#[test]
+fn rejects_zero_amounts() {
+  let rows = parse_rows(&format!("{ALICE},0"), 18);
+  assert_eq!(rows[0].parsed, Err("금액이 0입니다".to_string()));
+}
+
+#[test]
+fn rejects_negative_amounts() {
+  let rows = parse_rows(&format!("{ALICE},-1"), 18);
+  assert_eq!(rows[0].parsed, Err("음수 금액".to_string()));
+}
+
+#[test]
+fn rejects_bad_addresses_and_amounts() {
+  let rows = parse_rows(&format!("{ALICE},1\n0x12,1\n{BOB},abc"), 0);
+  assert_eq!(rows[1].parsed, Err("잘못된 주소".to_string()));
+  assert_eq!(rows[2].parsed, Err("잘못된 금액".to_string()));
+}
+
+#[test]
+fn flags_the_row_that_overflows_the_total() {
+  let max = U256::MAX.to_string();
+  let rows = parse_rows(&format!("{ALICE},{max}\n{BOB},1\n{ALICE},0"), 0);
+  assert_eq!(rows[0].parsed, Ok((address(ALICE), U256::MAX)));
+  assert_eq!(
+    rows[1].parsed,
+    Err("합계가 uint256 범위를 넘습니다".to_string())
+  );
+}
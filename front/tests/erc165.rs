@@ -0,0 +1,34 @@
+use front::erc165::{format_interface_id, parse_interface_id, INTERFACES};
+
+#[test]
+fn parses_interface_ids_with_or_without_prefix() {
+  let erc721 = [0x80, 0xac, 0x58, 0xcd];
+  assert_eq!(parse_interface_id("0x80ac58cd"), Ok(erc721));
+  assert_eq!(parse_interface_id("80ac58cd"), Ok(erc721));
+  assert_eq!(parse_interface_id(" 0x80AC58CD "), Ok(erc721));
+  assert_eq!(parse_interface_id("0x00000000"), Ok([0; 4]));
+}
+
+#[test]
+fn rejects_malformed_interface_ids() {
+  for value in [
+    "",
+    "0x",
+    "0x80ac58",
+    "0x80ac58cd00",
+    "0xzzac58cd",
+    "+0ac58cd",
+  ] {
+    assert!(parse_interface_id(value).is_err(), "{:?}", value);
+  }
+}
+
+#[test]
+fn formats_what_it_parses() {
+  for (name, interface_id) in INTERFACES {
+    let formatted = format_interface_id(interface_id);
+    assert_eq!(formatted.len(), 10, "{}", name);
+    assert_eq!(parse_interface_id(&formatted), Ok(interface_id), "{}", name);
+  }
+  assert_eq!(format_interface_id([0, 0, 0, 1]), "0x00000001");
+}
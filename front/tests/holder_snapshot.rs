@@ -0,0 +1,110 @@
+use ethers::contract::LogMeta;
+use ethers::types::{Address, H256, U256, U64};
+use front::holder_snapshot::{
+  to_csv, token_range, Holders, Replay, MAX_OWNER_OF_TOKENS,
+};
+use shared::contract_abis::ierc721::TransferFilter;
+
+fn transfer(
+  block: u64,
+  log_index: u64,
+  from: Address,
+  to: Address,
+  token_id: u64,
+) -> (TransferFilter, LogMeta) {
+  let transfer = TransferFilter {
+    from,
+    to,
+    token_id: U256::from(token_id),
+  };
+  let meta = LogMeta {
+    address: Address::repeat_byte(0xc0),
+    block_number: U64::from(block),
+    block_hash: H256::from_low_u64_be(block),
+    transaction_hash: H256::from_low_u64_be(block * 100 + log_index),
+    transaction_index: U64::zero(),
+    log_index: U256::from(log_index),
+  };
+  (transfer, meta)
+}
+
+fn holder(n: u8) -> Address {
+  Address::repeat_byte(n)
+}
+
+#[test]
+fn writes_one_row_per_holder() {
+  let mut holders = Holders::new();
+  holders
+    .entry(holder(0xaa))
+    .or_default()
+    .extend([U256::from(3), U256::from(1)]);
+  holders
+    .entry(holder(0x0b))
+    .or_default()
+    .insert(U256::from(2));
+
+  assert_eq!(
+    to_csv(&holders),
+    format!(
+      "holder,count,token_ids\n{:#x},1,2\n{:#x},2,1 3\n",
+      holder(0x0b),
+      holder(0xaa)
+    )
+  );
+}
+
+#[test]
+fn writes_only_the_header_without_holders() {
+  assert_eq!(to_csv(&Holders::new()), "holder,count,token_ids\n");
+}
+
+#[test]
+fn keeps_the_last_owner_and_drops_burned_tokens() {
+  let mut replay = Replay::default();
+  // Out of order within the range on purpose.
+  replay.push(vec![
+    transfer(5, 0, holder(1), holder(2), 1),
+    transfer(3, 0, Address::zero(), holder(1), 1),
+    transfer(3, 1, Address::zero(), holder(1), 2),
+    transfer(4, 0, holder(1), Address::zero(), 2),
+  ]);
+
+  let holders = replay.holders();
+  assert_eq!(holders.len(), 1);
+  assert_eq!(holders[&holder(2)], [U256::from(1)].into());
+}
+
+#[test]
+fn ignores_logs_repeated_across_ranges() {
+  let mut replay = Replay::default();
+  replay.push(vec![
+    transfer(10, 0, Address::zero(), holder(1), 7),
+    transfer(11, 0, holder(1), holder(2), 7),
+  ]);
+  // The next range repeats block 10; replaying it again would hand token 7
+  // back to holder 1.
+  replay.push(vec![
+    transfer(10, 0, Address::zero(), holder(1), 7),
+    transfer(12, 0, holder(2), holder(3), 8),
+  ]);
+
+  let holders = replay.holders();
+  assert_eq!(holders[&holder(2)], [U256::from(7)].into());
+  assert_eq!(holders[&holder(3)], [U256::from(8)].into());
+  assert!(!holders.contains_key(&holder(1)));
+}
+
+#[test]
+fn caps_the_owner_of_token_range() {
+  let last = (MAX_OWNER_OF_TOKENS - 1).to_string();
+  assert_eq!(
+    token_range("0", &last),
+    Ok((U256::zero(), U256::from(MAX_OWNER_OF_TOKENS - 1)))
+  );
+
+  assert!(token_range("0", &MAX_OWNER_OF_TOKENS.to_string()).is_err());
+  assert!(token_range("0", &U256::MAX.to_string()).is_err());
+  assert!(token_range("5", "4").is_err());
+  assert!(token_range("0", "x").is_err());
+}
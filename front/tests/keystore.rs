@@ -0,0 +1,182 @@
+use ethers::core::rand::thread_rng;
+use ethers::utils::hex;
+use front::signer::keystore::{address, decrypt, encrypt, KeystoreError};
+
+/// Key and password shared by both vectors from the Web3 Secret Storage
+/// Definition.
+const PASSWORD: &str = "testpassword";
+const PRIVATE_KEY: &str =
+  "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+const PBKDF2: &str = r#"{
+  "crypto": {
+    "cipher": "aes-128-ctr",
+    "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+    "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+    "kdf": "pbkdf2",
+    "kdfparams": {
+      "c": 262144,
+      "dklen": 32,
+      "prf": "hmac-sha256",
+      "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+    },
+    "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+  },
+  "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+  "version": 3
+}"#;
+
+/// The spec's scrypt vector. Its `n = 2^18, r = 1` breaks RFC 7914's
+/// `n < 2^(16 * r)`, so the `scrypt` crate refuses it.
+const SPEC_SCRYPT: &str = r#"{
+  "crypto": {
+    "cipher": "aes-128-ctr",
+    "cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+    "ciphertext": "d172bf743a674da9cdad04534d56926ef8358534d458fffccd4e6ad2fbde479c",
+    "kdf": "scrypt",
+    "kdfparams": {
+      "dklen": 32,
+      "n": 262144,
+      "p": 8,
+      "r": 1,
+      "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+    },
+    "mac": "2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097"
+  },
+  "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+  "version": 3
+}"#;
+
+/// The same key under scrypt `n = 2^13, r = 8, p = 1`, generated with
+/// OpenSSL's scrypt and AES-128-CTR.
+const SCRYPT: &str = r#"{
+  "address": "008aeeda4d805471df9b2a5b0f38a0c3bcba786b",
+  "crypto": {
+    "cipher": "aes-128-ctr",
+    "cipherparams": { "iv": "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff" },
+    "ciphertext": "ba5fb3901ed95a17478375697ec9085d564490e593a92acbe4fa5cbf58efb247",
+    "kdf": "scrypt",
+    "kdfparams": {
+      "dklen": 32,
+      "n": 8192,
+      "p": 1,
+      "r": 8,
+      "salt": "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+    },
+    "mac": "5846f26f4760428adc1b41a6ff04d14aec0d8b08b1e804c8653a9d6f8251b7fa"
+  },
+  "id": "7e59dc02-8d42-409d-b29a-a8a0f862cc81",
+  "version": 3
+}"#;
+
+#[test]
+fn pbkdf2_vector() {
+  let private_key = decrypt(PBKDF2, PASSWORD).unwrap();
+  assert_eq!(hex::encode(private_key), PRIVATE_KEY);
+}
+
+#[test]
+fn scrypt_vector() {
+  let private_key = decrypt(SCRYPT, PASSWORD).unwrap();
+  assert_eq!(hex::encode(private_key), PRIVATE_KEY);
+  assert_eq!(
+    address(SCRYPT).unwrap().as_deref(),
+    Some("0x008aeeda4d805471df9b2a5b0f38a0c3bcba786b")
+  );
+}
+
+#[test]
+fn spec_scrypt_vector_is_unsupported() {
+  assert_eq!(
+    decrypt(SPEC_SCRYPT, PASSWORD),
+    Err(KeystoreError::Unsupported(
+      "scrypt n = 262144, r = 1, p = 8".to_string()
+    ))
+  );
+}
+
+#[test]
+fn refuses_costly_kdf_params() {
+  for (from, to) in [
+    // 2^20 * 8 * 128 bytes = 1 GiB.
+    ("\"n\": 8192", "\"n\": 1048576"),
+    ("\"r\": 8", "\"r\": 4294967295"),
+    ("\"p\": 1", "\"p\": 1000"),
+    ("\"dklen\": 32", "\"dklen\": 4294967295"),
+  ] {
+    assert!(
+      matches!(
+        decrypt(&SCRYPT.replace(from, to), PASSWORD),
+        Err(KeystoreError::Unsupported(_))
+      ),
+      "{}",
+      to
+    );
+  }
+  assert!(matches!(
+    decrypt(&PBKDF2.replace("262144", "4294967295"), PASSWORD),
+    Err(KeystoreError::Unsupported(_))
+  ));
+}
+
+#[test]
+fn refuses_malformed_kdf_params() {
+  for (from, to) in [
+    ("\"n\": 8192", "\"n\": 8000"),
+    ("\"n\": 8192", "\"n\": 1"),
+    ("\"r\": 8", "\"r\": 0"),
+    ("\"p\": 1", "\"p\": 0"),
+  ] {
+    assert!(
+      matches!(
+        decrypt(&SCRYPT.replace(from, to), PASSWORD),
+        Err(KeystoreError::Format(_))
+      ),
+      "{}",
+      to
+    );
+  }
+  assert!(matches!(
+    decrypt(&SCRYPT.replace("\"dklen\": 32", "\"dklen\": 16"), PASSWORD),
+    Err(KeystoreError::Unsupported(_))
+  ));
+}
+
+#[test]
+fn wrong_password() {
+  assert_eq!(
+    decrypt(PBKDF2, "wrongpassword"),
+    Err(KeystoreError::WrongPassword)
+  );
+}
+
+#[test]
+fn not_a_keystore() {
+  assert!(matches!(
+    decrypt("{}", PASSWORD),
+    Err(KeystoreError::Format(_))
+  ));
+  assert!(matches!(
+    decrypt(
+      &PBKDF2.replace("\"version\": 3", "\"version\": 1"),
+      PASSWORD
+    ),
+    Err(KeystoreError::Unsupported(_))
+  ));
+}
+
+#[test]
+fn round_trip() {
+  let private_key = hex::decode(PRIVATE_KEY).unwrap();
+  let json = encrypt(&mut thread_rng(), &private_key, PASSWORD).unwrap();
+
+  assert_eq!(
+    address(&json).unwrap().as_deref(),
+    Some("0x008aeeda4d805471df9b2a5b0f38a0c3bcba786b")
+  );
+  assert_eq!(decrypt(&json, PASSWORD).unwrap(), private_key);
+  assert_eq!(
+    decrypt(&json, "wrongpassword"),
+    Err(KeystoreError::WrongPassword)
+  );
+}
@@ -0,0 +1,95 @@
+use front::rpc::{is_pinned, rank, Health, MAX_LAG};
+use serde_json::json;
+
+fn healthy(block_number: u64, latency_ms: u64) -> Health {
+  Health {
+    chain_id: Some(1),
+    block_number: Some(block_number),
+    latency_ms: Some(latency_ms),
+    error: None,
+  }
+}
+
+#[test]
+fn ranks_healthy_endpoints_by_latency() {
+  let healths = [healthy(100, 300), healthy(100, 50), healthy(100, 120)];
+  assert_eq!(rank(&healths, Some(1)), [1, 2, 0]);
+}
+
+#[test]
+fn ranks_failing_and_lagging_endpoints_last() {
+  let failing = Health {
+    error: Some("connection refused".to_string()),
+    ..healthy(100, 10)
+  };
+  let lagging = healthy(100 - MAX_LAG - 1, 20);
+  let healths = [failing, lagging, healthy(100, 500)];
+  assert_eq!(rank(&healths, Some(1)), [2, 0, 1]);
+}
+
+#[test]
+fn keeps_an_endpoint_just_within_the_lag() {
+  let healths = [healthy(100 - MAX_LAG, 20), healthy(100, 500)];
+  assert_eq!(rank(&healths, Some(1)), [0, 1]);
+}
+
+#[test]
+fn ranks_unprobed_endpoints_after_timed_ones() {
+  let healths = [Health::default(), healthy(100, 900)];
+  assert_eq!(rank(&healths, Some(1)), [1, 0]);
+}
+
+#[test]
+fn leaves_out_endpoints_on_another_chain() {
+  let other = Health {
+    chain_id: Some(5),
+    ..healthy(100, 10)
+  };
+  let healths = [other, healthy(100, 50), Health::default()];
+  assert_eq!(rank(&healths, Some(1)), [1, 2]);
+  // Without an expected chain there is nothing to compare against.
+  assert_eq!(rank(&healths, None), [0, 1, 2]);
+}
+
+#[test]
+fn pins_reads_at_a_block_number_or_hash() {
+  let call = json!({ "to": "0x00000000000000000000000000000000000000aa" });
+  assert!(is_pinned("eth_call", &json!([call, "0x10"])));
+  assert!(is_pinned(
+    "eth_getBalance",
+    &json!(["0xaa", { "blockHash": "0x01" }])
+  ));
+  assert!(is_pinned(
+    "eth_getStorageAt",
+    &json!(["0xaa", "0x0", "0x10"])
+  ));
+  assert!(is_pinned("eth_getBlockByNumber", &json!(["0x10", false])));
+  assert!(is_pinned(
+    "eth_getLogs",
+    &json!([{ "fromBlock": "0x1", "toBlock": "0x10" }])
+  ));
+  assert!(is_pinned("eth_getLogs", &json!([{ "blockHash": "0x01" }])));
+}
+
+#[test]
+fn does_not_pin_reads_at_a_tag() {
+  let call = json!({ "to": "0x00000000000000000000000000000000000000aa" });
+  assert!(!is_pinned("eth_call", &json!([call, "latest"])));
+  assert!(!is_pinned("eth_call", &json!([call])));
+  assert!(!is_pinned("eth_getBalance", &json!(["0xaa", "latest"])));
+  assert!(!is_pinned(
+    "eth_getTransactionCount",
+    &json!(["0xaa", "pending"])
+  ));
+  assert!(!is_pinned(
+    "eth_getLogs",
+    &json!([{ "fromBlock": "0x1", "toBlock": "latest" }])
+  ));
+}
+
+#[test]
+fn does_not_pin_other_methods() {
+  assert!(!is_pinned("eth_getTransactionReceipt", &json!(["0x01"])));
+  assert!(!is_pinned("eth_blockNumber", &json!([])));
+  assert!(!is_pinned("eth_sendRawTransaction", &json!(["0x02"])));
+}
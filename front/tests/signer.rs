@@ -0,0 +1,69 @@
+use ethers::types::Address;
+use front::signer::Signer;
+
+const MNEMONIC: &str =
+  "test test test test test test test test test test test junk";
+/// First two accounts of [`MNEMONIC`], as Hardhat and Anvil list them.
+const ACCOUNT_0: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+const ACCOUNT_1: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+const KEY_0: &str =
+  "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+fn address(value: &str) -> Address {
+  value.parse().unwrap()
+}
+
+#[test]
+fn derives_accounts_from_a_mnemonic() {
+  let signer = Signer::from_mnemonic(MNEMONIC, "", 0).unwrap();
+  assert_eq!(signer.address(), address(ACCOUNT_0));
+
+  let signer = Signer::from_mnemonic(MNEMONIC, "", 1).unwrap();
+  assert_eq!(signer.address(), address(ACCOUNT_1));
+
+  // Surrounding whitespace from pasting is ignored.
+  let signer =
+    Signer::from_mnemonic(&format!("  {}\n", MNEMONIC), "", 0).unwrap();
+  assert_eq!(signer.address(), address(ACCOUNT_0));
+}
+
+#[test]
+fn a_mnemonic_password_changes_the_accounts() {
+  let signer = Signer::from_mnemonic(MNEMONIC, "secret", 0).unwrap();
+  assert_ne!(signer.address(), address(ACCOUNT_0));
+}
+
+#[test]
+fn rejects_bad_mnemonics() {
+  // Wrong checksum word, and too few words.
+  let bad_checksum = MNEMONIC.replace("junk", "test");
+  assert!(Signer::from_mnemonic(&bad_checksum, "", 0).is_err());
+  assert!(Signer::from_mnemonic("test test test", "", 0).is_err());
+  assert!(Signer::from_mnemonic("", "", 0).is_err());
+}
+
+#[test]
+fn loads_private_keys_with_or_without_prefix() {
+  for key in [
+    KEY_0.to_string(),
+    format!("0x{}", KEY_0),
+    format!(" 0x{}\n", KEY_0),
+  ] {
+    let signer = Signer::from_private_key(&key).unwrap();
+    assert_eq!(signer.address(), address(ACCOUNT_0), "{:?}", key);
+  }
+}
+
+#[test]
+fn rejects_bad_private_keys() {
+  for key in [
+    "",
+    "0x",
+    &KEY_0[..62],
+    &format!("{}00", KEY_0),
+    &KEY_0.replace('a', "g"),
+    "0000000000000000000000000000000000000000000000000000000000000000",
+  ] {
+    assert!(Signer::from_private_key(key).is_err(), "{:?}", key);
+  }
+}
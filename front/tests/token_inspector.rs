@@ -0,0 +1,46 @@
+use front::token_inspector::{decode_data_uri, percent_decode};
+
+#[test]
+fn decodes_base64_data_uris() {
+  assert_eq!(
+    decode_data_uri("application/json;base64,eyJuYW1lIjoiIzEifQ==").unwrap(),
+    br##"{"name":"#1"}"##
+  );
+  // Whitespace around the payload, as some contracts emit it.
+  assert_eq!(decode_data_uri(";base64, aGk= ").unwrap(), b"hi");
+}
+
+#[test]
+fn decodes_percent_encoded_data_uris() {
+  assert_eq!(
+    decode_data_uri("application/json,%7B%22name%22%3A%22a%20b%22%7D").unwrap(),
+    br#"{"name":"a b"}"#
+  );
+  // Only the first comma separates the header.
+  assert_eq!(decode_data_uri("text/plain,a,b").unwrap(), b"a,b");
+  assert_eq!(
+    decode_data_uri("image/svg+xml;utf8,<svg/>").unwrap(),
+    b"<svg/>"
+  );
+}
+
+#[test]
+fn rejects_malformed_data_uris() {
+  assert!(decode_data_uri("application/json").is_err());
+  assert!(decode_data_uri(";base64,not base64!").is_err());
+}
+
+#[test]
+fn percent_decodes_bytes() {
+  assert_eq!(percent_decode("a%20b%2Fc%2fd"), b"a b/c/d");
+  assert_eq!(percent_decode("%E2%9C%93"), "✓".as_bytes());
+  assert_eq!(percent_decode(""), b"");
+}
+
+#[test]
+fn keeps_stray_percent_signs() {
+  assert_eq!(percent_decode("100%"), b"100%");
+  assert_eq!(percent_decode("%4"), b"%4");
+  assert_eq!(percent_decode("%zz%41"), b"%zzA");
+  assert_eq!(percent_decode("%+1"), b"%+1");
+}
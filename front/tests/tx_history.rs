@@ -0,0 +1,133 @@
+use ethers::abi::{encode, Abi, Token};
+use ethers::types::{Address, Bytes, Log, H256, U256};
+use ethers::utils::keccak256;
+use front::tx_history::{decode_log, decode_revert};
+use shared::contract_abis::{ierc20::IERC20_ABI, ierc721::IERC721_ABI};
+
+fn with_selector(signature: &str, args: &[Token]) -> Vec<u8> {
+  [&keccak256(signature)[..4], &encode(args)].concat()
+}
+
+fn custom_errors() -> Abi {
+  serde_json::from_str(
+    r#"[{
+      "type": "error",
+      "name": "InsufficientBalance",
+      "inputs": [
+        {"name": "available", "type": "uint256"},
+        {"name": "required", "type": "uint256"}
+      ]
+    }]"#,
+  )
+  .unwrap()
+}
+
+fn address_topic(address: Address) -> H256 {
+  H256::from(address)
+}
+
+fn transfer_log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+  Log {
+    address: Address::repeat_byte(0xc0),
+    topics,
+    data: Bytes::from(data),
+    ..Log::default()
+  }
+}
+
+#[test]
+fn decodes_error_strings() {
+  let data =
+    with_selector("Error(string)", &[Token::String("not owner".to_string())]);
+  assert_eq!(decode_revert(&data, &[]), "not owner");
+}
+
+#[test]
+fn decodes_panic_codes() {
+  let data = with_selector("Panic(uint256)", &[Token::Uint(U256::from(0x11))]);
+  assert_eq!(decode_revert(&data, &[]), "Panic(0x11)");
+}
+
+#[test]
+fn decodes_custom_errors_from_known_abis() {
+  let data = with_selector(
+    "InsufficientBalance(uint256,uint256)",
+    &[Token::Uint(U256::from(1)), Token::Uint(U256::from(5))],
+  );
+  assert_eq!(
+    decode_revert(&data, &[IERC20_ABI.clone(), custom_errors()]),
+    "InsufficientBalance(1, 5)"
+  );
+  // Without the ABI only the raw data is left.
+  assert_eq!(
+    decode_revert(&data, &[]),
+    format!("revert {}", Bytes::from(data.clone()))
+  );
+}
+
+#[test]
+fn reports_empty_and_malformed_revert_data() {
+  assert_eq!(decode_revert(&[], &[]), "revert (사유 없음)");
+  assert_eq!(decode_revert(&[0x08, 0xc3], &[]), "revert (사유 없음)");
+  // The `Error(string)` selector with arguments that are not a string.
+  let data = [0x08, 0xc3, 0x79, 0xa0, 0x01];
+  assert_eq!(decode_revert(&data, &[]), "revert 0x08c379a001");
+}
+
+#[test]
+fn tells_erc20_and_erc721_transfers_apart() {
+  let topic = H256::from(keccak256("Transfer(address,address,uint256)"));
+  let (from, to) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+  let contract = format!("{:#x}", Address::repeat_byte(0xc0));
+
+  // Same topic; ERC-20 keeps the amount in data, ERC-721 indexes the ID.
+  let erc20 = transfer_log(
+    vec![topic, address_topic(from), address_topic(to)],
+    encode(&[Token::Uint(U256::from(500))]),
+  );
+  let erc721 = transfer_log(
+    vec![
+      topic,
+      address_topic(from),
+      address_topic(to),
+      H256::from_low_u64_be(7),
+    ],
+    vec![],
+  );
+
+  for abis in [
+    [IERC20_ABI.clone(), IERC721_ABI.clone()],
+    [IERC721_ABI.clone(), IERC20_ABI.clone()],
+  ] {
+    assert_eq!(
+      decode_log(&erc20, &abis),
+      format!(
+        "Transfer(from={:#x}, to={:#x}, value=500) @ {}",
+        from, to, contract
+      )
+    );
+    assert_eq!(
+      decode_log(&erc721, &abis),
+      format!(
+        "Transfer(from={:#x}, to={:#x}, tokenId=7) @ {}",
+        from, to, contract
+      )
+    );
+  }
+}
+
+#[test]
+fn falls_back_to_the_raw_topic() {
+  let topic = H256::from(keccak256("Unknown()"));
+  let log = transfer_log(vec![topic], vec![]);
+  assert_eq!(
+    decode_log(&log, &[IERC20_ABI.clone()]),
+    format!("{:#x} @ {:#x}", topic, log.address)
+  );
+
+  let anonymous = transfer_log(vec![], vec![]);
+  assert_eq!(
+    decode_log(&anonymous, &[]),
+    format!("anonymous @ {:#x}", log.address)
+  );
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+
+use crate::indexer::{Holder, TokenTransfer};
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Collection {
+  pub id: u64,
+  pub slug: String,
+  pub name: String,
+  pub description: String,
+  pub chain: Option<String>,
+  pub contract_address: Option<String>,
+  pub base_uri: Option<String>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+/// Editable fields, the body of `PUT /collections/{slug}`.
+#[derive(
+  serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct CollectionFields {
+  pub name: String,
+  pub description: String,
+  pub chain: Option<String>,
+  pub contract_address: Option<String>,
+  pub base_uri: Option<String>,
+}
+
+/// Body of `POST /collections`. The slug is fixed once created since hosted
+/// metadata lives under it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NewCollection {
+  pub slug: String,
+  #[serde(flatten)]
+  pub fields: CollectionFields,
+}
+
+/// Query string of `GET /collections`.
+#[derive(
+  serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq,
+)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::IntoParams),
+  into_params(parameter_in = Query)
+)]
+#[serde(default)]
+pub struct CollectionQuery {
+  /// 1-based page number.
+  pub page: Option<u32>,
+  /// Page size, at most 100 (default 20).
+  pub per_page: Option<u32>,
+  pub chain: Option<String>,
+  pub contract_address: Option<String>,
+  /// Case-insensitive match on name or slug.
+  pub q: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::ToSchema),
+  aliases(
+    CollectionPage = Page<Collection>,
+    HolderPage = Page<Holder>,
+    TransferPage = Page<TokenTransfer>
+  )
+)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub page: u32,
+  pub per_page: u32,
+  pub total: u64,
+}
+
+#[cfg(not(feature = "openapi"))]
+pub type CollectionPage = Page<Collection>;
+#[cfg(not(feature = "openapi"))]
+pub type HolderPage = Page<Holder>;
+#[cfg(not(feature = "openapi"))]
+pub type TransferPage = Page<TokenTransfer>;
@@ -173,6 +173,26 @@ pub mod ierc721 {
                         },
                     ],
                 ),
+                (
+                    ::std::borrow::ToOwned::to_owned("name"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("name"),
+                            inputs: ::std::vec![],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::String,
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("string"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
                 (
                     ::std::borrow::ToOwned::to_owned("ownerOf"),
                     ::std::vec![
@@ -334,6 +354,110 @@ pub mod ierc721 {
                         },
                     ],
                 ),
+                (
+                    ::std::borrow::ToOwned::to_owned("symbol"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("symbol"),
+                            inputs: ::std::vec![],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::String,
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("string"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
+                (
+                    ::std::borrow::ToOwned::to_owned("tokenByIndex"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("tokenByIndex"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("index"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Uint(
+                                        256usize,
+                                    ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("uint256"),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Uint(
+                                        256usize,
+                                    ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("uint256"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
+                (
+                    ::std::borrow::ToOwned::to_owned("tokenURI"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("tokenURI"),
+                            inputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::borrow::ToOwned::to_owned("tokenId"),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Uint(
+                                        256usize,
+                                    ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("uint256"),
+                                    ),
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::String,
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("string"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
+                (
+                    ::std::borrow::ToOwned::to_owned("totalSupply"),
+                    ::std::vec![
+                        ::ethers::core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("totalSupply"),
+                            inputs: ::std::vec![],
+                            outputs: ::std::vec![
+                                ::ethers::core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers::core::abi::ethabi::ParamType::Uint(
+                                        256usize,
+                                    ),
+                                    internal_type: ::core::option::Option::Some(
+                                        ::std::borrow::ToOwned::to_owned("uint256"),
+                                    ),
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers::core::abi::ethabi::StateMutability::View,
+                        },
+                    ],
+                ),
                 (
                     ::std::borrow::ToOwned::to_owned("transferFrom"),
                     ::std::vec![
@@ -552,6 +676,14 @@ pub mod ierc721 {
                 .method_hash([64, 193, 15, 25], (to, token_id))
                 .expect("method not found (this should never happen)")
         }
+        ///Calls the contract's `name` (0x06fdde03) function
+        pub fn name(
+            &self,
+        ) -> ::ethers::contract::builders::ContractCall<M, ::std::string::String> {
+            self.0
+                .method_hash([6, 253, 222, 3], ())
+                .expect("method not found (this should never happen)")
+        }
         ///Calls the contract's `ownerOf` (0x6352211e) function
         pub fn owner_of(
             &self,
@@ -606,6 +738,40 @@ pub mod ierc721 {
                 .method_hash([1, 255, 201, 167], interface_id)
                 .expect("method not found (this should never happen)")
         }
+        ///Calls the contract's `symbol` (0x95d89b41) function
+        pub fn symbol(
+            &self,
+        ) -> ::ethers::contract::builders::ContractCall<M, ::std::string::String> {
+            self.0
+                .method_hash([149, 216, 155, 65], ())
+                .expect("method not found (this should never happen)")
+        }
+        ///Calls the contract's `tokenByIndex` (0x4f6ccce7) function
+        pub fn token_by_index(
+            &self,
+            index: ::ethers::core::types::U256,
+        ) -> ::ethers::contract::builders::ContractCall<M, ::ethers::core::types::U256> {
+            self.0
+                .method_hash([79, 108, 204, 231], index)
+                .expect("method not found (this should never happen)")
+        }
+        ///Calls the contract's `tokenURI` (0xc87b56dd) function
+        pub fn token_uri(
+            &self,
+            token_id: ::ethers::core::types::U256,
+        ) -> ::ethers::contract::builders::ContractCall<M, ::std::string::String> {
+            self.0
+                .method_hash([200, 123, 86, 221], token_id)
+                .expect("method not found (this should never happen)")
+        }
+        ///Calls the contract's `totalSupply` (0x18160ddd) function
+        pub fn total_supply(
+            &self,
+        ) -> ::ethers::contract::builders::ContractCall<M, ::ethers::core::types::U256> {
+            self.0
+                .method_hash([24, 22, 13, 221], ())
+                .expect("method not found (this should never happen)")
+        }
         ///Calls the contract's `transferFrom` (0x23b872dd) function
         pub fn transfer_from(
             &self,
@@ -843,6 +1009,19 @@ pub mod ierc721 {
         pub to: ::ethers::core::types::Address,
         pub token_id: ::ethers::core::types::U256,
     }
+    ///Container type for all input parameters for the `name` function with signature `name()` and selector `0x06fdde03`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "name", abi = "name()")]
+    pub struct NameCall;
     ///Container type for all input parameters for the `ownerOf` function with signature `ownerOf(uint256)` and selector `0x6352211e`
     #[derive(
         Clone,
@@ -930,6 +1109,62 @@ pub mod ierc721 {
     pub struct SupportsInterfaceCall {
         pub interface_id: [u8; 4],
     }
+    ///Container type for all input parameters for the `symbol` function with signature `symbol()` and selector `0x95d89b41`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "symbol", abi = "symbol()")]
+    pub struct SymbolCall;
+    ///Container type for all input parameters for the `tokenByIndex` function with signature `tokenByIndex(uint256)` and selector `0x4f6ccce7`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "tokenByIndex", abi = "tokenByIndex(uint256)")]
+    pub struct TokenByIndexCall {
+        pub index: ::ethers::core::types::U256,
+    }
+    ///Container type for all input parameters for the `tokenURI` function with signature `tokenURI(uint256)` and selector `0xc87b56dd`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "tokenURI", abi = "tokenURI(uint256)")]
+    pub struct TokenURICall {
+        pub token_id: ::ethers::core::types::U256,
+    }
+    ///Container type for all input parameters for the `totalSupply` function with signature `totalSupply()` and selector `0x18160ddd`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthCall,
+        ::ethers::contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "totalSupply", abi = "totalSupply()")]
+    pub struct TotalSupplyCall;
     ///Container type for all input parameters for the `transferFrom` function with signature `transferFrom(address,address,uint256)` and selector `0x23b872dd`
     #[derive(
         Clone,
@@ -955,11 +1190,16 @@ pub mod ierc721 {
         GetApproved(GetApprovedCall),
         IsApprovedForAll(IsApprovedForAllCall),
         Mint(MintCall),
+        Name(NameCall),
         OwnerOf(OwnerOfCall),
         SafeTransferFrom(SafeTransferFromCall),
         SafeTransferFromWithFromAndToAndData(SafeTransferFromWithFromAndToAndDataCall),
         SetApprovalForAll(SetApprovalForAllCall),
         SupportsInterface(SupportsInterfaceCall),
+        Symbol(SymbolCall),
+        TokenByIndex(TokenByIndexCall),
+        TokenURI(TokenURICall),
+        TotalSupply(TotalSupplyCall),
         TransferFrom(TransferFromCall),
     }
     impl ::ethers::core::abi::AbiDecode for IERC721Calls {
@@ -992,6 +1232,11 @@ pub mod ierc721 {
             ) {
                 return Ok(Self::Mint(decoded));
             }
+            if let Ok(decoded) = <NameCall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::Name(decoded));
+            }
             if let Ok(decoded) = <OwnerOfCall as ::ethers::core::abi::AbiDecode>::decode(
                 data,
             ) {
@@ -1017,6 +1262,26 @@ pub mod ierc721 {
             ) {
                 return Ok(Self::SupportsInterface(decoded));
             }
+            if let Ok(decoded) = <SymbolCall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::Symbol(decoded));
+            }
+            if let Ok(decoded) = <TokenByIndexCall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::TokenByIndex(decoded));
+            }
+            if let Ok(decoded) = <TokenURICall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::TokenURI(decoded));
+            }
+            if let Ok(decoded) = <TotalSupplyCall as ::ethers::core::abi::AbiDecode>::decode(
+                data,
+            ) {
+                return Ok(Self::TotalSupply(decoded));
+            }
             if let Ok(decoded) = <TransferFromCall as ::ethers::core::abi::AbiDecode>::decode(
                 data,
             ) {
@@ -1039,6 +1304,7 @@ pub mod ierc721 {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
                 Self::Mint(element) => ::ethers::core::abi::AbiEncode::encode(element),
+                Self::Name(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::OwnerOf(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::SafeTransferFrom(element) => {
                     ::ethers::core::abi::AbiEncode::encode(element)
@@ -1052,6 +1318,10 @@ pub mod ierc721 {
                 Self::SupportsInterface(element) => {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
+                Self::Symbol(element) => ::ethers::core::abi::AbiEncode::encode(element),
+                Self::TokenByIndex(element) => ::ethers::core::abi::AbiEncode::encode(element),
+                Self::TokenURI(element) => ::ethers::core::abi::AbiEncode::encode(element),
+                Self::TotalSupply(element) => ::ethers::core::abi::AbiEncode::encode(element),
                 Self::TransferFrom(element) => {
                     ::ethers::core::abi::AbiEncode::encode(element)
                 }
@@ -1066,6 +1336,7 @@ pub mod ierc721 {
                 Self::GetApproved(element) => ::core::fmt::Display::fmt(element, f),
                 Self::IsApprovedForAll(element) => ::core::fmt::Display::fmt(element, f),
                 Self::Mint(element) => ::core::fmt::Display::fmt(element, f),
+                Self::Name(element) => ::core::fmt::Display::fmt(element, f),
                 Self::OwnerOf(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SafeTransferFrom(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SafeTransferFromWithFromAndToAndData(element) => {
@@ -1073,6 +1344,10 @@ pub mod ierc721 {
                 }
                 Self::SetApprovalForAll(element) => ::core::fmt::Display::fmt(element, f),
                 Self::SupportsInterface(element) => ::core::fmt::Display::fmt(element, f),
+                Self::Symbol(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TokenByIndex(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TokenURI(element) => ::core::fmt::Display::fmt(element, f),
+                Self::TotalSupply(element) => ::core::fmt::Display::fmt(element, f),
                 Self::TransferFrom(element) => ::core::fmt::Display::fmt(element, f),
             }
         }
@@ -1102,6 +1377,11 @@ pub mod ierc721 {
             Self::Mint(value)
         }
     }
+    impl ::core::convert::From<NameCall> for IERC721Calls {
+        fn from(value: NameCall) -> Self {
+            Self::Name(value)
+        }
+    }
     impl ::core::convert::From<OwnerOfCall> for IERC721Calls {
         fn from(value: OwnerOfCall) -> Self {
             Self::OwnerOf(value)
@@ -1128,6 +1408,26 @@ pub mod ierc721 {
             Self::SupportsInterface(value)
         }
     }
+    impl ::core::convert::From<SymbolCall> for IERC721Calls {
+        fn from(value: SymbolCall) -> Self {
+            Self::Symbol(value)
+        }
+    }
+    impl ::core::convert::From<TokenByIndexCall> for IERC721Calls {
+        fn from(value: TokenByIndexCall) -> Self {
+            Self::TokenByIndex(value)
+        }
+    }
+    impl ::core::convert::From<TokenURICall> for IERC721Calls {
+        fn from(value: TokenURICall) -> Self {
+            Self::TokenURI(value)
+        }
+    }
+    impl ::core::convert::From<TotalSupplyCall> for IERC721Calls {
+        fn from(value: TotalSupplyCall) -> Self {
+            Self::TotalSupply(value)
+        }
+    }
     impl ::core::convert::From<TransferFromCall> for IERC721Calls {
         fn from(value: TransferFromCall) -> Self {
             Self::TransferFrom(value)
@@ -1169,6 +1469,18 @@ pub mod ierc721 {
         Hash
     )]
     pub struct IsApprovedForAllReturn(pub bool);
+    ///Container type for all return fields from the `name` function with signature `name()` and selector `0x06fdde03`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct NameReturn(pub ::std::string::String);
     ///Container type for all return fields from the `ownerOf` function with signature `ownerOf(uint256)` and selector `0x6352211e`
     #[derive(
         Clone,
@@ -1193,4 +1505,52 @@ pub mod ierc721 {
         Hash
     )]
     pub struct SupportsInterfaceReturn(pub bool);
+    ///Container type for all return fields from the `symbol` function with signature `symbol()` and selector `0x95d89b41`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct SymbolReturn(pub ::std::string::String);
+    ///Container type for all return fields from the `tokenByIndex` function with signature `tokenByIndex(uint256)` and selector `0x4f6ccce7`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct TokenByIndexReturn(pub ::ethers::core::types::U256);
+    ///Container type for all return fields from the `tokenURI` function with signature `tokenURI(uint256)` and selector `0xc87b56dd`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct TokenURIReturn(pub ::std::string::String);
+    ///Container type for all return fields from the `totalSupply` function with signature `totalSupply()` and selector `0x18160ddd`
+    #[derive(
+        Clone,
+        ::ethers::contract::EthAbiType,
+        ::ethers::contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct TotalSupplyReturn(pub ::ethers::core::types::U256);
 }
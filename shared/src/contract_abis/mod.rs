@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception, unused_imports)]
+pub mod ierc20;
+#[allow(clippy::module_inception, unused_imports)]
+pub mod ierc721;
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+
+/// Which event layout to decode. ERC-20 and ERC-721 share the `Transfer`
+/// and `Approval` signatures but ERC-721 also indexes the token id.
+#[derive(
+  serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStandard {
+  Erc20,
+  Erc721,
+}
+
+impl TokenStandard {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      TokenStandard::Erc20 => "erc20",
+      TokenStandard::Erc721 => "erc721",
+    }
+  }
+}
+
+impl std::str::FromStr for TokenStandard {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "erc20" => Ok(TokenStandard::Erc20),
+      "erc721" => Ok(TokenStandard::Erc721),
+      _ => Err(format!("unknown token standard {}", s)),
+    }
+  }
+}
+
+/// Body of `POST /contracts`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NewIndexedContract {
+  /// One of the backend's `RPC_URLS` names.
+  pub chain: String,
+  pub address: String,
+  pub standard: TokenStandard,
+  /// First block to scan, usually the deployment block.
+  #[serde(default)]
+  pub start_block: u64,
+}
+
+/// A contract whose events the backend indexes. `next_block` is the first
+/// block not yet scanned.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IndexedContract {
+  pub id: u64,
+  pub chain: String,
+  pub address: String,
+  pub standard: TokenStandard,
+  pub start_block: u64,
+  pub next_block: u64,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+/// One `Transfer` log. ERC-721 transfers carry `token_id`, ERC-20 ones
+/// `value`; both as decimal strings since they are `uint256`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct TokenTransfer {
+  pub block_number: u64,
+  pub tx_hash: String,
+  pub log_index: u32,
+  pub from_address: String,
+  pub to_address: String,
+  pub token_id: Option<String>,
+  pub value: Option<String>,
+}
+
+/// Current holder. `balance` is the token count for ERC-721 and the raw
+/// amount for ERC-20, as a decimal string.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Holder {
+  pub address: String,
+  pub balance: String,
+}
+
+/// Query string of the holder and transfer listings.
+#[derive(
+  serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq,
+)]
+#[cfg_attr(
+  feature = "openapi",
+  derive(utoipa::IntoParams),
+  into_params(parameter_in = Query)
+)]
+#[serde(default)]
+pub struct EventQuery {
+  /// 1-based page number.
+  pub page: Option<u32>,
+  /// Page size, at most 100 (default 20).
+  pub per_page: Option<u32>,
+  /// Only transfers from or to this address (transfers only).
+  pub address: Option<String>,
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+
+use crate::metadata::Metadata;
+
+/// Work the backend runs off the request path.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobRequest {
+  /// Numbers `count` copies of `template` as `{name} #{i}`, hashes each
+  /// token and zips them with a CSV and manifest. With `slug`, every token
+  /// is also stored as hosted metadata of that collection.
+  Generate {
+    project_name: String,
+    template: Metadata,
+    count: u32,
+    #[serde(default)]
+    slug: Option<String>,
+  },
+}
+
+#[derive(
+  serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+  Queued,
+  Running,
+  Done,
+  Failed,
+}
+
+impl JobStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      JobStatus::Queued => "queued",
+      JobStatus::Running => "running",
+      JobStatus::Done => "done",
+      JobStatus::Failed => "failed",
+    }
+  }
+
+  pub fn is_finished(&self) -> bool {
+    matches!(self, JobStatus::Done | JobStatus::Failed)
+  }
+}
+
+impl std::str::FromStr for JobStatus {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "queued" => Ok(JobStatus::Queued),
+      "running" => Ok(JobStatus::Running),
+      "done" => Ok(JobStatus::Done),
+      "failed" => Ok(JobStatus::Failed),
+      _ => Err(format!("unknown job status {}", s)),
+    }
+  }
+}
+
+/// Body of `GET /jobs/{id}`. `progress` counts finished units out of `total`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Job {
+  pub id: u64,
+  pub kind: String,
+  pub status: JobStatus,
+  pub progress: u32,
+  pub total: u32,
+  pub error: Option<String>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
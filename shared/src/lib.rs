@@ -0,0 +1,13 @@
+//! Types that cross the wire between `front` and `back`.
+//!
+//! Everything here must build for `wasm32-unknown-unknown`; backend-only
+//! derives sit behind the `openapi` and `sqlx` features, and the generated
+//! contract bindings behind `contracts`.
+
+pub mod collection;
+#[cfg(feature = "contracts")]
+pub mod contract_abis;
+pub mod indexer;
+pub mod job;
+pub mod metadata;
+pub mod reveal;
@@ -0,0 +1,49 @@
+/// ERC-721 token metadata as produced by the metadata window and hosted by
+/// the backend.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct Metadata {
+  pub name: String,
+  pub description: String,
+  pub image: String,
+  pub attributes: Vec<Attribute>,
+}
+
+impl Default for Metadata {
+  fn default() -> Self {
+    Self {
+      name: "".to_string(),
+      description: "".to_string(),
+      image: "".to_string(),
+      attributes: Vec::new(),
+    }
+  }
+}
+
+/// Token `token_id` of a collection generated from `template`: the template
+/// named `{name} #{token_id}`. The metadata window and the backend's generate
+/// job both number tokens this way.
+pub fn numbered(template: &Metadata, token_id: u32) -> Metadata {
+  let mut metadata = template.clone();
+  metadata.name = format!("{} #{}", template.name, token_id);
+  metadata
+}
+
+/// One trait. Values are always strings for now.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct Attribute {
+  pub trait_type: String,
+  pub value: String,
+}
+
+impl Default for Attribute {
+  fn default() -> Self {
+    Self {
+      trait_type: "".to_string(),
+      value: "".to_string(),
+    }
+  }
+}
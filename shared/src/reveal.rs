@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+
+/// Per-collection delayed reveal.
+///
+/// Until the collection is revealed every token resolves to the placeholder
+/// JSON. With `require_minted`, real metadata is only served for tokens that
+/// `ownerOf` resolves on `chain` for `contract_address`.
+#[derive(
+  serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(default)]
+pub struct RevealSettings {
+  pub revealed: bool,
+  pub reveal_at: Option<DateTime<Utc>>,
+
+  pub require_minted: bool,
+  pub chain: Option<String>,
+  pub contract_address: Option<String>,
+}
+
+impl RevealSettings {
+  pub fn is_revealed_at(&self, now: DateTime<Utc>) -> bool {
+    self.revealed || self.reveal_at.is_some_and(|reveal_at| reveal_at <= now)
+  }
+}
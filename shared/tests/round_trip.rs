@@ -0,0 +1,208 @@
+use chrono::{TimeZone, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use shared::collection::{
+  Collection, CollectionFields, CollectionPage, CollectionQuery, NewCollection,
+  Page,
+};
+use shared::job::{JobRequest, JobStatus};
+use shared::metadata::{Attribute, Metadata};
+use shared::reveal::RevealSettings;
+
+/// Serializes `value`, checks the JSON is exactly `expected`, and checks it
+/// decodes back to `value`.
+fn round_trip<T>(value: &T, expected: serde_json::Value)
+where
+  T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+  let encoded = serde_json::to_value(value).unwrap();
+  assert_eq!(encoded, expected);
+
+  let decoded: T = serde_json::from_value(encoded).unwrap();
+  assert_eq!(&decoded, value);
+}
+
+fn collection() -> Collection {
+  Collection {
+    id: 7,
+    slug: "drop".to_string(),
+    name: "Drop".to_string(),
+    description: "".to_string(),
+    chain: Some("sepolia".to_string()),
+    contract_address: None,
+    base_uri: None,
+    created_at: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+    updated_at: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+  }
+}
+
+#[test]
+fn metadata() {
+  let metadata = Metadata {
+    name: "Toy #0".to_string(),
+    description: "A toy".to_string(),
+    image: "ipfs://cid/0.png".to_string(),
+    attributes: vec![Attribute {
+      trait_type: "Color".to_string(),
+      value: "Red".to_string(),
+    }],
+  };
+
+  round_trip(
+    &metadata,
+    json!({
+      "name": "Toy #0",
+      "description": "A toy",
+      "image": "ipfs://cid/0.png",
+      "attributes": [{"trait_type": "Color", "value": "Red"}],
+    }),
+  );
+}
+
+#[test]
+fn metadata_fills_missing_fields() {
+  let metadata: Metadata =
+    serde_json::from_value(json!({"name": "x"})).unwrap();
+
+  assert_eq!(
+    metadata,
+    Metadata {
+      name: "x".to_string(),
+      ..Metadata::default()
+    }
+  );
+}
+
+#[test]
+fn collection_page() {
+  let page: CollectionPage = Page {
+    items: vec![collection()],
+    page: 1,
+    per_page: 20,
+    total: 1,
+  };
+
+  round_trip(
+    &page,
+    json!({
+      "items": [{
+        "id": 7,
+        "slug": "drop",
+        "name": "Drop",
+        "description": "",
+        "chain": "sepolia",
+        "contract_address": null,
+        "base_uri": null,
+        "created_at": "2024-01-02T03:04:05Z",
+        "updated_at": "2024-01-02T03:04:05Z",
+      }],
+      "page": 1,
+      "per_page": 20,
+      "total": 1,
+    }),
+  );
+}
+
+#[test]
+fn new_collection_is_flat() {
+  let new = NewCollection {
+    slug: "drop".to_string(),
+    fields: CollectionFields {
+      name: "Drop".to_string(),
+      ..CollectionFields::default()
+    },
+  };
+
+  round_trip(
+    &new,
+    json!({
+      "slug": "drop",
+      "name": "Drop",
+      "description": "",
+      "chain": null,
+      "contract_address": null,
+      "base_uri": null,
+    }),
+  );
+}
+
+#[test]
+fn collection_query() {
+  let query = CollectionQuery {
+    page: Some(2),
+    q: Some("toy".to_string()),
+    ..CollectionQuery::default()
+  };
+
+  round_trip(
+    &query,
+    json!({
+      "page": 2,
+      "per_page": null,
+      "chain": null,
+      "contract_address": null,
+      "q": "toy",
+    }),
+  );
+}
+
+#[test]
+fn reveal_settings() {
+  let settings = RevealSettings {
+    reveal_at: Some(Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap()),
+    require_minted: true,
+    chain: Some("sepolia".to_string()),
+    contract_address: Some(
+      "0x0000000000000000000000000000000000000001".to_string(),
+    ),
+    ..RevealSettings::default()
+  };
+
+  round_trip(
+    &settings,
+    json!({
+      "revealed": false,
+      "reveal_at": "2024-06-01T00:00:00Z",
+      "require_minted": true,
+      "chain": "sepolia",
+      "contract_address": "0x0000000000000000000000000000000000000001",
+    }),
+  );
+}
+
+#[test]
+fn job_request_is_tagged_by_kind() {
+  let request = JobRequest::Generate {
+    project_name: "toys".to_string(),
+    template: Metadata::default(),
+    count: 10,
+    slug: None,
+  };
+
+  round_trip(
+    &request,
+    json!({
+      "kind": "generate",
+      "project_name": "toys",
+      "template": {"name": "", "description": "", "image": "", "attributes": []},
+      "count": 10,
+      "slug": null,
+    }),
+  );
+}
+
+#[test]
+fn job_status_is_snake_case() {
+  for status in [
+    JobStatus::Queued,
+    JobStatus::Running,
+    JobStatus::Done,
+    JobStatus::Failed,
+  ] {
+    assert_eq!(
+      serde_json::to_value(status).unwrap(),
+      json!(status.as_str())
+    );
+    assert_eq!(status.as_str().parse::<JobStatus>(), Ok(status));
+  }
+}